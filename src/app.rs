@@ -1,20 +1,49 @@
-use std::sync::Arc;
-use tokio::spawn;
+use chrono::Local;
+use ratatui::crossterm::event::KeyEvent;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::{spawn, sync::Semaphore, task::JoinHandle};
 
 use crate::{
-    client::Client,
+    arn::AccessPointArn,
+    client::{parse_path, Client},
     config::Config,
     error::{AppError, Result},
+    etag::{verify_md5_etag, EtagCheck},
     event::{
-        AppEventType, CompleteDownloadObjectResult, CompleteInitializeResult,
-        CompleteLoadObjectResult, CompleteLoadObjectsResult, CompletePreviewObjectResult, Sender,
+        AppEventType, CompleteDownloadObjectResult, CompleteDownloadPrefixResult,
+        CompleteInitializeResult, CompleteLoadBucketAccessResult, CompleteLoadObjectResult,
+        CompleteLoadObjectsResult, CompleteLoadVersionDiffResult, CompletePreviewObjectResult,
+        CompleteRestoreObjectResult, Sender,
+    },
+    file::{
+        copy_to_clipboard, is_downloaded, next_available_file_name, prefix_download_path,
+        save_binary, save_error_log,
+    },
+    if_match, key_code_char,
+    object::{
+        AppObjects, BucketItem, FileDetail, FileVersion, ObjectItem, ObjectItemsDiff, ObjectKey,
+        PreviewPosition, RawObject, RestoreStatus, RestoreTier, StorageClass,
     },
-    file::{copy_to_clipboard, save_binary, save_error_log},
-    if_match,
-    object::{AppObjects, BucketItem, FileDetail, ObjectItem, ObjectKey, RawObject},
     pages::page::{Page, PageStack},
+    recent::RecentObjects,
+    ui::common::format_size_byte,
+    util::{format_duration, ThroughputMeter},
+    widget::{
+        BucketAccessDialogState, CommandPaletteState, Hyperlink, RegionPickerState,
+        VersionDiffDialogState,
+    },
 };
 
+const MAX_CONCURRENT_PREFETCH: usize = 8;
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(3);
+const MAX_COLLAPSE_DEPTH: usize = 32;
+const PRESIGN_EXPIRES_IN: Duration = Duration::from_secs(60 * 60);
+
 #[derive(Debug)]
 pub enum Notification {
     None,
@@ -28,6 +57,8 @@ pub enum Notification {
 pub struct AppViewState {
     pub notification: Notification,
     pub is_loading: bool,
+    pub command_palette: Option<CommandPaletteState>,
+    pub region_picker: Option<RegionPickerState>,
 
     width: usize,
     height: usize,
@@ -38,6 +69,8 @@ impl AppViewState {
         AppViewState {
             notification: Notification::None,
             is_loading: true,
+            command_palette: None,
+            region_picker: None,
             width,
             height,
         }
@@ -56,23 +89,64 @@ pub struct App {
     app_objects: AppObjects,
     client: Option<Arc<Client>>,
     config: Config,
+    prefetch_tasks: HashMap<ObjectKey, JoinHandle<()>>,
+    prefetch_semaphore: Arc<Semaphore>,
+    // the objects whose prefetch failed (e.g. access denied)
+    prefetch_failed: HashSet<ObjectKey>,
+    reveal_target: Option<RevealTarget>,
+    objects_loads: HashMap<ObjectKey, ObjectsLoad>,
+    next_objects_load_id: u64,
+    recent_objects: RecentObjects,
+    // the bucket given by the argument, loaded again when the region is switched
+    bucket: Option<String>,
+    // descend automatically through the folders that contain only one folder
+    collapse_dirs: bool,
     tx: Sender,
 }
 
+#[derive(Debug)]
+struct ObjectsLoad {
+    id: u64,
+    // whether the object list page has been opened before the listing completed
+    streamed: bool,
+}
+
+#[derive(Debug)]
+struct RevealTarget {
+    // remaining names to follow from the current object list
+    path: VecDeque<String>,
+    dir: bool,
+    // set if opened from the recent objects, to open the detail of the object
+    recent: Option<(String, String)>,
+}
+
 impl App {
     pub fn new(config: Config, tx: Sender, width: usize, height: usize) -> App {
+        let recent_objects = Config::recent_objects_file_path()
+            .map(RecentObjects::load)
+            .unwrap_or_default();
         App {
             app_view_state: AppViewState::new(width, height),
             app_objects: AppObjects::default(),
-            page_stack: PageStack::new(tx.clone()),
+            page_stack: PageStack::new(tx.clone(), config.wrap_selection),
             client: None,
             config,
+            prefetch_tasks: HashMap::new(),
+            prefetch_semaphore: Arc::new(Semaphore::new(MAX_CONCURRENT_PREFETCH)),
+            prefetch_failed: HashSet::new(),
+            reveal_target: None,
+            objects_loads: HashMap::new(),
+            next_objects_load_id: 0,
+            recent_objects,
+            bucket: None,
+            collapse_dirs: false,
             tx,
         }
     }
 
     pub fn initialize(&mut self, client: Client, bucket: Option<String>) {
         self.client = Some(Arc::new(client));
+        self.bucket.clone_from(&bucket);
 
         let (client, tx) = self.unwrap_client_tx();
         spawn(async move {
@@ -108,6 +182,18 @@ impl App {
         }
     }
 
+    pub fn is_anonymous(&self) -> bool {
+        self.client.as_ref().is_some_and(|c| c.is_anonymous())
+    }
+
+    pub fn is_requester_pays(&self) -> bool {
+        self.client.as_ref().is_some_and(|c| c.is_requester_pays())
+    }
+
+    pub fn region(&self) -> Option<String> {
+        self.client.as_ref().map(|c| c.region().to_string())
+    }
+
     pub fn resize(&mut self, width: usize, height: usize) {
         self.app_view_state.reset_size(width, height);
     }
@@ -117,6 +203,19 @@ impl App {
         bucket_page.current_selected_item().name.clone()
     }
 
+    pub fn hyperlinks(&self) -> &[Hyperlink] {
+        if self.app_view_state.command_palette.is_some()
+            || self.app_view_state.region_picker.is_some()
+        {
+            // writing the links would move the cursor of the input
+            return &[];
+        }
+        match self.page_stack.current_page() {
+            Page::ObjectPreview(page) => page.hyperlinks(),
+            _ => &[],
+        }
+    }
+
     fn current_path(&self) -> Vec<&str> {
         self.page_stack
             .iter()
@@ -142,6 +241,14 @@ impl App {
         }
     }
 
+    /// Returns the location of the current object list page.
+    ///
+    /// Unlike `current_object_key`, the folder selected on the page is not included.
+    fn current_list_object_key(&self) -> ObjectKey {
+        let page = self.page_stack.current_page().as_object_list();
+        page.object_key().clone()
+    }
+
     fn current_object_key_with_name(&self, name: String) -> ObjectKey {
         let mut object_path: Vec<String> =
             self.current_path().iter().map(|s| s.to_string()).collect();
@@ -164,8 +271,13 @@ impl App {
     pub fn bucket_list_move_down(&mut self) {
         if let Some(current_object_items) = self.current_object_items() {
             // object list has been already loaded
-            let object_list_page = Page::of_object_list(current_object_items, self.tx.clone());
+            let object_list_page = Page::of_object_list(
+                current_object_items,
+                self.current_object_key(),
+                self.tx.clone(),
+            );
             self.page_stack.push(object_list_page);
+            self.collapse_single_dir();
         } else {
             self.tx.send(AppEventType::LoadObjects);
             self.app_view_state.is_loading = true;
@@ -187,11 +299,14 @@ impl App {
                     let object_detail_page = Page::of_object_detail(
                         detail.clone(),
                         versions.clone(),
+                        object_page.object_key().bucket_name.clone(),
                         object_page.object_list(),
                         object_page.list_state(),
+                        self.config.layout.list_width_percentage,
                         self.tx.clone(),
                     );
                     self.page_stack.push(object_detail_page);
+                    self.record_recent_object(&name);
                 } else {
                     self.tx.send(AppEventType::LoadObject);
                     self.app_view_state.is_loading = true;
@@ -200,9 +315,13 @@ impl App {
             ObjectItem::Dir { .. } => {
                 if let Some(current_object_items) = self.current_object_items() {
                     // object list has been already loaded
-                    let object_list_page =
-                        Page::of_object_list(current_object_items, self.tx.clone());
+                    let object_list_page = Page::of_object_list(
+                        current_object_items,
+                        self.current_object_key(),
+                        self.tx.clone(),
+                    );
                     self.page_stack.push(object_list_page);
+                    self.collapse_single_dir();
                 } else {
                     self.tx.send(AppEventType::LoadObjects);
                     self.app_view_state.is_loading = true;
@@ -212,11 +331,205 @@ impl App {
     }
 
     pub fn object_list_move_up(&mut self) {
-        if self.page_stack.len() == 2 /* bucket list and object list */ && self.bucket_items().len() == 1
-        {
+        if self.is_single_bucket_root() {
             return;
         }
         self.page_stack.pop();
+        // the folders passed through by collapsing are stepped out at once
+        while self.current_object_list_collapsed() && !self.is_single_bucket_root() {
+            self.page_stack.pop();
+        }
+        self.uncollapse_current_page();
+    }
+
+    pub fn object_list_move_up_to(&mut self, depth: usize) {
+        // the pages of the ancestors are kept in the stack, so they can be reused without reloading
+        let page_len = depth + 2 /* bucket list and object list of the bucket root */;
+        while self.page_stack.len() > page_len {
+            self.page_stack.pop();
+        }
+        self.uncollapse_current_page();
+    }
+
+    fn is_single_bucket_root(&self) -> bool {
+        self.page_stack.len() == 2 /* bucket list and object list */ && self.bucket_items().len() == 1
+    }
+
+    fn current_object_list_collapsed(&self) -> bool {
+        matches!(self.page_stack.current_page(), Page::ObjectList(page) if page.is_collapsed())
+    }
+
+    fn uncollapse_current_page(&mut self) {
+        if let Page::ObjectList(page) = self.page_stack.current_page_mut() {
+            page.set_collapsed(false);
+        }
+    }
+
+    pub fn toggle_collapse_dirs(&mut self) {
+        self.collapse_dirs = !self.collapse_dirs;
+        let msg = if self.collapse_dirs {
+            "Collapsing single folders enabled"
+        } else {
+            "Collapsing single folders disabled"
+        };
+        self.tx.send(AppEventType::NotifyInfo(msg.into()));
+    }
+
+    /// Descends into the only folder of the current object list, chaining until a folder with other contents.
+    fn collapse_single_dir(&mut self) {
+        if !self.collapse_dirs || self.reveal_target.is_some() {
+            return;
+        }
+        let collapsed_depth = self
+            .page_stack
+            .iter()
+            .rev()
+            .skip(1)
+            .take_while(|page| matches!(page, Page::ObjectList(p) if p.is_collapsed()))
+            .count();
+        if collapsed_depth >= MAX_COLLAPSE_DEPTH {
+            return;
+        }
+        let Page::ObjectList(page) = self.page_stack.current_page_mut() else {
+            return;
+        };
+        if !page.has_single_dir() {
+            return;
+        }
+        page.set_collapsed(true);
+        self.object_list_move_down();
+    }
+
+    /// Navigates to the prefix containing `key` (relative to the bucket root) and selects it.
+    ///
+    /// If a part of the key no longer exists, the nearest existing prefix is shown.
+    pub fn reveal_object(&mut self, key: String) {
+        let dir = key.ends_with('/');
+        let path = parse_path(&key, dir);
+        if path.iter().any(|p| p.is_empty()) {
+            let msg = format!("Invalid key: '{}'", key);
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+
+        self.object_list_move_up_to(0);
+        self.reveal_target = Some(RevealTarget {
+            path: path.into(),
+            dir,
+            recent: None,
+        });
+        self.reveal_next();
+    }
+
+    pub fn open_recent_objects(&mut self) {
+        let items = self.recent_objects.items().to_vec();
+        let recent_objects_page = Page::of_recent_objects(items, self.tx.clone());
+        self.page_stack.push(recent_objects_page);
+    }
+
+    /// Navigates to the object from the bucket list, and opens its detail.
+    pub fn open_recent_object(&mut self, bucket: String, key: String) {
+        // the current pages are kept if the object cannot be opened
+        if !self
+            .page_stack
+            .head()
+            .as_bucket_list()
+            .has_item_by_name(&bucket)
+        {
+            let msg = format!("Bucket '{}' is not available", bucket);
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+
+        self.page_stack.clear();
+        let bucket_page = self.page_stack.current_page_mut().as_mut_bucket_list();
+        bucket_page.select_item_by_name(&bucket);
+
+        self.reveal_target = Some(RevealTarget {
+            path: parse_path(&key, false).into(),
+            dir: false,
+            recent: Some((bucket, key)),
+        });
+        if let Some(current_object_items) = self.current_object_items() {
+            let object_list_page = Page::of_object_list(
+                current_object_items,
+                self.current_object_key(),
+                self.tx.clone(),
+            );
+            self.page_stack.push(object_list_page);
+            self.reveal_next();
+        } else {
+            // continues after the objects are loaded
+            self.tx.send(AppEventType::LoadObjects);
+            self.app_view_state.is_loading = true;
+        }
+    }
+
+    pub fn remove_recent_object(&mut self, bucket: String, key: String) {
+        self.recent_objects.remove(&bucket, &key);
+        self.save_recent_objects();
+    }
+
+    fn record_recent_object(&mut self, name: &str) {
+        let bucket = self.current_bucket();
+        let key = format!("{}{}", self.current_object_prefix(), name);
+        self.recent_objects.add(bucket, key, Local::now());
+        self.save_recent_objects();
+    }
+
+    fn save_recent_objects(&self) {
+        if let Err(e) = self.recent_objects.save() {
+            self.tx.send(AppEventType::NotifyError(e));
+        }
+    }
+
+    fn reveal_next(&mut self) {
+        while let Some(target) = self.reveal_target.as_mut() {
+            let Some(name) = target.path.pop_front() else {
+                self.reveal_target = None;
+                return;
+            };
+            let last = target.path.is_empty();
+            let dir = !last || target.dir;
+
+            let page = self.page_stack.current_page_mut().as_mut_object_list();
+            if !page.select_item_by_name(&name, dir) {
+                let msg = match self.reveal_target.take().and_then(|t| t.recent) {
+                    Some((bucket, key)) => {
+                        self.recent_objects.mark_stale(&bucket, &key);
+                        self.save_recent_objects();
+                        format!(
+                            "'{}' no longer exists, marked as deleted in recent objects",
+                            key
+                        )
+                    }
+                    None => format!("'{}' no longer exists, showing the nearest folder", name),
+                };
+                self.tx.send(AppEventType::NotifyWarn(msg));
+                return;
+            }
+            if last {
+                let recent = self.reveal_target.take().and_then(|t| t.recent);
+                if recent.is_some() {
+                    self.object_list_move_down();
+                }
+                return;
+            }
+
+            if let Some(current_object_items) = self.current_object_items() {
+                let object_list_page = Page::of_object_list(
+                    current_object_items,
+                    self.current_object_key(),
+                    self.tx.clone(),
+                );
+                self.page_stack.push(object_list_page);
+            } else {
+                // continues after the objects are loaded
+                self.tx.send(AppEventType::LoadObjects);
+                self.app_view_state.is_loading = true;
+                return;
+            }
+        }
     }
 
     pub fn back_to_bucket_list(&mut self) {
@@ -226,31 +539,130 @@ impl App {
         self.page_stack.clear();
     }
 
-    pub fn load_objects(&self) {
+    pub fn load_objects(&mut self) {
         let bucket = self.current_bucket();
         let prefix = self.current_object_prefix();
+        let object_key = self.current_object_key();
+
+        // a newer load for the same location supersedes the older one
+        let load_id = self.next_objects_load_id;
+        self.next_objects_load_id += 1;
+        let load = ObjectsLoad {
+            id: load_id,
+            streamed: false,
+        };
+        self.objects_loads.insert(object_key.clone(), load);
+
+        // revealing an object needs the complete listing to select the item
+        let stream = self.reveal_target.is_none();
+
         let (client, tx) = self.unwrap_client_tx();
         spawn(async move {
-            let items = client.load_objects(&bucket, &prefix).await;
+            let items = client
+                .load_objects(&bucket, &prefix, |items| {
+                    if stream {
+                        let page =
+                            AppEventType::LoadObjectsPage(object_key.clone(), load_id, items);
+                        tx.send(page);
+                    }
+                })
+                .await;
             let result = CompleteLoadObjectsResult::new(items);
-            tx.send(AppEventType::CompleteLoadObjects(result));
+            tx.send(AppEventType::CompleteLoadObjects(
+                object_key, load_id, result,
+            ));
         });
     }
 
-    pub fn complete_load_objects(&mut self, result: Result<CompleteLoadObjectsResult>) {
+    pub fn load_objects_page(
+        &mut self,
+        object_key: ObjectKey,
+        load_id: u64,
+        items: Vec<ObjectItem>,
+    ) {
+        let Some(load) = self.objects_loads.get_mut(&object_key) else {
+            return;
+        };
+        if load.id != load_id {
+            return;
+        }
+
+        let loaded = if load.streamed {
+            match self.page_stack.find_object_list_mut(&object_key) {
+                Some(page) => {
+                    page.append_items(items);
+                    page.object_list_len()
+                }
+                // the page has been closed while loading
+                None => return,
+            }
+        } else {
+            // the first page, the user has been waiting for it
+            load.streamed = true;
+            let loaded = items.len();
+            let object_list_page = Page::of_object_list(items, object_key, self.tx.clone());
+            self.page_stack.push(object_list_page);
+            self.app_view_state.is_loading = false;
+            loaded
+        };
+
+        let msg = format!("Loaded {} objects\u{2026}", loaded);
+        self.tx.send(AppEventType::NotifyInfo(msg));
+    }
+
+    pub fn complete_load_objects(
+        &mut self,
+        object_key: ObjectKey,
+        load_id: u64,
+        result: Result<CompleteLoadObjectsResult>,
+    ) {
+        let streamed = match self.objects_loads.get(&object_key) {
+            Some(load) if load.id == load_id => load.streamed,
+            _ => return,
+        };
+        self.objects_loads.remove(&object_key);
+
+        if streamed {
+            // the page is already open and the user may have moved elsewhere
+            match result {
+                Ok(CompleteLoadObjectsResult { items }) => {
+                    let loaded = items.len();
+                    self.app_objects
+                        .set_object_items(object_key.clone(), items.clone());
+                    if let Some(page) = self.page_stack.find_object_list_mut(&object_key) {
+                        page.set_items(items);
+                    }
+                    let msg = format!("Loaded {} objects", loaded);
+                    self.tx.send(AppEventType::NotifyInfo(msg));
+                }
+                Err(e) => {
+                    self.tx.send(AppEventType::NotifyError(e));
+                }
+            }
+            return;
+        }
+
         match result {
             Ok(CompleteLoadObjectsResult { items }) => {
                 self.app_objects
-                    .set_object_items(self.current_object_key().to_owned(), items.clone());
+                    .set_object_items(object_key.clone(), items.clone());
 
-                let object_list_page = Page::of_object_list(items, self.tx.clone());
+                let object_list_page = Page::of_object_list(items, object_key, self.tx.clone());
                 self.page_stack.push(object_list_page);
             }
             Err(e) => {
+                self.reveal_target = None;
+                // stop collapsing at the folder that failed to load
+                self.uncollapse_current_page();
                 self.tx.send(AppEventType::NotifyError(e));
+                self.app_view_state.is_loading = false;
+                return;
             }
         }
         self.app_view_state.is_loading = false;
+
+        self.collapse_single_dir();
+        self.reveal_next();
     }
 
     pub fn load_object(&self) {
@@ -268,13 +680,24 @@ impl App {
             let key = format!("{}{}", prefix, name);
 
             let map_key = self.current_object_key_with_name(name.to_string());
+            // the detail may have been prefetched
+            let cached_detail = self.app_objects.get_object_detail(&map_key).cloned();
 
             let (client, tx) = self.unwrap_client_tx();
             spawn(async move {
-                let detail = client
-                    .load_object_detail(&bucket, &key, &name, size_byte)
-                    .await;
-                let versions = client.load_object_versions(&bucket, &key).await;
+                let detail = match cached_detail {
+                    Some(detail) => Ok(detail),
+                    None => {
+                        client
+                            .load_object_detail(&bucket, &key, &name, size_byte, None)
+                            .await
+                    }
+                };
+                // the versions are not needed if the detail cannot be read
+                let versions = match detail {
+                    Ok(_) => client.load_object_versions(&bucket, &key).await,
+                    Err(_) => Ok(Vec::new()),
+                };
                 let result = CompleteLoadObjectResult::new(detail, versions, map_key);
                 tx.send(AppEventType::CompleteLoadObject(result));
             });
@@ -290,18 +713,40 @@ impl App {
             }) => {
                 self.app_objects
                     .set_object_details(map_key, *detail.clone(), versions.clone());
+                self.record_recent_object(&detail.name);
 
                 let object_page = self.page_stack.current_page().as_object_list();
 
                 let object_detail_page = Page::of_object_detail(
                     *detail.clone(),
                     versions.clone(),
+                    object_page.object_key().bucket_name.clone(),
                     object_page.object_list(),
                     object_page.list_state(),
+                    self.config.layout.list_width_percentage,
                     self.tx.clone(),
                 );
                 self.page_stack.push(object_detail_page);
             }
+            Err(e) if e.is_access_denied() => {
+                // the object is listed but its detail cannot be read, which is not an error of the app
+                let object_page = self.page_stack.current_page().as_object_list();
+                let key = format!(
+                    "{}{}",
+                    self.current_object_prefix(),
+                    object_page.current_selected_item().name()
+                );
+
+                let object_denied_page = Page::of_object_denied(
+                    key,
+                    e,
+                    object_page.object_list(),
+                    object_page.list_state(),
+                    self.config.layout.list_width_percentage,
+                    self.tx.clone(),
+                );
+                self.page_stack.push(object_denied_page);
+            }
             Err(e) => {
                 self.tx.send(AppEventType::NotifyError(e));
             }
@@ -309,89 +754,121 @@ impl App {
         self.app_view_state.is_loading = false;
     }
 
-    pub fn open_help(&mut self) {
-        let helps = match self.page_stack.current_page() {
-            Page::Initializing(_) | Page::Help(_) => {
-                return;
+    /// Reloads the current view from S3, bypassing the cached items.
+    pub fn refresh(&mut self) {
+        match self.page_stack.current_page() {
+            Page::ObjectList(_) => self.refresh_objects(),
+            Page::ObjectDetail(_) => self.refresh_object(),
+            Page::ObjectPreview(_) => {
+                let page = self.page_stack.current_page_mut().as_mut_object_preview();
+                page.refresh();
             }
-            Page::BucketList(page) => page.helps(),
-            Page::ObjectList(page) => page.helps(),
-            Page::ObjectDetail(page) => page.helps(),
-            Page::ObjectPreview(page) => page.helps(),
-        };
-        let help_page = Page::of_help(helps, self.tx.clone());
-        self.page_stack.push(help_page);
-    }
-
-    pub fn close_current_page(&mut self) {
-        self.page_stack.pop();
+            _ => {
+                let msg = "Nothing to refresh on this page".to_string();
+                self.tx.send(AppEventType::NotifyWarn(msg));
+            }
+        }
     }
 
-    pub fn detail_download_object(&mut self, file_detail: FileDetail, version_id: Option<String>) {
-        self.tx
-            .send(AppEventType::DownloadObject(file_detail, version_id));
+    fn refresh_objects(&mut self) {
+        let object_key = self.current_list_object_key();
+        if self.objects_loads.contains_key(&object_key) {
+            let msg = "Objects are still loading".to_string();
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
         self.app_view_state.is_loading = true;
-    }
 
-    pub fn preview_download_object(&self, obj: RawObject, path: String) {
-        let result = CompleteDownloadObjectResult::new(Ok(obj), path);
-        self.tx.send(AppEventType::CompleteDownloadObject(result));
+        let bucket = object_key.bucket_name.clone();
+        let prefix = object_key.prefix();
+
+        let (client, tx) = self.unwrap_client_tx();
+        spawn(async move {
+            let items = client.load_objects(&bucket, &prefix, |_| {}).await;
+            let result = CompleteLoadObjectsResult::new(items);
+            tx.send(AppEventType::CompleteRefreshObjects(object_key, result));
+        });
     }
 
-    pub fn open_preview(&mut self, file_detail: FileDetail, version_id: Option<String>) {
-        self.tx
-            .send(AppEventType::PreviewObject(file_detail, version_id));
-        self.app_view_state.is_loading = true;
+    pub fn complete_refresh_objects(
+        &mut self,
+        object_key: ObjectKey,
+        result: Result<CompleteLoadObjectsResult>,
+    ) {
+        match result {
+            Ok(CompleteLoadObjectsResult { items }) => {
+                let loaded = items.len();
+                let diff = self
+                    .app_objects
+                    .get_object_items(&object_key)
+                    .map(|old_items| ObjectItemsDiff::new(&old_items, &items))
+                    .unwrap_or_default();
+                let msg = if diff.is_empty() {
+                    format!("Reloaded {} objects, no changes", loaded)
+                } else {
+                    format!(
+                        "Reloaded {} objects (+{} / -{})",
+                        loaded,
+                        diff.added.len(),
+                        diff.removed.len()
+                    )
+                };
+                // the objects may have been changed, so they are loaded again when opened
+                self.app_objects.remove_object_details_under(&object_key);
+                self.prefetch_failed
+                    .retain(|key| !key.is_item_of(&object_key));
+                self.app_objects
+                    .set_object_items(object_key.clone(), items.clone());
+                if let Some(page) = self.page_stack.find_object_list_mut(&object_key) {
+                    page.set_refreshed_items(items, diff);
+                }
+                self.tx.send(AppEventType::NotifyInfo(msg));
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.app_view_state.is_loading = false;
     }
 
-    pub fn download_object(&self, file_detail: FileDetail, version_id: Option<String>) {
-        let object_name = file_detail.name;
+    fn refresh_object(&mut self) {
+        let page = self.page_stack.current_page().as_object_detail();
+        let file_detail = page.file_detail();
+        let name = file_detail.name.clone();
         let size_byte = file_detail.size_byte;
 
-        self.download_object_and(
-            &object_name,
-            size_byte,
-            None,
-            version_id,
-            |tx, obj, path| {
-                let result = CompleteDownloadObjectResult::new(obj, path);
-                tx.send(AppEventType::CompleteDownloadObject(result));
-            },
-        )
-    }
+        self.app_view_state.is_loading = true;
 
-    pub fn download_object_as(
-        &self,
-        file_detail: FileDetail,
-        input: String,
-        version_id: Option<String>,
-    ) {
-        let object_name = file_detail.name;
-        let size_byte = file_detail.size_byte;
+        let bucket = self.current_bucket();
+        let prefix = self.current_object_prefix();
+        let key = format!("{}{}", prefix, name);
+        let map_key = self.current_object_key_with_name(name.clone());
 
-        self.download_object_and(
-            &object_name,
-            size_byte,
-            Some(&input),
-            version_id,
-            |tx, obj, path| {
-                let result = CompleteDownloadObjectResult::new(obj, path);
-                tx.send(AppEventType::CompleteDownloadObject(result));
-            },
-        )
+        let (client, tx) = self.unwrap_client_tx();
+        spawn(async move {
+            let detail = client
+                .load_object_detail(&bucket, &key, &name, size_byte, None)
+                .await;
+            let versions = client.load_object_versions(&bucket, &key).await;
+            let result = CompleteLoadObjectResult::new(detail, versions, map_key);
+            tx.send(AppEventType::CompleteRefreshObject(result));
+        });
     }
 
-    pub fn complete_download_object(&mut self, result: Result<CompleteDownloadObjectResult>) {
-        let result = match result {
-            Ok(CompleteDownloadObjectResult { obj, path }) => {
-                save_binary(&path, &obj.bytes).map(|_| path)
-            }
-            Err(e) => Err(e),
-        };
+    pub fn complete_refresh_object(&mut self, result: Result<CompleteLoadObjectResult>) {
         match result {
-            Ok(path) => {
-                let msg = format!("Download completed successfully: {}", path);
-                self.tx.send(AppEventType::NotifySuccess(msg));
+            Ok(CompleteLoadObjectResult {
+                detail,
+                versions,
+                map_key,
+            }) => {
+                self.app_objects
+                    .set_object_details(map_key, *detail.clone(), versions.clone());
+                if let Page::ObjectDetail(page) = self.page_stack.current_page_mut() {
+                    page.update_detail(*detail, versions);
+                }
+                let msg = "Reloaded object detail".to_string();
+                self.tx.send(AppEventType::NotifyInfo(msg));
             }
             Err(e) => {
                 self.tx.send(AppEventType::NotifyError(e));
@@ -400,36 +877,685 @@ impl App {
         self.app_view_state.is_loading = false;
     }
 
-    pub fn preview_object(&self, file_detail: FileDetail, version_id: Option<String>) {
-        let object_name = file_detail.name.clone();
-        let size_byte = file_detail.size_byte;
+    /// Prefetches the details of the files visible in the current object list.
+    ///
+    /// Requests for the files that are no longer visible are cancelled.
+    pub fn prefetch_object_details(&mut self) {
+        if self.client.is_none() {
+            return;
+        }
 
-        self.download_object_and(
-            &object_name,
-            size_byte,
-            None,
-            version_id.clone(),
-            |tx, obj, path| {
-                let result = CompletePreviewObjectResult::new(obj, file_detail, version_id, path);
-                tx.send(AppEventType::CompletePreviewObject(result));
-            },
-        )
-    }
+        // the keys are built from the location of the page, which does not include its selected folder
+        let visible_files: Vec<(ObjectKey, String, String, usize)> =
+            match self.page_stack.current_page() {
+                Page::ObjectList(page) => page
+                    .visible_items()
+                    .into_iter()
+                    .filter_map(|item| match item {
+                        ObjectItem::File {
+                            name, size_byte, ..
+                        } => {
+                            let map_key = page.object_key().join(name.clone());
+                            let key = page.object_key().item_key(item);
+                            Some((map_key, key, name.clone(), *size_byte))
+                        }
+                        ObjectItem::Dir { .. } => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
 
-    pub fn complete_preview_object(&mut self, result: Result<CompletePreviewObjectResult>) {
-        match result {
+        let visible_keys: HashSet<&ObjectKey> =
+            visible_files.iter().map(|(k, _, _, _)| k).collect();
+        self.prefetch_tasks.retain(|key, task| {
+            let keep = visible_keys.contains(key);
+            if !keep {
+                task.abort();
+            }
+            keep
+        });
+
+        let bucket = self.current_bucket();
+        for (map_key, key, name, size_byte) in visible_files {
+            if self.app_objects.get_object_detail(&map_key).is_some()
+                || self.prefetch_tasks.contains_key(&map_key)
+                || self.prefetch_failed.contains(&map_key)
+            {
+                continue;
+            }
+
+            let (client, tx) = self.unwrap_client_tx();
+            let semaphore = self.prefetch_semaphore.clone();
+            let bucket = bucket.clone();
+            let task_key = map_key.clone();
+            let task = spawn(async move {
+                // the permit is released when the task is completed or aborted
+                let Ok(_permit) = semaphore.acquire().await else {
+                    return;
+                };
+                let detail = client
+                    .load_object_detail(&bucket, &key, &name, size_byte, None)
+                    .await
+                    .map(Box::new);
+                tx.send(AppEventType::CompletePrefetchObjectDetail(task_key, detail));
+            });
+            self.prefetch_tasks.insert(map_key, task);
+        }
+    }
+
+    pub fn complete_prefetch_object_detail(
+        &mut self,
+        map_key: ObjectKey,
+        result: Result<Box<FileDetail>>,
+    ) {
+        self.prefetch_tasks.remove(&map_key);
+        match result {
+            Ok(detail) => {
+                self.app_objects.set_object_detail(map_key, *detail);
+            }
+            Err(e) => {
+                // prefetching is best-effort, the error will be shown when the object is opened.
+                // it is not retried until the listing is refreshed, since it would fail again on every redraw
+                tracing::debug!("failed to prefetch object detail: {:?}", e);
+                self.prefetch_failed.insert(map_key);
+            }
+        }
+    }
+
+    pub fn open_help(&mut self) {
+        let helps = match self.page_stack.current_page() {
+            Page::Initializing(_) | Page::Help(_) => {
+                return;
+            }
+            Page::BucketList(page) => page.helps(),
+            Page::ObjectList(page) => page.helps(),
+            Page::ObjectDetail(page) => page.helps(),
+            Page::ObjectPreview(page) => page.helps(),
+            Page::ObjectDenied(page) => page.helps(),
+            Page::RecentObjects(page) => page.helps(),
+        };
+        let help_page = Page::of_help(helps, self.tx.clone());
+        self.page_stack.push(help_page);
+    }
+
+    pub fn open_command_palette(&mut self) {
+        let commands = self.page_stack.current_page().commands();
+        if commands.is_empty() {
+            return;
+        }
+        self.app_view_state.command_palette = Some(CommandPaletteState::new(commands));
+    }
+
+    pub fn close_command_palette(&mut self) {
+        self.app_view_state.command_palette = None;
+    }
+
+    /// Closes the command palette and runs the selected command.
+    ///
+    /// Returns the key of the command to be handled by the current page, if it is not handled by the app.
+    pub fn run_selected_command(&mut self) -> Option<KeyEvent> {
+        let key = self
+            .app_view_state
+            .command_palette
+            .as_ref()
+            .and_then(|state| state.selected_command())
+            .map(|command| command.key);
+        self.close_command_palette();
+
+        match key? {
+            key_code_char!('r', Ctrl) => {
+                self.refresh();
+                None
+            }
+            key_code_char!('g', Ctrl) => {
+                self.open_region_picker();
+                None
+            }
+            key => Some(key),
+        }
+    }
+
+    pub fn open_region_picker(&mut self) {
+        if let Some(region) = self.region() {
+            self.app_view_state.region_picker = Some(RegionPickerState::new(region));
+        }
+    }
+
+    pub fn close_region_picker(&mut self) {
+        self.app_view_state.region_picker = None;
+    }
+
+    /// Rebuilds the client against `region`. The pages and the loaded objects are kept.
+    pub fn set_region(&mut self, region: String) {
+        if self.region().as_ref() == Some(&region) {
+            return;
+        }
+        self.app_view_state.is_loading = true;
+
+        let (client, tx) = self.unwrap_client_tx();
+        spawn(async move {
+            let client = client.with_region(region).await;
+            tx.send(AppEventType::CompleteSetRegion(client));
+        });
+    }
+
+    pub fn complete_set_region(&mut self, client: Client) {
+        let msg = format!("Switched region to {}", client.region());
+        self.clear_notification();
+        self.tx.send(AppEventType::NotifySuccess(msg));
+
+        if matches!(self.page_stack.current_page(), Page::Initializing(_)) {
+            // the buckets failed to load in the old region, so load them again
+            // is_loading is reset when the buckets are loaded
+            self.initialize(client, self.bucket.clone());
+            return;
+        }
+
+        self.client = Some(Arc::new(client));
+        self.app_view_state.is_loading = false;
+
+        let bucket_opened = self
+            .page_stack
+            .iter()
+            .any(|page| matches!(page, Page::ObjectList(_)));
+        if bucket_opened {
+            // the opened bucket may be in another region than the new one
+            let bucket = self.current_bucket();
+            let (client, tx) = self.unwrap_client_tx();
+            spawn(async move {
+                if let Err(e) = client.load_bucket(&bucket).await {
+                    let msg = format!(
+                        "Bucket '{}' is not reachable in this region: {}",
+                        bucket,
+                        e.notification_msg()
+                    );
+                    tx.send(AppEventType::NotifyWarn(msg));
+                }
+            });
+        }
+    }
+
+    pub fn close_current_page(&mut self) {
+        let page = self.page_stack.pop();
+        if let Page::ObjectPreview(page) = page {
+            // remember the position to restore it when the same object is previewed again
+            let file_detail = page.file_detail();
+            let (v_offset, h_offset) = page.offset();
+            let position = PreviewPosition {
+                e_tag: file_detail.e_tag.clone(),
+                version_id: page.file_version_id().map(String::from),
+                v_offset,
+                h_offset,
+            };
+            let map_key = self.current_object_key_with_name(file_detail.name.clone());
+            self.app_objects.set_preview_position(map_key, position);
+        }
+    }
+
+    pub fn load_version_diff(
+        &mut self,
+        file_detail: FileDetail,
+        old_version: FileVersion,
+        new_version: FileVersion,
+    ) {
+        self.app_view_state.is_loading = true;
+
+        let name = file_detail.name;
+        let bucket = self.current_bucket();
+        let prefix = self.current_object_prefix();
+        let key = format!("{}{}", prefix, name);
+
+        let (client, tx) = self.unwrap_client_tx();
+        spawn(async move {
+            let old_detail = client.load_object_detail(
+                &bucket,
+                &key,
+                &name,
+                old_version.size_byte,
+                Some(old_version.version_id.clone()),
+            );
+            let new_detail = client.load_object_detail(
+                &bucket,
+                &key,
+                &name,
+                new_version.size_byte,
+                Some(new_version.version_id.clone()),
+            );
+            let (old_detail, new_detail) = tokio::join!(old_detail, new_detail);
+            let result = CompleteLoadVersionDiffResult::new(
+                old_version.version_id,
+                new_version.version_id,
+                old_detail,
+                new_detail,
+            );
+            tx.send(AppEventType::CompleteLoadVersionDiff(result));
+        });
+    }
+
+    pub fn complete_load_version_diff(&mut self, result: Result<CompleteLoadVersionDiffResult>) {
+        match result {
+            Ok(CompleteLoadVersionDiffResult {
+                old_version_id,
+                new_version_id,
+                old_detail,
+                new_detail,
+            }) => {
+                let state = VersionDiffDialogState::new(
+                    old_version_id,
+                    new_version_id,
+                    *old_detail,
+                    *new_detail,
+                );
+                let page = self.page_stack.current_page_mut().as_mut_object_detail();
+                page.open_version_diff_dialog(state);
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.app_view_state.is_loading = false;
+    }
+
+    pub fn load_bucket_access(&mut self, bucket: String) {
+        if AccessPointArn::parse(&bucket).is_some() {
+            // the policy, ACL and public access block are of the bucket, not of the access point
+            let msg = "Bucket access settings are not available for access points";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        }
+        self.app_view_state.is_loading = true;
+
+        let (client, tx) = self.unwrap_client_tx();
+        spawn(async move {
+            let policy_public = client.get_bucket_policy_status(&bucket);
+            let public_access_block = client.get_public_access_block(&bucket);
+            let grants = client.get_bucket_acl(&bucket);
+            let (policy_public, public_access_block, grants) =
+                tokio::join!(policy_public, public_access_block, grants);
+            let result = CompleteLoadBucketAccessResult::new(
+                bucket,
+                policy_public,
+                public_access_block,
+                grants,
+            );
+            tx.send(AppEventType::CompleteLoadBucketAccess(result));
+        });
+    }
+
+    pub fn complete_load_bucket_access(&mut self, result: Result<CompleteLoadBucketAccessResult>) {
+        match result {
+            Ok(CompleteLoadBucketAccessResult { bucket, access }) => {
+                let state = BucketAccessDialogState::new(bucket, access);
+                let page = self.page_stack.current_page_mut().as_mut_bucket_list();
+                page.open_access_dialog(state);
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.app_view_state.is_loading = false;
+    }
+
+    pub fn restore_object(
+        &mut self,
+        file_detail: FileDetail,
+        version_id: Option<String>,
+        tier: RestoreTier,
+        days: i32,
+    ) {
+        if self.is_anonymous() {
+            let msg = "Objects cannot be restored without credentials";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        }
+        self.app_view_state.is_loading = true;
+
+        let bucket = self.current_bucket();
+        let key = file_detail.key;
+        let map_key = self.current_object_key_with_name(file_detail.name);
+
+        let (client, tx) = self.unwrap_client_tx();
+        spawn(async move {
+            let result = client
+                .restore_object(&bucket, &key, version_id.clone(), tier, days)
+                .await;
+            let result = CompleteRestoreObjectResult::new(result, map_key, version_id, tier, days);
+            tx.send(AppEventType::CompleteRestoreObject(result));
+        });
+    }
+
+    pub fn complete_restore_object(&mut self, result: Result<CompleteRestoreObjectResult>) {
+        match result {
+            Ok(CompleteRestoreObjectResult {
+                map_key,
+                version_id,
+                tier,
+                days,
+            }) => {
+                if version_id.is_none() {
+                    if let Some(detail) = self.app_objects.get_object_detail(&map_key) {
+                        if detail.restore_status.is_none() {
+                            let mut detail = detail.clone();
+                            detail.restore_status = Some(RestoreStatus::InProgress);
+                            self.app_objects.set_object_detail(map_key, detail);
+                        }
+                    }
+                    let page = self.page_stack.current_page_mut().as_mut_object_detail();
+                    page.set_restore_in_progress();
+                }
+
+                let unit = if days == 1 { "day" } else { "days" };
+                let msg = format!(
+                    "Restore requested ({} tier, {} {}), the object can be downloaded once it completes",
+                    tier.name(),
+                    days,
+                    unit
+                );
+                self.tx.send(AppEventType::NotifySuccess(msg));
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.app_view_state.is_loading = false;
+    }
+
+    pub fn transition_storage_class(
+        &mut self,
+        file_detail: FileDetail,
+        storage_class: StorageClass,
+    ) {
+        if self.is_anonymous() {
+            let msg = "Storage class cannot be changed without credentials";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        }
+        self.app_view_state.is_loading = true;
+
+        let bucket = self.current_bucket();
+        let map_key = self.current_object_key_with_name(file_detail.name.clone());
+
+        let (client, tx) = self.unwrap_client_tx();
+        spawn(async move {
+            // the copy creates a new object, so the detail and versions are loaded again
+            let result = match client
+                .transition_storage_class(&bucket, &file_detail, storage_class)
+                .await
+            {
+                Ok(()) => {
+                    let FileDetail {
+                        key,
+                        name,
+                        size_byte,
+                        ..
+                    } = file_detail;
+                    let detail = client
+                        .load_object_detail(&bucket, &key, &name, size_byte, None)
+                        .await;
+                    let versions = client.load_object_versions(&bucket, &key).await;
+                    CompleteLoadObjectResult::new(detail, versions, map_key)
+                }
+                Err(e) => Err(e),
+            };
+            tx.send(AppEventType::CompleteTransitionStorageClass(
+                storage_class,
+                result,
+            ));
+        });
+    }
+
+    pub fn complete_transition_storage_class(
+        &mut self,
+        storage_class: StorageClass,
+        result: Result<CompleteLoadObjectResult>,
+    ) {
+        match result {
+            Ok(CompleteLoadObjectResult {
+                detail,
+                versions,
+                map_key,
+            }) => {
+                self.app_objects
+                    .set_object_details(map_key, *detail.clone(), versions.clone());
+                if let Page::ObjectDetail(page) = self.page_stack.current_page_mut() {
+                    page.update_detail(*detail, versions);
+                }
+                let msg = format!("Changed storage class to {}", storage_class.name());
+                self.tx.send(AppEventType::NotifySuccess(msg));
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.app_view_state.is_loading = false;
+    }
+
+    pub fn detail_download_object(&mut self, file_detail: FileDetail, version_id: Option<String>) {
+        self.tx
+            .send(AppEventType::DownloadObject(file_detail, version_id));
+        self.app_view_state.is_loading = true;
+    }
+
+    pub fn preview_download_object(&self, obj: RawObject, path: String) {
+        // the preview may contain only the first part of the object
+        let result = CompleteDownloadObjectResult::new(Ok(obj), path, None);
+        self.tx.send(AppEventType::CompleteDownloadObject(result));
+    }
+
+    pub fn open_preview(&mut self, file_detail: FileDetail, version_id: Option<String>) {
+        let max_size_byte = self.config.preview.max_size_bytes;
+        if file_detail.size_byte > max_size_byte {
+            let page = self.page_stack.current_page_mut().as_mut_object_detail();
+            page.open_large_object_dialog(max_size_byte);
+            return;
+        }
+        self.tx
+            .send(AppEventType::PreviewObject(file_detail, version_id, None));
+        self.app_view_state.is_loading = true;
+    }
+
+    pub fn open_partial_preview(&mut self, file_detail: FileDetail, version_id: Option<String>) {
+        let limit = self.config.preview.max_size_bytes;
+        self.tx.send(AppEventType::PreviewObject(
+            file_detail,
+            version_id,
+            Some(limit),
+        ));
+        self.app_view_state.is_loading = true;
+    }
+
+    pub fn download_object(&self, file_detail: FileDetail, version_id: Option<String>) {
+        let object_name = file_detail.name.clone();
+        let size_byte = file_detail.size_byte;
+        let verify = self.config.verify_download_md5;
+        let latest = version_id.is_none();
+
+        self.download_object_and(
+            &object_name,
+            size_byte,
+            None,
+            version_id,
+            None,
+            move |tx, obj, path| {
+                // hashed in the download task, so that a large object does not block the UI
+                let check = obj
+                    .as_ref()
+                    .ok()
+                    .filter(|_| verify)
+                    .map(|obj| check_download_etag(&file_detail, latest, &obj.bytes));
+                let result = CompleteDownloadObjectResult::new(obj, path, check);
+                tx.send(AppEventType::CompleteDownloadObject(result));
+            },
+        )
+    }
+
+    pub fn download_object_as(
+        &self,
+        file_detail: FileDetail,
+        input: String,
+        version_id: Option<String>,
+    ) {
+        let object_name = file_detail.name.clone();
+        let size_byte = file_detail.size_byte;
+        let verify = self.config.verify_download_md5;
+        let latest = version_id.is_none();
+
+        self.download_object_and(
+            &object_name,
+            size_byte,
+            Some(&input),
+            version_id,
+            None,
+            move |tx, obj, path| {
+                // hashed in the download task, so that a large object does not block the UI
+                let check = obj
+                    .as_ref()
+                    .ok()
+                    .filter(|_| verify)
+                    .map(|obj| check_download_etag(&file_detail, latest, &obj.bytes));
+                let result = CompleteDownloadObjectResult::new(obj, path, check);
+                tx.send(AppEventType::CompleteDownloadObject(result));
+            },
+        )
+    }
+
+    pub fn complete_download_object(&mut self, result: Result<CompleteDownloadObjectResult>) {
+        let result = match result {
+            Ok(CompleteDownloadObjectResult {
+                obj,
+                path,
+                etag_check,
+            }) => save_binary(&path, &obj.bytes).map(|_| (path, etag_check)),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok((path, None)) => {
+                let msg = format!("Download completed successfully: {}", path);
+                self.tx.send(AppEventType::NotifySuccess(msg));
+            }
+            Ok((path, Some(EtagCheck::Match))) => {
+                let msg = format!("Download completed successfully (MD5 verified): {}", path);
+                self.tx.send(AppEventType::NotifySuccess(msg));
+            }
+            Ok((path, Some(EtagCheck::Skipped(reason)))) => {
+                let msg = format!(
+                    "Downloaded, but integrity not verified ({}): {}",
+                    reason, path
+                );
+                self.tx.send(AppEventType::NotifyWarn(msg));
+            }
+            Ok((path, Some(EtagCheck::Mismatch { md5 }))) => {
+                let msg = format!(
+                    "Downloaded, but MD5 ({}) does not match the ETag, the file may be corrupted: {}",
+                    md5, path
+                );
+                self.tx.send(AppEventType::NotifyWarn(msg));
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.app_view_state.is_loading = false;
+    }
+
+    pub fn download_prefix(&mut self, name: String) {
+        let bucket = self.current_bucket();
+        // the folder itself is created in the download directory
+        let parent = self.current_list_object_key().prefix();
+        let dir = PathBuf::from(&self.config.download_dir);
+
+        let (client, tx) = self.unwrap_client_tx();
+        spawn(async move {
+            let result = download_prefix_objects(&client, &bucket, &parent, &name, &dir, &tx).await;
+            tx.send(AppEventType::CompleteDownloadPrefix(result));
+        });
+        self.app_view_state.is_loading = true;
+    }
+
+    pub fn complete_download_prefix(&mut self, result: Result<CompleteDownloadPrefixResult>) {
+        match result {
+            Ok(CompleteDownloadPrefixResult {
+                dir,
+                downloaded,
+                skipped,
+                failed,
+            }) => {
+                let skipped_s = if skipped > 0 {
+                    format!(", {} already existed", skipped)
+                } else {
+                    "".into()
+                };
+                if failed.is_empty() {
+                    let msg = format!("Downloaded {} files to {}{}", downloaded, dir, skipped_s);
+                    self.tx.send(AppEventType::NotifySuccess(msg));
+                } else {
+                    for (key, e) in &failed {
+                        tracing::error!("Failed to download {}", key);
+                        self.handle_error(e);
+                    }
+                    let msg = format!(
+                        "Downloaded {} files to {}{}, {} failed (see the error log)",
+                        downloaded,
+                        dir,
+                        skipped_s,
+                        failed.len()
+                    );
+                    self.tx.send(AppEventType::NotifyWarn(msg));
+                }
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.app_view_state.is_loading = false;
+    }
+
+    pub fn preview_object(
+        &self,
+        file_detail: FileDetail,
+        version_id: Option<String>,
+        limit: Option<usize>,
+    ) {
+        let object_name = file_detail.name.clone();
+        let size_byte = file_detail.size_byte;
+
+        self.download_object_and(
+            &object_name,
+            size_byte,
+            None,
+            version_id.clone(),
+            limit,
+            |tx, obj, path| {
+                let result = CompletePreviewObjectResult::new(obj, file_detail, version_id, path);
+                tx.send(AppEventType::CompletePreviewObject(result));
+            },
+        )
+    }
+
+    pub fn complete_preview_object(&mut self, result: Result<CompletePreviewObjectResult>) {
+        match result {
             Ok(CompletePreviewObjectResult {
                 obj,
                 file_detail,
                 file_version_id,
                 path,
             }) => {
+                if obj.bytes.len() < file_detail.size_byte {
+                    let msg = format!(
+                        "Showing the first {} of {}",
+                        format_size_byte(obj.bytes.len()),
+                        format_size_byte(file_detail.size_byte)
+                    );
+                    self.tx.send(AppEventType::NotifyInfo(msg));
+                }
+                let offset = self.preview_offset(&file_detail, file_version_id.as_deref());
                 let object_preview_page = Page::of_object_preview(
                     file_detail,
                     file_version_id,
                     obj,
                     path,
                     self.config.preview.clone(),
+                    offset,
                     self.tx.clone(),
                 );
                 self.page_stack.push(object_preview_page);
@@ -442,12 +1568,67 @@ impl App {
         self.app_view_state.is_loading = false;
     }
 
+    fn preview_offset(
+        &mut self,
+        file_detail: &FileDetail,
+        version_id: Option<&str>,
+    ) -> Option<(usize, usize)> {
+        let map_key = self.current_object_key_with_name(file_detail.name.clone());
+        match self.app_objects.get_preview_position(&map_key) {
+            Some(p) if p.e_tag == file_detail.e_tag && p.version_id.as_deref() == version_id => {
+                Some((p.v_offset, p.h_offset))
+            }
+            Some(_) => {
+                // the object has been changed, or another version is previewed
+                self.app_objects.remove_preview_position(&map_key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn preview_refresh_object(&mut self, file_detail: FileDetail) {
+        self.app_view_state.is_loading = true;
+
+        let object_name = file_detail.name;
+        let size_byte = file_detail.size_byte;
+
+        self.download_object_and(
+            &object_name,
+            size_byte,
+            None,
+            None,
+            None,
+            |tx, obj, path| {
+                let result = CompleteDownloadObjectResult::new(obj, path, None);
+                tx.send(AppEventType::CompletePreviewRefreshObject(result));
+            },
+        )
+    }
+
+    pub fn complete_preview_refresh_object(
+        &mut self,
+        result: Result<CompleteDownloadObjectResult>,
+    ) {
+        match result {
+            Ok(CompleteDownloadObjectResult { obj, .. }) => {
+                let page = self.page_stack.current_page_mut().as_mut_object_preview();
+                page.update_object(obj);
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+        self.app_view_state.is_loading = false;
+    }
+
     fn download_object_and<F>(
         &self,
         object_name: &str,
         size_byte: usize,
         save_file_name: Option<&str>,
         version_id: Option<String>,
+        limit: Option<usize>,
         f: F,
     ) where
         F: FnOnce(Sender, Result<RawObject>, String) + Send + 'static,
@@ -461,16 +1642,23 @@ impl App {
             .download_file_path(save_file_name.unwrap_or(object_name));
 
         let (client, tx) = self.unwrap_client_tx();
-        let loading = self.handle_loading_size(size_byte, tx.clone());
+        let loading = self.handle_loading_size(size_byte, limit, tx.clone());
         spawn(async move {
             let obj = client
-                .download_object(&bucket, &key, version_id, size_byte, loading)
+                .download_object(&bucket, &key, version_id, size_byte, limit, loading)
                 .await;
             f(tx, obj, path);
         });
     }
 
-    fn handle_loading_size(&self, total_size: usize, tx: Sender) -> Box<dyn Fn(usize) + Send> {
+    fn handle_loading_size(
+        &self,
+        size_byte: usize,
+        limit: Option<usize>,
+        tx: Sender,
+    ) -> Box<dyn Fn(usize) + Send> {
+        // a partial download ends at the limit
+        let total_size = limit.map_or(size_byte, |limit| limit.min(size_byte));
         if total_size < 10_000_000 {
             return Box::new(|_| {});
         }
@@ -478,10 +1666,26 @@ impl App {
         let opt =
             humansize::FormatSizeOptions::from(humansize::DECIMAL).decimal_places(decimal_places);
         let total_s = humansize::format_size_i(total_size, opt);
+        let meter = Mutex::new(ThroughputMeter::new(THROUGHPUT_WINDOW, Instant::now()));
         let f = move |current| {
             let percent = (current * 100) / total_size;
             let cur_s = humansize::format_size_i(current, opt);
-            let msg = format!("{:3}% downloaded ({} out of {})", percent, cur_s, total_s);
+
+            let mut meter = meter.lock().unwrap();
+            meter.record(Instant::now(), current);
+            let speed_s = meter
+                .bytes_per_sec()
+                .map(|b| format!(", {:.1} MiB/s", b / (1024.0 * 1024.0)))
+                .unwrap_or_default();
+            let remaining_s = meter
+                .remaining(total_size)
+                .map(|d| format!(", {} left", format_duration(d)))
+                .unwrap_or_default();
+
+            let msg = format!(
+                "{:3}% downloaded ({} out of {}{}{})",
+                percent, cur_s, total_s, speed_s, remaining_s
+            );
             tx.send(AppEventType::NotifyInfo(msg));
         };
         Box::new(f)
@@ -520,7 +1724,16 @@ impl App {
         file_detail: FileDetail,
         input: String,
         version_id: Option<String>,
+        overwrite: bool,
     ) {
+        if !overwrite {
+            if let Some(renamed_input) = self.renamed_input_if_exists(&input) {
+                let page = self.page_stack.current_page_mut().as_mut_object_detail();
+                page.open_save_confirm_dialog(input, renamed_input);
+                return;
+            }
+        }
+
         self.tx.send(AppEventType::DownloadObjectAs(
             file_detail,
             input,
@@ -537,7 +1750,16 @@ impl App {
         file_detail: FileDetail,
         input: String,
         version_id: Option<String>,
+        overwrite: bool,
     ) {
+        if !overwrite {
+            if let Some(renamed_input) = self.renamed_input_if_exists(&input) {
+                let page = self.page_stack.current_page_mut().as_mut_object_preview();
+                page.open_save_confirm_dialog(input, renamed_input);
+                return;
+            }
+        }
+
         self.tx.send(AppEventType::DownloadObjectAs(
             file_detail,
             input,
@@ -549,6 +1771,115 @@ impl App {
         page.close_save_dialog();
     }
 
+    fn renamed_input_if_exists(&self, input: &str) -> Option<String> {
+        let exists = |name: &str| Path::new(&self.config.download_file_path(name)).exists();
+        exists(input).then(|| next_available_file_name(input, exists))
+    }
+
+    pub fn save_list_width_percentage(&mut self, percentage: u16) {
+        self.config.layout.list_width_percentage = percentage;
+        if let Err(e) = self.config.save_list_width_percentage() {
+            let e = AppError::msg(format!("{:#}", e));
+            self.tx.send(AppEventType::NotifyError(e));
+        }
+    }
+
+    pub fn set_preview_options(&mut self, number: bool, wrap: bool) {
+        // kept only for the session, the config file is not updated
+        self.config.preview.number = number;
+        self.config.preview.wrap = wrap;
+    }
+
+    pub fn copy_presigned_put_url(&self, name: String, content_type: Option<String>) {
+        if self.is_anonymous() {
+            let msg = "Presigned URLs cannot be created without credentials";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        }
+        let object_key = self.current_list_object_key();
+        let bucket = object_key.bucket_name.clone();
+        let key = format!("{}{}", object_key.prefix(), name);
+
+        let (client, tx) = self.unwrap_client_tx();
+        spawn(async move {
+            let result = client
+                .presign_put(&bucket, &key, PRESIGN_EXPIRES_IN, content_type.as_deref())
+                .await;
+            let s3_uri = format!("s3://{}/{}", bucket, key);
+            tx.send(AppEventType::CompletePresignPut(s3_uri, result));
+        });
+    }
+
+    pub fn complete_presign_put(&self, s3_uri: String, result: Result<String>) {
+        match result.and_then(copy_to_clipboard) {
+            Ok(_) => {
+                let msg = format!(
+                    "Copied presigned URL, valid for {}, which allows anyone with it to upload to {}",
+                    format_duration(PRESIGN_EXPIRES_IN),
+                    s3_uri
+                );
+                self.tx.send(AppEventType::NotifyWarn(msg));
+            }
+            Err(e) => {
+                self.tx.send(AppEventType::NotifyError(e));
+            }
+        }
+    }
+
+    pub fn create_folder(&self, name: String) {
+        if self.is_anonymous() {
+            let msg = "Folders cannot be created without credentials";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        }
+        let object_key = self.current_list_object_key();
+        let bucket = object_key.bucket_name.clone();
+        let prefix = object_key.prefix();
+
+        let (client, tx) = self.unwrap_client_tx();
+        spawn(async move {
+            let result = client.create_folder(&bucket, &prefix, &name).await;
+            tx.send(AppEventType::CompleteCreateFolder(object_key, name, result));
+        });
+    }
+
+    pub fn complete_create_folder(
+        &mut self,
+        object_key: ObjectKey,
+        name: String,
+        result: Result<()>,
+    ) {
+        if let Err(e) = result {
+            self.tx.send(AppEventType::NotifyError(e));
+            return;
+        }
+
+        // show the folder without reloading, in the same place as the next listing would
+        let dir = ObjectItem::Dir { name: name.clone() };
+        if let Some(mut items) = self.app_objects.get_object_items(&object_key) {
+            let exists = items
+                .iter()
+                .any(|item| matches!(item, ObjectItem::Dir { name: n } if *n == name));
+            if !exists {
+                let dirs_len = items
+                    .iter()
+                    .take_while(|item| matches!(item, ObjectItem::Dir { .. }))
+                    .count();
+                items.insert(dirs_len, dir.clone());
+                self.app_objects.set_object_items(object_key.clone(), items);
+            }
+        }
+        if let Page::ObjectList(page) = self.page_stack.current_page_mut() {
+            if *page.object_key() == object_key && !page.select_item_by_name(&name, true) {
+                page.append_items(vec![dir]);
+                page.select_item_by_name(&name, true);
+            }
+        }
+
+        let msg = format!("Created folder '{}/'", name);
+        self.tx.send(AppEventType::NotifySuccess(msg));
+    }
+
     pub fn copy_to_clipboard(&self, name: String, value: String) {
         match copy_to_clipboard(value) {
             Ok(_) => {
@@ -579,7 +1910,7 @@ impl App {
 
     pub fn error_notification(&mut self, e: AppError) {
         self.handle_error(&e);
-        self.app_view_state.notification = Notification::Error(e.msg);
+        self.app_view_state.notification = Notification::Error(e.notification_msg());
     }
 
     fn handle_error(&self, e: &AppError) {
@@ -598,3 +1929,549 @@ impl App {
         (self.client.as_ref().unwrap().clone(), self.tx.clone())
     }
 }
+
+/// Downloads all objects under `parent` + `name` + `/` into `dir`, keeping the folder structure.
+///
+/// The objects are downloaded one by one, and a failure of an object does not stop the others.
+async fn download_prefix_objects(
+    client: &Client,
+    bucket: &str,
+    parent: &str,
+    name: &str,
+    dir: &Path,
+    tx: &Sender,
+) -> Result<CompleteDownloadPrefixResult> {
+    let prefix = format!("{}{}/", parent, name);
+    let objects: Vec<_> = client
+        .list_all_objects(bucket, &prefix)
+        .await?
+        .into_iter()
+        // skip the objects that only represent folders
+        .filter(|obj| !obj.key.ends_with('/'))
+        .collect();
+
+    let total = objects.len();
+    let total_size = objects.iter().map(|obj| obj.size_byte).sum();
+    let mut done_size = 0;
+
+    let mut result = CompleteDownloadPrefixResult {
+        dir: String::from(dir.join(name).to_string_lossy()),
+        ..Default::default()
+    };
+    for (i, obj) in objects.into_iter().enumerate() {
+        let msg = format!(
+            "Downloading {}/{} files ({} out of {})",
+            i + 1,
+            total,
+            format_size_byte(done_size),
+            format_size_byte(total_size)
+        );
+        tx.send(AppEventType::NotifyInfo(msg));
+
+        let downloaded = match prefix_download_path(dir, &obj.key[parent.len()..]) {
+            Ok(path) if is_downloaded(&path, obj.size_byte) => Ok(false),
+            Ok(path) => client
+                .download_object(bucket, &obj.key, None, obj.size_byte, None, |_| {})
+                .await
+                .and_then(|raw| save_binary(&path.to_string_lossy(), &raw.bytes))
+                .map(|_| true),
+            Err(e) => Err(e),
+        };
+        match downloaded {
+            Ok(true) => result.downloaded += 1,
+            Ok(false) => result.skipped += 1,
+            Err(e) => result.failed.push((obj.key, e)),
+        }
+        done_size += obj.size_byte;
+    }
+    Ok(result)
+}
+
+// the ETag in the detail is the one of the latest version
+fn check_download_etag(file_detail: &FileDetail, latest: bool, bytes: &[u8]) -> EtagCheck {
+    if !latest {
+        return EtagCheck::Skipped("not the latest version");
+    }
+    verify_md5_etag(&file_detail.e_tag, file_detail.encryption.as_deref(), bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{backend::TestBackend, buffer::Buffer, style::Color, Terminal};
+    use rstest::rstest;
+
+    use crate::{
+        client::test_util::{
+            anonymous_options, test_client, test_client_with_credentials, MockHttpClient,
+        },
+        event::{self, Receiver},
+        ui,
+    };
+
+    use super::*;
+
+    type Uris = Arc<Mutex<Vec<String>>>;
+
+    /// Returns the app with the list of `bucket-1`, and the URIs of the requests sent to `http_client`.
+    async fn setup_app(http_client: MockHttpClient, credentials: bool) -> (App, Receiver, Uris) {
+        let uris = http_client.uris.clone();
+        let (tx, rx) = event::new_without_terminal();
+        let mut app = App::new(Config::default(), tx.clone(), 60, 20);
+        let client = if credentials {
+            test_client_with_credentials(http_client).await
+        } else {
+            test_client(http_client, anonymous_options()).await
+        };
+        app.client = Some(Arc::new(client));
+
+        let buckets = vec![BucketItem {
+            name: "bucket-1".into(),
+        }];
+        app.app_objects.set_bucket_items(buckets.clone());
+        app.page_stack.pop();
+        app.page_stack.push(Page::of_bucket_list(buckets, tx));
+        (app, rx, uris)
+    }
+
+    /// Opens the object list of `object_path`, selecting the folder `selected` if given.
+    fn open_object_list(
+        app: &mut App,
+        object_path: &[&str],
+        items: Vec<ObjectItem>,
+        selected: Option<&str>,
+    ) {
+        let object_key = ObjectKey {
+            bucket_name: "bucket-1".into(),
+            object_path: object_path.iter().map(|s| s.to_string()).collect(),
+        };
+        app.app_objects
+            .set_object_items(object_key.clone(), items.clone());
+        let mut page = Page::of_object_list(items, object_key, app.tx.clone());
+        if let Some(name) = selected {
+            assert!(page.as_mut_object_list().select_item_by_name(name, true));
+        }
+        app.page_stack.push(page);
+
+        // the visible items are decided by rendering
+        render(app);
+    }
+
+    /// Opens `bucket-1/dir1/` with its folder `dir2` selected, which is not a part of the location.
+    fn open_page_with_selected_folder(app: &mut App) {
+        open_object_list(
+            app,
+            &["dir1"],
+            vec![dir("dir2"), file("file1.txt")],
+            Some("dir2"),
+        );
+    }
+
+    fn render(app: &mut App) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        terminal.draw(|f| ui::render(f, app)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn screen_line(buf: &Buffer, y: u16) -> String {
+        (0..buf.area.width)
+            .map(|x| buf.get(x, y).symbol())
+            .collect()
+    }
+
+    /// Returns the row of the screen showing `s`.
+    fn find_row(buf: &Buffer, s: &str) -> Option<u16> {
+        (0..buf.area.height).find(|y| screen_line(buf, *y).contains(s))
+    }
+
+    fn dir(name: &str) -> ObjectItem {
+        ObjectItem::Dir { name: name.into() }
+    }
+
+    fn file(name: &str) -> ObjectItem {
+        ObjectItem::File {
+            name: name.into(),
+            size_byte: 10,
+            last_modified: Local::now(),
+            owner: None,
+        }
+    }
+
+    fn file_detail(e_tag: &str) -> FileDetail {
+        FileDetail {
+            name: "file1.txt".into(),
+            size_byte: 11,
+            last_modified: Local::now(),
+            e_tag: e_tag.into(),
+            content_type: "text/plain".into(),
+            storage_class: "STANDARD".into(),
+            encryption: None,
+            kms_key_id: None,
+            bucket_key_enabled: None,
+            content_encoding: None,
+            key: "file1.txt".into(),
+            s3_uri: "s3://bucket-1/file1.txt".into(),
+            arn: "arn:aws:s3:::bucket-1/file1.txt".into(),
+            object_url: "https://bucket-1.s3.us-east-1.amazonaws.com/file1.txt".into(),
+            restore_status: None,
+        }
+    }
+
+    #[rstest]
+    #[case::refresh(|app: &mut App| app.refresh(), "/?list-type=2&delimiter=%2F&prefix=dir1%2F")]
+    #[case::download_prefix(
+        |app: &mut App| app.download_prefix("dir2".into()),
+        "/?list-type=2&prefix=dir1%2Fdir2%2F"
+    )]
+    #[case::create_folder(|app: &mut App| app.create_folder("dir3".into()), "/dir1/dir3/?x-id=PutObject")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_request_in_page_with_selected_folder(
+        #[case] action: fn(&mut App),
+        #[case] expected: &str,
+    ) {
+        let (mut app, rx, uris) = setup_app(MockHttpClient::default(), true).await;
+        open_page_with_selected_folder(&mut app);
+
+        action(&mut app);
+        // sent after the request is completed
+        rx.recv();
+
+        let uri = uris.lock().unwrap()[0].clone();
+        let path = uri.split_once(".com").unwrap().1;
+        assert_eq!(path, expected);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_copy_presigned_put_url_in_page_with_selected_folder() {
+        let (mut app, rx, _) = setup_app(MockHttpClient::default(), true).await;
+        open_page_with_selected_folder(&mut app);
+
+        app.copy_presigned_put_url("file2.txt".into(), None);
+        let AppEventType::CompletePresignPut(s3_uri, result) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        assert_eq!(s3_uri, "s3://bucket-1/dir1/file2.txt");
+        let url = result.unwrap();
+        assert!(url.contains("/dir1/file2.txt?"), "{}", url);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_complete_create_folder_in_page_with_selected_folder() {
+        let (mut app, rx, _) = setup_app(MockHttpClient::default(), true).await;
+        open_page_with_selected_folder(&mut app);
+
+        app.create_folder("dir3".into());
+        let AppEventType::CompleteCreateFolder(object_key, name, result) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        app.complete_create_folder(object_key, name, result);
+
+        // the created folder is listed and selected
+        let buf = render(&mut app);
+        let y = find_row(&buf, "dir3/").unwrap();
+        assert_eq!(buf.get(2, y).bg, Color::Cyan);
+        assert!(find_row(&buf, "dir2/").is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_prefetch_object_details_is_not_retried_after_failure() {
+        let http_client = MockHttpClient {
+            denied_keys: vec!["file1.txt"],
+            ..Default::default()
+        };
+        let (mut app, rx, uris) = setup_app(http_client, false).await;
+        open_page_with_selected_folder(&mut app);
+
+        app.prefetch_object_details();
+        let AppEventType::CompletePrefetchObjectDetail(map_key, result) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        let uri = uris.lock().unwrap()[0].clone();
+        assert!(uri.ends_with("/dir1/file1.txt"), "{}", uri);
+        assert!(result.is_err());
+        app.complete_prefetch_object_detail(map_key, result);
+
+        // redrawing does not send the failed request again
+        app.prefetch_object_details();
+        assert!(app.prefetch_tasks.is_empty());
+        assert_eq!(uris.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_collapse_single_dir_stops_at_failed_folder() {
+        let (mut app, rx, _) = setup_app(MockHttpClient::default(), false).await;
+        app.collapse_dirs = true;
+        open_object_list(&mut app, &[], vec![dir("dir1")], None);
+
+        app.collapse_single_dir();
+        assert!(matches!(rx.recv(), AppEventType::LoadObjects));
+        let object_key = app.current_object_key();
+        app.objects_loads.insert(
+            object_key.clone(),
+            ObjectsLoad {
+                id: 0,
+                streamed: false,
+            },
+        );
+
+        let result = Err(AppError::msg("failed"));
+        app.complete_load_objects(object_key, 0, result);
+        assert!(matches!(rx.recv(), AppEventType::NotifyError(_)));
+
+        // the folder is not loaded again
+        app.tx.send(AppEventType::NotifyInfo("end".into()));
+        assert!(matches!(rx.recv(), AppEventType::NotifyInfo(_)));
+        let page = app.page_stack.current_page().as_object_list();
+        assert!(!page.is_collapsed());
+        assert!(!app.app_view_state.is_loading);
+    }
+
+    #[tokio::test]
+    async fn test_load_bucket_access_of_access_point() {
+        let (mut app, rx, uris) = setup_app(MockHttpClient::default(), false).await;
+
+        let arn = "arn:aws:s3:us-east-1:123456789012:accesspoint/my-ap";
+        app.load_bucket_access(arn.into());
+
+        assert!(matches!(rx.recv(), AppEventType::NotifyWarn(_)));
+        assert!(uris.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_run_refresh_from_command_palette() {
+        let (mut app, rx, uris) = setup_app(MockHttpClient::default(), false).await;
+        open_object_list(&mut app, &[], vec![file("file1.txt")], None);
+
+        app.open_command_palette();
+        let state = app.app_view_state.command_palette.as_mut().unwrap();
+        while state.selected_command().unwrap().desc != "Refresh object list" {
+            state.select_next();
+        }
+        assert_eq!(state.selected_command().unwrap().keys, "Ctrl-r");
+
+        // Ctrl-r is handled by the app, not by the page
+        assert_eq!(app.run_selected_command(), None);
+        assert!(app.app_view_state.command_palette.is_none());
+        assert!(app.app_view_state.is_loading);
+        assert!(matches!(
+            rx.recv(),
+            AppEventType::CompleteRefreshObjects(..)
+        ));
+        assert_eq!(
+            *uris.lock().unwrap(),
+            ["https://bucket-1.s3.us-east-1.amazonaws.com/?list-type=2&delimiter=%2F&prefix="]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_complete_set_region_keeps_pages() {
+        let (mut app, rx, old_uris) = setup_app(MockHttpClient::default(), false).await;
+        open_object_list(&mut app, &[], vec![dir("dir1")], Some("dir1"));
+        open_object_list(&mut app, &["dir1"], vec![file("file1.txt")], None);
+
+        let http_client = MockHttpClient::default();
+        let uris = http_client.uris.clone();
+        let client = test_client(http_client, anonymous_options()).await;
+        app.complete_set_region(client);
+
+        assert!(matches!(rx.recv(), AppEventType::NotifySuccess(_)));
+        app.tx.send(AppEventType::NotifyInfo("end".into()));
+        assert!(matches!(rx.recv(), AppEventType::NotifyInfo(_)));
+
+        // the pages and the loaded objects are kept, and nothing is loaded again
+        assert_eq!(app.page_stack.len(), 3);
+        let page = app.page_stack.current_page().as_object_list();
+        assert_eq!(page.current_selected_item().name(), "file1.txt");
+        assert!(app.current_object_items().is_some());
+        assert!(!app.app_view_state.is_loading);
+        assert!(uris.lock().unwrap().is_empty());
+        assert!(old_uris.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_complete_set_region_of_unreachable_bucket() {
+        let (mut app, rx, _) = setup_app(MockHttpClient::default(), false).await;
+        open_object_list(&mut app, &[], vec![file("file1.txt")], None);
+
+        let http_client = MockHttpClient {
+            bodies: vec![
+                r#"<LocationConstraint xmlns="http://s3.amazonaws.com/doc/2006-03-01/">ap-northeast-1</LocationConstraint>"#,
+            ],
+            ..Default::default()
+        };
+        let client = test_client_with_credentials(http_client).await;
+        app.complete_set_region(client);
+
+        assert!(matches!(rx.recv(), AppEventType::NotifySuccess(_)));
+        let AppEventType::NotifyWarn(msg) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        assert_eq!(
+            msg,
+            "Bucket 'bucket-1' is not reachable in this region: Bucket 'bucket-1' is in region 'ap-northeast-1', expected 'us-east-1'"
+        );
+        assert_eq!(app.page_stack.len(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_complete_set_region_reloads_buckets_on_initial_error() {
+        let (tx, rx) = event::new_without_terminal();
+        let mut app = App::new(Config::default(), tx, 60, 20);
+        app.bucket = Some("bucket-1".into());
+
+        let http_client = MockHttpClient::default();
+        let uris = http_client.uris.clone();
+        let client = test_client(http_client, anonymous_options()).await;
+        app.complete_set_region(client);
+        assert!(matches!(rx.recv(), AppEventType::NotifySuccess(_)));
+        let AppEventType::CompleteInitialize(result) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        app.complete_initialize(result);
+
+        // the given bucket is opened with the new client
+        assert!(matches!(rx.recv(), AppEventType::LoadObjects));
+        app.load_objects();
+        assert!(matches!(rx.recv(), AppEventType::CompleteLoadObjects(..)));
+        let uris = uris.lock().unwrap();
+        assert_eq!(
+            *uris,
+            ["https://bucket-1.s3.us-east-1.amazonaws.com/?list-type=2&delimiter=%2F&prefix="]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_open_recent_object_of_unavailable_bucket() {
+        let (mut app, rx, uris) = setup_app(MockHttpClient::default(), false).await;
+        open_object_list(&mut app, &[], vec![dir("dir1")], None);
+        app.recent_objects
+            .add("bucket-2".into(), "dir1/file1.txt".into(), Local::now());
+        app.open_recent_objects();
+
+        app.open_recent_object("bucket-2".into(), "dir1/file1.txt".into());
+
+        let AppEventType::NotifyWarn(msg) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        assert_eq!(msg, "Bucket 'bucket-2' is not available");
+        app.tx.send(AppEventType::NotifyInfo("end".into()));
+        assert!(matches!(rx.recv(), AppEventType::NotifyInfo(_)));
+        assert!(uris.lock().unwrap().is_empty());
+
+        // the recent objects are still shown
+        let buf = render(&mut app);
+        assert!(find_row(&buf, "bucket-2").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_restore_object_without_credentials() {
+        let (mut app, rx, uris) = setup_app(MockHttpClient::default(), false).await;
+        let file_detail = FileDetail {
+            storage_class: "GLACIER".into(),
+            ..file_detail("etag")
+        };
+
+        app.restore_object(file_detail, None, RestoreTier::Standard, 1);
+
+        let AppEventType::NotifyWarn(msg) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        assert_eq!(msg, "Objects cannot be restored without credentials");
+        assert!(uris.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_handle_loading_size_of_partial_download() {
+        let (app, rx, _) = setup_app(MockHttpClient::default(), false).await;
+
+        // the first 20 MB of a 2 GB object
+        let loading = app.handle_loading_size(2_000_000_000, Some(20_000_000), app.tx.clone());
+        loading(20_000_000);
+
+        let AppEventType::NotifyInfo(msg) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        assert!(
+            msg.starts_with("100% downloaded (20 MB out of 20 MB"),
+            "{}",
+            msg
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_loading_size_of_small_partial_download() {
+        let (app, rx, _) = setup_app(MockHttpClient::default(), false).await;
+
+        // no progress is shown for the first 1 MB of a large object
+        let loading = app.handle_loading_size(2_000_000_000, Some(1_000_000), app.tx.clone());
+        loading(1_000_000);
+
+        app.tx.send(AppEventType::NotifyInfo("end".into()));
+        let AppEventType::NotifyInfo(msg) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        assert_eq!(msg, "end");
+    }
+
+    #[rstest]
+    #[case("5eb63bbbe01eeed093cb22bb8f5acdc3", None, Some(EtagCheck::Match))]
+    #[case(
+        "5eb63bbbe01eeed093cb22bb8f5acdc3",
+        Some("v1"),
+        Some(EtagCheck::Skipped("not the latest version"))
+    )]
+    #[case(
+        "0123456789abcdef0123456789abcdef-2",
+        None,
+        Some(EtagCheck::Skipped("uploaded in multiple parts"))
+    )]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_download_object_checks_etag_in_task(
+        #[case] e_tag: &str,
+        #[case] version_id: Option<&str>,
+        #[case] expected: Option<EtagCheck>,
+    ) {
+        let http_client = MockHttpClient {
+            bodies: vec!["hello world"],
+            ..Default::default()
+        };
+        let (mut app, rx, _) = setup_app(http_client, false).await;
+        app.config.verify_download_md5 = true;
+        open_object_list(&mut app, &[], vec![file("file1.txt")], None);
+
+        app.download_object(file_detail(e_tag), version_id.map(Into::into));
+
+        let AppEventType::CompleteDownloadObject(Ok(result)) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        assert_eq!(result.etag_check, expected);
+    }
+
+    #[tokio::test]
+    async fn test_complete_download_object_without_verified_integrity() {
+        let (mut app, rx, _) = setup_app(MockHttpClient::default(), false).await;
+        let path = std::env::temp_dir().join(format!(
+            "stu-test-{}-download-without-verified-integrity.txt",
+            std::process::id()
+        ));
+        let path = path.to_string_lossy().to_string();
+        let obj = RawObject {
+            bytes: b"hello world".to_vec(),
+        };
+        let check = Some(EtagCheck::Skipped("uploaded in multiple parts"));
+
+        let result = CompleteDownloadObjectResult::new(Ok(obj), path.clone(), check);
+        app.complete_download_object(result);
+        std::fs::remove_file(&path).unwrap();
+
+        let AppEventType::NotifyWarn(msg) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        assert_eq!(
+            msg,
+            format!(
+                "Downloaded, but integrity not verified (uploaded in multiple parts): {}",
+                path
+            )
+        );
+    }
+}
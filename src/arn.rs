@@ -0,0 +1,180 @@
+/// ARN given instead of a bucket name, which the SDK accepts as the bucket of the requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessPointArn {
+    pub arn: String,
+    pub service: AccessPointService,
+    pub region: String,
+    pub account_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessPointService {
+    /// `arn:<partition>:s3:<region>:<account-id>:accesspoint/<name>`
+    AccessPoint,
+    /// `arn:<partition>:s3-object-lambda:<region>:<account-id>:accesspoint/<name>`
+    ObjectLambda,
+}
+
+impl AccessPointArn {
+    /// Parses the bucket name as an access point ARN.
+    ///
+    /// Returns `None` if it is not an ARN, and `Err` with the reason if the ARN is not supported.
+    pub fn parse(bucket: &str) -> Option<Result<AccessPointArn, String>> {
+        if !bucket.starts_with("arn:") {
+            return None;
+        }
+        Some(parse_arn(bucket))
+    }
+
+    /// Returns the ARN of the object accessed through the access point.
+    pub fn object_arn(&self, key: &str) -> String {
+        format!("{}/object/{}", self.arn, key)
+    }
+
+    /// Returns the path of the access point in the management console, e.g. `ap/<account-id>/<name>`.
+    pub fn console_path(&self) -> String {
+        let kind = match self.service {
+            AccessPointService::AccessPoint => "ap",
+            AccessPointService::ObjectLambda => "olap",
+        };
+        format!("{}/{}/{}", kind, self.account_id, self.name)
+    }
+
+    /// Returns the URL of the object accessed through the access point.
+    pub fn object_url(&self, key: &str) -> String {
+        let host = match self.service {
+            AccessPointService::AccessPoint => "s3-accesspoint",
+            AccessPointService::ObjectLambda => "s3-object-lambda",
+        };
+        format!(
+            "https://{}-{}.{}.{}.amazonaws.com/{}",
+            self.name, self.account_id, host, self.region, key
+        )
+    }
+}
+
+fn parse_arn(arn: &str) -> Result<AccessPointArn, String> {
+    let parts: Vec<&str> = arn.splitn(6, ':').collect();
+    let [_, partition, service, region, account_id, resource] = parts[..] else {
+        return Err(format!("Invalid ARN '{}'", arn));
+    };
+    let service = match service {
+        "s3" => AccessPointService::AccessPoint,
+        "s3-object-lambda" => AccessPointService::ObjectLambda,
+        "s3-outposts" => return Err("Outposts ARNs are not supported".into()),
+        _ => return Err(format!("ARN of service '{}' is not an S3 bucket", service)),
+    };
+    if partition.is_empty() {
+        return Err(format!("Invalid ARN '{}': partition is missing", arn));
+    }
+    let Some(name) = resource.strip_prefix("accesspoint/") else {
+        return Err(format!(
+            "Unsupported ARN resource '{}', only access points are supported",
+            resource
+        ));
+    };
+    if region.is_empty() {
+        // multi-region access points have no region, and require SigV4A
+        return Err("Multi-Region Access Point ARNs are not supported".into());
+    }
+    if account_id.len() != 12 || !account_id.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid account ID '{}' in ARN", account_id));
+    }
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid_name {
+        return Err(format!("Invalid access point name '{}' in ARN", name));
+    }
+    Ok(AccessPointArn {
+        arn: arn.to_string(),
+        service,
+        region: region.to_string(),
+        account_id: account_id.to_string(),
+        name: name.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(
+        "arn:aws:s3:us-east-1:123456789012:accesspoint/my-ap",
+        AccessPointService::AccessPoint,
+        "https://my-ap-123456789012.s3-accesspoint.us-east-1.amazonaws.com/dir/file.txt"
+    )]
+    #[case(
+        "arn:aws:s3-object-lambda:ap-northeast-1:123456789012:accesspoint/my-olap",
+        AccessPointService::ObjectLambda,
+        "https://my-olap-123456789012.s3-object-lambda.ap-northeast-1.amazonaws.com/dir/file.txt"
+    )]
+    fn test_parse_access_point_arn(
+        #[case] s: &str,
+        #[case] service: AccessPointService,
+        #[case] url: &str,
+    ) {
+        let arn = AccessPointArn::parse(s).unwrap().unwrap();
+        assert_eq!(arn.service, service);
+        assert_eq!(arn.account_id, "123456789012");
+        assert_eq!(
+            arn.object_arn("dir/file.txt"),
+            format!("{}/object/dir/file.txt", s)
+        );
+        assert_eq!(arn.object_url("dir/file.txt"), url);
+    }
+
+    #[rstest]
+    #[case(
+        "arn:aws:s3:us-east-1:123456789012:accesspoint/my-ap",
+        "ap/123456789012/my-ap"
+    )]
+    #[case(
+        "arn:aws:s3-object-lambda:us-east-1:123456789012:accesspoint/my-olap",
+        "olap/123456789012/my-olap"
+    )]
+    fn test_console_path(#[case] s: &str, #[case] expected: &str) {
+        let arn = AccessPointArn::parse(s).unwrap().unwrap();
+        assert_eq!(arn.console_path(), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "arn:aws:s3:::bucket-1",
+        "Unsupported ARN resource 'bucket-1', only access points are supported"
+    )]
+    #[case(
+        "arn:aws:s3::123456789012:accesspoint/mrap.mrap",
+        "Multi-Region Access Point ARNs are not supported"
+    )]
+    #[case(
+        "arn:aws:s3-outposts:us-east-1:123456789012:outpost/op-1/accesspoint/ap",
+        "Outposts ARNs are not supported"
+    )]
+    #[case(
+        "arn:aws:sqs:us-east-1:123456789012:queue",
+        "ARN of service 'sqs' is not an S3 bucket"
+    )]
+    #[case(
+        "arn:aws:s3:us-east-1:1234:accesspoint/ap",
+        "Invalid account ID '1234' in ARN"
+    )]
+    #[case(
+        "arn:aws:s3:us-east-1:123456789012:accesspoint/My_AP",
+        "Invalid access point name 'My_AP' in ARN"
+    )]
+    #[case("arn:aws:s3", "Invalid ARN 'arn:aws:s3'")]
+    fn test_parse_unsupported_arn(#[case] s: &str, #[case] expected: &str) {
+        assert_eq!(AccessPointArn::parse(s), Some(Err(expected.to_string())));
+    }
+
+    #[test]
+    fn test_parse_bucket_name() {
+        assert_eq!(AccessPointArn::parse("bucket-1"), None);
+    }
+}
@@ -1,23 +1,71 @@
-use std::fmt::Debug;
+use std::{error::Error, fmt::Debug, future::Future, sync::Arc, time::Duration};
 
-use aws_config::{meta::region::RegionProviderChain, BehaviorVersion};
-use aws_sdk_s3::{config::Region, operation::list_objects_v2::ListObjectsV2Output};
+use aws_config::{
+    meta::region::RegionProviderChain, timeout::TimeoutConfig, BehaviorVersion, ConfigLoader,
+};
+use aws_sdk_s3::{
+    config::{ProvideCredentials, Region, SharedCredentialsProvider},
+    error::{DisplayErrorContext, ProvideErrorMetadata, SdkError},
+    operation::list_objects_v2::ListObjectsV2Output,
+    presigning::PresigningConfig,
+    primitives::ByteStream,
+    types::{
+        GlacierJobParameters, Grant, MetadataDirective, RequestPayer, RestoreRequest,
+        ServerSideEncryption, StorageClass as S3StorageClass, Tier, Type,
+    },
+};
 use chrono::TimeZone;
+use itertools::Itertools;
 
 use crate::{
+    arn::AccessPointArn,
     cache::SimpleStringCache,
-    config::Config,
+    config::{ClientConfig, Config},
+    credentials::CredentialsChain,
     error::{AppError, Result},
-    object::{BucketItem, FileDetail, FileVersion, ObjectItem, RawObject},
+    limiter::RequestLimiter,
+    object::{
+        BucketGrant, BucketItem, FileDetail, FileVersion, ObjectItem, ObjectOwner, PrefixObject,
+        PublicAccessBlock, RawObject, RestoreStatus, RestoreTier, StorageClass, DELIMITER,
+    },
 };
 
-const DELIMITER: &str = "/";
+/// Target of the logs of the requests to S3.
+///
+/// Only the operation, its parameters (bucket, prefix, key, etc.) and the outcome are logged, never the credentials.
+pub const LOG_TARGET: &str = "stu::client::request";
+
+pub const ARCHIVED_OBJECT_MSG: &str = "Object is archived \u{2014} restore required";
 const DEFAULT_REGION: &str = "ap-northeast-1";
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 5;
+const DEFAULT_OPERATION_TIMEOUT_SECS: u64 = 30;
+
+const ACCESS_DENIED_ERROR_CODES: [&str; 2] = ["AccessDenied", "AllAccessDisabled"];
+const ACCESS_DENIED_STATUS: u16 = 403;
+const REQUEST_PAYER_HINT: &str = "if the bucket is requester pays, enable --request-payer";
+const PUBLIC_GROUP_URIS: [&str; 2] = [
+    "http://acs.amazonaws.com/groups/global/AllUsers",
+    "http://acs.amazonaws.com/groups/global/AuthenticatedUsers",
+];
+
+/// Options to build the client except the region, kept to rebuild it against another region.
+#[derive(Debug, Clone, Default)]
+pub struct ClientOptions {
+    pub endpoint_url: Option<String>,
+    pub profile: Option<String>,
+    pub client_config: ClientConfig,
+    pub no_sign_request: bool,
+    pub request_payer: bool,
+}
 
 pub struct Client {
     pub client: aws_sdk_s3::Client,
     region: String,
+    options: ClientOptions,
     bucket_region_cache: SimpleStringCache,
+    credentials_provider: Option<SharedCredentialsProvider>,
+    credentials_chain: Option<CredentialsChain>,
+    limiter: Arc<RequestLimiter>,
 }
 
 impl Debug for Client {
@@ -27,47 +75,156 @@ impl Debug for Client {
 }
 
 impl Client {
-    pub async fn new(
-        region: Option<String>,
-        endpoint_url: Option<String>,
-        profile: Option<String>,
+    /// Creates a new client.
+    ///
+    /// Timeouts can be overridden by `client_config`, otherwise the following defaults are used:
+    /// - connect timeout: 5 seconds
+    /// - operation timeout: 30 seconds (does not include reading the object body when downloading)
+    ///
+    /// If `options.no_sign_request` is true, credentials are not loaded and requests are sent unsigned.
+    ///
+    /// If `options.request_payer` is true (or enabled by `client_config`), object requests are sent as the requester agreeing to pay for them.
+    ///
+    /// The requests are limited by `client_config.max_concurrent_requests` and `client_config.max_requests_per_sec` if set.
+    pub async fn new(region: Option<String>, mut options: ClientOptions) -> Client {
+        let force_path_style = options.endpoint_url.is_some();
+        options.request_payer |= options.client_config.request_payer;
+        let mut config_loader = build_config_loader(
+            region.clone(),
+            options.endpoint_url.clone(),
+            options.profile.clone(),
+            options.client_config.clone(),
+            options.no_sign_request,
+        );
+        let credentials_chain = if options.no_sign_request {
+            None
+        } else {
+            let chain = CredentialsChain::new(region, options.profile.clone()).await;
+            config_loader = config_loader.credentials_provider(chain.clone());
+            Some(chain)
+        };
+        let bucket_region_cache = SimpleStringCache::new(Config::cache_file_path().unwrap());
+        Client::from_config_loader(
+            config_loader,
+            force_path_style,
+            options,
+            bucket_region_cache,
+            credentials_chain,
+        )
+        .await
+    }
+
+    /// Builds a new client with the same options against `region`.
+    pub async fn with_region(&self, region: String) -> Client {
+        let mut client = Client::new(Some(region), self.options.clone()).await;
+        // the limits apply to all the requests of the app, not per region
+        client.limiter = self.limiter.clone();
+        client
+    }
+
+    async fn from_config_loader(
+        config_loader: ConfigLoader,
+        force_path_style: bool,
+        options: ClientOptions,
+        bucket_region_cache: SimpleStringCache,
+        credentials_chain: Option<CredentialsChain>,
     ) -> Client {
-        let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-            .or_default_provider()
-            .or_else(DEFAULT_REGION);
-
-        let mut config_loader =
-            aws_config::defaults(BehaviorVersion::latest()).region(region_provider);
-        if let Some(url) = &endpoint_url {
-            config_loader = config_loader.endpoint_url(url);
-        }
-        if let Some(profile) = &profile {
-            config_loader = config_loader.profile_name(profile);
-        }
         let sdk_config = config_loader.load().await;
 
         let mut config_builder = aws_sdk_s3::config::Builder::from(&sdk_config);
-        if endpoint_url.is_some() {
+        if force_path_style {
             config_builder = config_builder.force_path_style(true);
         }
         let config = config_builder.build();
 
         let client = aws_sdk_s3::Client::from_conf(config);
         let region = sdk_config.region().unwrap().to_string();
-
-        let bucket_region_cache = SimpleStringCache::new(Config::cache_file_path().unwrap());
+        let credentials_provider = sdk_config.credentials_provider();
+        let limiter = Arc::new(RequestLimiter::new(
+            options.client_config.max_concurrent_requests,
+            options.client_config.max_requests_per_sec,
+        ));
 
         Client {
             client,
             region,
+            options,
             bucket_region_cache,
+            credentials_provider,
+            credentials_chain,
+            limiter,
+        }
+    }
+
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+
+    /// Returns true if requests are sent unsigned, so operations that require authentication are not available.
+    pub fn is_anonymous(&self) -> bool {
+        self.options.no_sign_request
+    }
+
+    /// Returns true if requests are sent with the requester agreeing to pay for the request and data transfer.
+    pub fn is_requester_pays(&self) -> bool {
+        self.options.request_payer
+    }
+
+    pub fn endpoint_url(&self) -> Option<&str> {
+        self.options.endpoint_url.as_deref()
+    }
+
+    /// Resolves the credentials and returns where they came from: the environment, the profile, IMDS, etc.
+    pub async fn credentials_source(&self) -> Result<String> {
+        if self.options.no_sign_request {
+            return Ok("none (unsigned requests)".to_string());
+        }
+        let provider = self
+            .credentials_provider
+            .as_ref()
+            .ok_or_else(|| AppError::msg("No credentials provider configured"))?;
+        provider
+            .provide_credentials()
+            .await
+            .map_err(|e| AppError::new("Failed to load credentials", e))?;
+        let source = self.credentials_chain.as_ref().and_then(|c| c.source());
+        // the provider is given directly (e.g. in the tests) if not built from the chain
+        Ok(source.unwrap_or_else(|| "custom provider".to_string()))
+    }
+
+    /// Sends the request after acquiring from the limiter shared by all the requests of the client.
+    async fn limited<T>(&self, request: impl Future<Output = T>) -> T {
+        let _permit = self.limiter.acquire().await;
+        request.await
+    }
+
+    // not requested by default, since most buckets do not need it and the owner is costly to include
+    fn fetch_owner(&self) -> Option<bool> {
+        self.options.client_config.fetch_owner.then_some(true)
+    }
+
+    fn request_payer(&self) -> Option<RequestPayer> {
+        self.options
+            .request_payer
+            .then_some(RequestPayer::Requester)
+    }
+
+    // requester pays buckets deny the requests without the request payer header
+    fn hint_request_payer(&self, e: AppError) -> AppError {
+        if self.options.request_payer || !e.is_access_denied() {
+            return e;
+        }
+        AppError {
+            msg: format!("{}; {}", e.msg, REQUEST_PAYER_HINT),
+            ..e
         }
     }
 
     pub async fn load_all_buckets(&self) -> Result<Vec<BucketItem>> {
-        let list_buckets_result = self.client.list_buckets().send().await;
+        let list_buckets_result = self.limited(self.client.list_buckets().send()).await;
+        log_request("ListBuckets", &[], &list_buckets_result);
         let list_buckets_output =
-            list_buckets_result.map_err(|e| AppError::new("Failed to load buckets", e))?;
+            list_buckets_result.map_err(|e| to_app_error("Failed to load buckets", e))?;
 
         let buckets: Vec<BucketItem> = list_buckets_output
             .buckets()
@@ -100,14 +257,13 @@ impl Client {
             return Ok(bucket_region);
         }
 
-        let bucket_region = self
-            .client
-            .get_bucket_location()
-            .bucket(bucket_name)
-            .send()
-            .await
+        let result = self
+            .limited(self.client.get_bucket_location().bucket(bucket_name).send())
+            .await;
+        log_request("GetBucketLocation", &[("bucket", bucket_name)], &result);
+        let bucket_region = result
             .map_err(|e| {
-                AppError::new(
+                to_app_error(
                     format!("Failed to fetch region for bucket {}", bucket_name),
                     e,
                 )
@@ -130,7 +286,29 @@ impl Client {
         Ok(bucket_region.to_string())
     }
 
+    /// Loads the bucket, which may be given as an access point ARN.
     pub async fn load_bucket(&self, name: &str) -> Result<BucketItem> {
+        if let Some(arn) = AccessPointArn::parse(name) {
+            let arn = arn.map_err(AppError::msg)?;
+            // the location of access points cannot be fetched, but it is included in the ARN
+            if arn.region != self.region {
+                return Err(AppError::msg(format!(
+                    "Access point '{}' is in region '{}', expected '{}'",
+                    name, arn.region, self.region
+                )));
+            }
+            return Ok(BucketItem {
+                name: name.to_string(),
+            });
+        }
+
+        if self.options.no_sign_request {
+            // the bucket location cannot be fetched without credentials, so trust the given region
+            return Ok(BucketItem {
+                name: name.to_string(),
+            });
+        }
+
         let region = self.get_bucket_region(name).await?;
         self.bucket_region_cache.write_cache().unwrap();
 
@@ -147,30 +325,55 @@ impl Client {
         Ok(bucket)
     }
 
-    pub async fn load_objects(&self, bucket: &str, prefix: &str) -> Result<Vec<ObjectItem>> {
+    /// Loads all objects under the prefix, calling `on_page` with the items of each page
+    /// that is followed by another one, so that they can be shown before the listing completes.
+    pub async fn load_objects<F>(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        mut on_page: F,
+    ) -> Result<Vec<ObjectItem>>
+    where
+        F: FnMut(Vec<ObjectItem>),
+    {
         let mut dirs_vec: Vec<Vec<ObjectItem>> = Vec::new();
         let mut files_vec: Vec<Vec<ObjectItem>> = Vec::new();
 
         let mut token: Option<String> = None;
         loop {
             let result = self
-                .client
-                .list_objects_v2()
-                .bucket(bucket)
-                .prefix(prefix)
-                .delimiter(DELIMITER)
-                .set_continuation_token(token)
-                .send()
+                .limited(
+                    self.client
+                        .list_objects_v2()
+                        .bucket(bucket)
+                        .prefix(prefix)
+                        .delimiter(DELIMITER)
+                        .set_continuation_token(token.clone())
+                        .set_fetch_owner(self.fetch_owner())
+                        .set_request_payer(self.request_payer())
+                        .send(),
+                )
                 .await;
-            let output = result.map_err(|e| AppError::new("Failed to load objects", e))?;
+            let params = [
+                ("bucket", bucket),
+                ("prefix", prefix),
+                ("continuation_token", token.as_deref().unwrap_or_default()),
+            ];
+            log_request("ListObjectsV2", &params, &result);
+            let output = result
+                .map_err(|e| self.hint_request_payer(to_app_error("Failed to load objects", e)))?;
 
             let dirs = objects_output_to_dirs(&output);
-            dirs_vec.push(dirs);
+            let files = objects_output_to_files(&output, self.options.client_config.fetch_owner);
 
-            let files = objects_output_to_files(&output);
+            token = output.next_continuation_token().map(String::from);
+            if token.is_some() {
+                on_page(dirs.iter().chain(files.iter()).cloned().collect());
+            }
+
+            dirs_vec.push(dirs);
             files_vec.push(files);
 
-            token = output.next_continuation_token().map(String::from);
             if token.is_none() {
                 break;
             }
@@ -181,21 +384,82 @@ impl Client {
         Ok(di.chain(fi).collect())
     }
 
+    /// Lists all objects under the prefix including the ones in the nested folders.
+    pub async fn list_all_objects(&self, bucket: &str, prefix: &str) -> Result<Vec<PrefixObject>> {
+        let mut objects = Vec::new();
+
+        let mut token: Option<String> = None;
+        loop {
+            let result = self
+                .limited(
+                    self.client
+                        .list_objects_v2()
+                        .bucket(bucket)
+                        .prefix(prefix)
+                        .set_continuation_token(token.clone())
+                        .set_request_payer(self.request_payer())
+                        .send(),
+                )
+                .await;
+            let params = [
+                ("bucket", bucket),
+                ("prefix", prefix),
+                ("continuation_token", token.as_deref().unwrap_or_default()),
+            ];
+            log_request("ListObjectsV2", &params, &result);
+            let output = result
+                .map_err(|e| self.hint_request_payer(to_app_error("Failed to load objects", e)))?;
+
+            for obj in output.contents() {
+                let key = obj.key().unwrap_or_default();
+                // some S3 compatible storages may omit it, which is reported instead of panicking
+                let last_modified = obj.last_modified().ok_or_else(|| {
+                    AppError::msg(format!("Last modified time of '{}' is missing", key))
+                })?;
+                objects.push(PrefixObject {
+                    key: key.to_string(),
+                    size_byte: obj.size().unwrap_or_default() as usize,
+                    last_modified: convert_datetime(last_modified),
+                });
+            }
+
+            token = output.next_continuation_token().map(String::from);
+            if token.is_none() {
+                break;
+            }
+        }
+
+        Ok(objects)
+    }
+
     pub async fn load_object_detail(
         &self,
         bucket: &str,
         key: &str,
         name: &str,
         size_byte: usize,
+        version_id: Option<String>,
     ) -> Result<FileDetail> {
         let result = self
-            .client
-            .head_object()
-            .bucket(bucket)
-            .key(key)
-            .send()
+            .limited(
+                self.client
+                    .head_object()
+                    .bucket(bucket)
+                    .key(key)
+                    .set_version_id(version_id.clone())
+                    .set_request_payer(self.request_payer())
+                    .send(),
+            )
             .await;
-        let output = result.map_err(|e| AppError::new("Failed to load object detail", e))?;
+        let params = [
+            ("bucket", bucket),
+            ("key", key),
+            ("version_id", version_id.as_deref().unwrap_or_default()),
+        ];
+        log_request("HeadObject", &params, &result);
+        let output = result.map_err(|e| {
+            self.hint_request_payer(to_app_error("Failed to load object detail", e))
+        })?;
 
         let name = name.to_owned();
         let last_modified = convert_datetime(output.last_modified().unwrap());
@@ -205,10 +469,21 @@ impl Client {
             .storage_class()
             .map_or("", |s| s.as_str())
             .to_string();
+        let encryption = if output.sse_customer_algorithm().is_some() {
+            Some("SSE-C".to_string())
+        } else {
+            output
+                .server_side_encryption()
+                .map(|e| e.as_str().to_string())
+        };
+        let kms_key_id = output.ssekms_key_id().map(String::from);
+        let bucket_key_enabled = output.bucket_key_enabled();
+        let content_encoding = output.content_encoding().map(String::from);
         let key = key.to_owned();
         let s3_uri = self.build_s3_uri(bucket, &key);
         let arn = self.build_arn(bucket, &key);
         let object_url = self.build_object_url(bucket, &key);
+        let restore_status = output.restore().and_then(parse_restore_status);
         Ok(FileDetail {
             name,
             size_byte,
@@ -216,22 +491,175 @@ impl Client {
             e_tag,
             content_type,
             storage_class,
+            encryption,
+            kms_key_id,
+            bucket_key_enabled,
+            content_encoding,
             key,
             s3_uri,
             arn,
             object_url,
+            restore_status,
         })
     }
 
+    pub async fn restore_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        version_id: Option<String>,
+        tier: RestoreTier,
+        days: i32,
+    ) -> Result<()> {
+        let tier = match tier {
+            RestoreTier::Expedited => Tier::Expedited,
+            RestoreTier::Standard => Tier::Standard,
+            RestoreTier::Bulk => Tier::Bulk,
+        };
+        let job_parameters = GlacierJobParameters::builder()
+            .tier(tier)
+            .build()
+            .map_err(|e| AppError::new("Failed to build restore request", e))?;
+        let restore_request = RestoreRequest::builder()
+            .days(days)
+            .glacier_job_parameters(job_parameters)
+            .build();
+
+        let result = self
+            .limited(
+                self.client
+                    .restore_object()
+                    .bucket(bucket)
+                    .key(key)
+                    .set_version_id(version_id.clone())
+                    .restore_request(restore_request)
+                    .set_request_payer(self.request_payer())
+                    .send(),
+            )
+            .await;
+        let params = [
+            ("bucket", bucket),
+            ("key", key),
+            ("version_id", version_id.as_deref().unwrap_or_default()),
+        ];
+        log_request("RestoreObject", &params, &result);
+        result.map_err(|e| self.hint_request_payer(to_app_error("Failed to restore object", e)))?;
+        Ok(())
+    }
+
+    /// Changes the storage class of the latest version by copying the object onto itself.
+    ///
+    /// The metadata and tags are copied, and the server-side encryption of `file_detail` (with its KMS key) is sent again,
+    /// since the copy is otherwise encrypted with the default of the bucket.
+    /// The ACL is not copied, so it is reset to the default of the bucket, and a new version is created if the bucket is versioned.
+    ///
+    /// Objects encrypted with SSE-C cannot be copied, since the key is not known.
+    pub async fn transition_storage_class(
+        &self,
+        bucket: &str,
+        file_detail: &FileDetail,
+        storage_class: StorageClass,
+    ) -> Result<()> {
+        let key = file_detail.key.as_str();
+        let copy_source = encode_copy_source(bucket, key);
+        let result = self
+            .limited(
+                self.client
+                    .copy_object()
+                    .bucket(bucket)
+                    .key(key)
+                    .copy_source(&copy_source)
+                    .storage_class(S3StorageClass::from(storage_class.name()))
+                    .metadata_directive(MetadataDirective::Copy)
+                    .set_server_side_encryption(
+                        file_detail
+                            .encryption
+                            .as_deref()
+                            .map(ServerSideEncryption::from),
+                    )
+                    .set_ssekms_key_id(file_detail.kms_key_id.clone())
+                    .set_bucket_key_enabled(file_detail.bucket_key_enabled)
+                    .set_request_payer(self.request_payer())
+                    .send(),
+            )
+            .await;
+        let params = [
+            ("bucket", bucket),
+            ("key", key),
+            ("storage_class", storage_class.name()),
+        ];
+        log_request("CopyObject", &params, &result);
+        result.map_err(|e| {
+            self.hint_request_payer(to_app_error("Failed to change storage class", e))
+        })?;
+        Ok(())
+    }
+
+    /// Returns whether the bucket policy makes the bucket public, or `None` if the bucket has no policy.
+    pub async fn get_bucket_policy_status(&self, bucket: &str) -> Result<Option<bool>> {
+        let result = self
+            .limited(self.client.get_bucket_policy_status().bucket(bucket).send())
+            .await;
+        log_request("GetBucketPolicyStatus", &[("bucket", bucket)], &result);
+        match result {
+            Ok(output) => {
+                let public = output.policy_status().and_then(|s| s.is_public());
+                Ok(Some(public.unwrap_or(false)))
+            }
+            Err(e) if e.code() == Some("NoSuchBucketPolicy") => Ok(None),
+            Err(e) => Err(to_app_error("Failed to load bucket policy status", e)),
+        }
+    }
+
+    pub async fn get_bucket_acl(&self, bucket: &str) -> Result<Vec<BucketGrant>> {
+        let result = self
+            .limited(self.client.get_bucket_acl().bucket(bucket).send())
+            .await;
+        log_request("GetBucketAcl", &[("bucket", bucket)], &result);
+        let output = result.map_err(|e| to_app_error("Failed to load bucket ACL", e))?;
+
+        let grants = output.grants().iter().map(convert_grant).collect();
+        Ok(grants)
+    }
+
+    /// Returns the public access block configuration of the bucket, or `None` if it is not configured.
+    pub async fn get_public_access_block(&self, bucket: &str) -> Result<Option<PublicAccessBlock>> {
+        let result = self
+            .limited(self.client.get_public_access_block().bucket(bucket).send())
+            .await;
+        log_request("GetPublicAccessBlock", &[("bucket", bucket)], &result);
+        match result {
+            Ok(output) => {
+                let block = output
+                    .public_access_block_configuration()
+                    .map(|c| PublicAccessBlock {
+                        block_public_acls: c.block_public_acls().unwrap_or(false),
+                        ignore_public_acls: c.ignore_public_acls().unwrap_or(false),
+                        block_public_policy: c.block_public_policy().unwrap_or(false),
+                        restrict_public_buckets: c.restrict_public_buckets().unwrap_or(false),
+                    });
+                Ok(block)
+            }
+            Err(e) if e.code() == Some("NoSuchPublicAccessBlockConfiguration") => Ok(None),
+            Err(e) => Err(to_app_error("Failed to load public access block", e)),
+        }
+    }
+
     fn build_s3_uri(&self, bucket: &str, key: &str) -> String {
         format!("s3://{}/{}", bucket, key)
     }
 
     fn build_arn(&self, bucket: &str, key: &str) -> String {
+        if let Some(Ok(arn)) = AccessPointArn::parse(bucket) {
+            return arn.object_arn(key);
+        }
         format!("arn:aws:s3:::{}/{}", bucket, key)
     }
 
     fn build_object_url(&self, bucket: &str, key: &str) -> String {
+        if let Some(Ok(arn)) = AccessPointArn::parse(bucket) {
+            return arn.object_url(key);
+        }
         format!(
             "https://{}.s3.{}.amazonaws.com/{}",
             bucket, self.region, key
@@ -240,13 +668,23 @@ impl Client {
 
     pub async fn load_object_versions(&self, bucket: &str, key: &str) -> Result<Vec<FileVersion>> {
         let result = self
-            .client
-            .list_object_versions()
-            .bucket(bucket)
-            .prefix(key)
-            .send()
+            .limited(
+                self.client
+                    .list_object_versions()
+                    .bucket(bucket)
+                    .prefix(key)
+                    .set_request_payer(self.request_payer())
+                    .send(),
+            )
             .await;
-        let output = result.map_err(|e| AppError::new("Failed to load object versions", e))?;
+        log_request(
+            "ListObjectVersions",
+            &[("bucket", bucket), ("prefix", key)],
+            &result,
+        );
+        let output = result.map_err(|e| {
+            self.hint_request_payer(to_app_error("Failed to load object versions", e))
+        })?;
 
         let versions = output
             .versions()
@@ -273,20 +711,52 @@ impl Client {
         key: &str,
         version_id: Option<String>,
         size_byte: usize,
+        limit: Option<usize>,
         f: F,
     ) -> Result<RawObject>
     where
         F: Fn(usize),
     {
-        let mut request = self.client.get_object().bucket(bucket).key(key);
-        if let Some(version_id) = version_id {
+        let mut request = self
+            .client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .set_request_payer(self.request_payer());
+        if let Some(version_id) = &version_id {
             request = request.version_id(version_id);
         }
+        // only the first `limit` bytes are downloaded
+        let range = limit
+            .filter(|limit| *limit > 0)
+            .map(|limit| format!("bytes=0-{}", limit - 1));
+        if let Some(range) = &range {
+            request = request.range(range);
+        }
 
+        // the permit is held until the body is collected, which takes most of the time
+        let _permit = self.limiter.acquire().await;
         let result = request.send().await;
-        let output = result.map_err(|e| AppError::new("Failed to download object", e))?;
+        let params = [
+            ("bucket", bucket),
+            ("key", key),
+            ("version_id", version_id.as_deref().unwrap_or_default()),
+            ("range", range.as_deref().unwrap_or_default()),
+        ];
+        log_request("GetObject", &params, &result);
+        let output = result.map_err(|e| {
+            let archived = e
+                .as_service_error()
+                .is_some_and(|e| e.is_invalid_object_state());
+            if archived {
+                AppError::new(ARCHIVED_OBJECT_MSG, e)
+            } else {
+                self.hint_request_payer(to_app_error("Failed to download object", e))
+            }
+        })?;
 
-        let mut bytes: Vec<u8> = Vec::with_capacity(size_byte);
+        let capacity = limit.map_or(size_byte, |limit| limit.min(size_byte));
+        let mut bytes: Vec<u8> = Vec::with_capacity(capacity);
         let mut stream = output.body;
         let mut i = 0;
         while let Some(buf) = stream // buf: 32 KiB
@@ -307,6 +777,51 @@ impl Client {
         Ok(RawObject { bytes })
     }
 
+    /// Returns a presigned URL which allows anyone with it to upload an object to the key until it expires.
+    ///
+    /// If `content_type` is set, it is signed, so the upload must be sent with the same `Content-Type` header.
+    pub async fn presign_put(
+        &self,
+        bucket: &str,
+        key: &str,
+        expires_in: Duration,
+        content_type: Option<&str>,
+    ) -> Result<String> {
+        let config = PresigningConfig::expires_in(expires_in)
+            .map_err(|e| AppError::new("Invalid expiration of presigned URL", e))?;
+        let request = self
+            .client
+            .put_object()
+            .bucket(bucket)
+            .key(key)
+            .set_content_type(content_type.map(String::from))
+            .set_request_payer(self.request_payer());
+        let presigned = request
+            .presigned(config)
+            .await
+            .map_err(|e| to_app_error("Failed to presign URL", e))?;
+        Ok(presigned.uri().to_string())
+    }
+
+    /// Puts an empty object with the key ending with the delimiter, which is listed as a folder.
+    pub async fn create_folder(&self, bucket: &str, prefix: &str, name: &str) -> Result<()> {
+        let key = format!("{}{}{}", prefix, name, DELIMITER);
+        let result = self
+            .limited(
+                self.client
+                    .put_object()
+                    .bucket(bucket)
+                    .key(&key)
+                    .body(ByteStream::from_static(&[]))
+                    .set_request_payer(self.request_payer())
+                    .send(),
+            )
+            .await;
+        log_request("PutObject", &[("bucket", bucket), ("key", &key)], &result);
+        result.map_err(|e| self.hint_request_payer(to_app_error("Failed to create folder", e)))?;
+        Ok(())
+    }
+
     pub fn open_management_console_buckets(&self) -> Result<()> {
         let path = format!(
             "https://s3.console.aws.amazon.com/s3/buckets?region={}",
@@ -316,10 +831,7 @@ impl Client {
     }
 
     pub fn open_management_console_list(&self, bucket: &str, prefix: &str) -> Result<()> {
-        let path = format!(
-            "https://s3.console.aws.amazon.com/s3/buckets/{}?region={}&prefix={}",
-            bucket, self.region, prefix
-        );
+        let path = self.management_console_list_url(bucket, prefix);
         open::that(path).map_err(AppError::error)
     }
 
@@ -329,11 +841,116 @@ impl Client {
         prefix: &str,
         name: &str,
     ) -> Result<()> {
-        let path = format!(
+        let path = self.management_console_object_url(bucket, prefix, name);
+        open::that(path).map_err(AppError::error)
+    }
+
+    fn management_console_list_url(&self, bucket: &str, prefix: &str) -> String {
+        let location = match AccessPointArn::parse(bucket) {
+            Some(Ok(arn)) => arn.console_path(),
+            _ => format!("buckets/{}", bucket),
+        };
+        format!(
+            "https://s3.console.aws.amazon.com/s3/{}?region={}&prefix={}",
+            location, self.region, prefix
+        )
+    }
+
+    fn management_console_object_url(&self, bucket: &str, prefix: &str, name: &str) -> String {
+        if let Some(Ok(_)) = AccessPointArn::parse(bucket) {
+            // the console has no page of an object in an access point, so the folder containing it is shown
+            return self.management_console_list_url(bucket, prefix);
+        }
+        format!(
             "https://s3.console.aws.amazon.com/s3/object/{}?region={}&prefix={}{}",
             bucket, self.region, prefix, name
-        );
-        open::that(path).map_err(AppError::error)
+        )
+    }
+}
+
+// the copy source must be URL-encoded, except for the delimiters
+//
+// objects in access points are specified by the object ARN, which is encoded as a whole
+fn encode_copy_source(bucket: &str, key: &str) -> String {
+    let source = match AccessPointArn::parse(bucket) {
+        Some(Ok(arn)) => arn.object_arn(key),
+        _ => format!("{}/{}", bucket, key),
+    };
+    source
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn to_app_error<E>(msg: impl Into<String>, e: SdkError<E>) -> AppError
+where
+    E: Error + ProvideErrorMetadata + Send + Sync + 'static,
+{
+    match e {
+        SdkError::TimeoutError(_) => AppError::timeout(msg, e),
+        _ if is_access_denied(&e) => AppError::access_denied(msg, e),
+        _ => AppError::new(msg, e),
+    }
+}
+
+fn is_access_denied<E: ProvideErrorMetadata>(e: &SdkError<E>) -> bool {
+    match e.code() {
+        // 403 is also returned for other errors, e.g. `SignatureDoesNotMatch`
+        Some(code) => ACCESS_DENIED_ERROR_CODES.contains(&code),
+        // HEAD responses have no body, so only the status tells it
+        None => e
+            .raw_response()
+            .is_some_and(|r| r.status().as_u16() == ACCESS_DENIED_STATUS),
+    }
+}
+
+fn convert_grant(grant: &Grant) -> BucketGrant {
+    let grantee = grant.grantee();
+    let uri = grantee.and_then(|g| g.uri());
+    let name = match grantee {
+        Some(g) if g.r#type() == &Type::Group => {
+            // e.g. http://acs.amazonaws.com/groups/global/AllUsers
+            let uri = uri.unwrap_or_default();
+            format!("{} (group)", uri.rsplit('/').next().unwrap_or(uri))
+        }
+        Some(g) => g
+            .display_name()
+            .or(g.email_address())
+            .or(g.id())
+            .unwrap_or_default()
+            .to_string(),
+        None => String::new(),
+    };
+    let permission = grant.permission().map_or("", |p| p.as_str()).to_string();
+    let public = uri.is_some_and(|uri| PUBLIC_GROUP_URIS.contains(&uri));
+    BucketGrant {
+        grantee: name,
+        permission,
+        public,
+    }
+}
+
+/// Parses the value of the `x-amz-restore` header.
+///
+/// e.g. `ongoing-request="false", expiry-date="Fri, 21 Dec 2012 00:00:00 GMT"`
+fn parse_restore_status(s: &str) -> Option<RestoreStatus> {
+    let value_of = |name: &str| {
+        let start = s.find(&format!("{}=\"", name))? + name.len() + 2;
+        let len = s[start..].find('"')?;
+        Some(&s[start..start + len])
+    };
+    match value_of("ongoing-request")? {
+        "true" => Some(RestoreStatus::InProgress),
+        "false" => {
+            let expiry_date = value_of("expiry-date").map(String::from);
+            Some(RestoreStatus::Restored { expiry_date })
+        }
+        _ => None,
     }
 }
 
@@ -350,26 +967,101 @@ fn objects_output_to_dirs(output: &ListObjectsV2Output) -> Vec<ObjectItem> {
         .collect()
 }
 
-fn objects_output_to_files(output: &ListObjectsV2Output) -> Vec<ObjectItem> {
+fn objects_output_to_files(output: &ListObjectsV2Output, fetch_owner: bool) -> Vec<ObjectItem> {
     let objects = output.contents();
     objects
         .iter()
+        // the placeholder of the folder itself (created as a folder) is not an item in it
+        .filter(|file| !is_folder_placeholder(file.key().unwrap(), output.prefix()))
         .map(|file| {
             let path = file.key().unwrap();
             let paths = parse_path(path, false);
             let name = paths.last().unwrap().to_owned();
             let size_byte = file.size().unwrap() as usize;
             let last_modified = convert_datetime(file.last_modified().unwrap());
+            // S3 omits the owner if it is not available, which is shown as absent rather than not fetched
+            let owner = fetch_owner.then(|| ObjectOwner {
+                display_name: file
+                    .owner()
+                    .and_then(|o| o.display_name())
+                    .map(String::from),
+                id: file.owner().and_then(|o| o.id()).map(String::from),
+            });
             ObjectItem::File {
                 name,
                 size_byte,
                 last_modified,
+                owner,
             }
         })
         .collect()
 }
 
-fn parse_path(path: &str, dir: bool) -> Vec<String> {
+fn is_folder_placeholder(key: &str, prefix: Option<&str>) -> bool {
+    key.ends_with(DELIMITER) && prefix == Some(key)
+}
+
+fn build_config_loader(
+    region: Option<String>,
+    endpoint_url: Option<String>,
+    profile: Option<String>,
+    client_config: ClientConfig,
+    no_sign_request: bool,
+) -> ConfigLoader {
+    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
+        .or_default_provider()
+        .or_else(DEFAULT_REGION);
+
+    let connect_timeout = client_config
+        .connect_timeout_secs
+        .unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECS);
+    let operation_timeout = client_config
+        .operation_timeout_secs
+        .unwrap_or(DEFAULT_OPERATION_TIMEOUT_SECS);
+    let timeout_config = TimeoutConfig::builder()
+        .connect_timeout(Duration::from_secs(connect_timeout))
+        .operation_timeout(Duration::from_secs(operation_timeout))
+        .build();
+
+    let mut config_loader = aws_config::defaults(BehaviorVersion::latest())
+        .region(region_provider)
+        .timeout_config(timeout_config);
+    if let Some(url) = &endpoint_url {
+        config_loader = config_loader.endpoint_url(url);
+    }
+    if let Some(profile) = &profile {
+        config_loader = config_loader.profile_name(profile);
+    }
+    if no_sign_request {
+        config_loader = config_loader.no_credentials();
+    }
+    config_loader
+}
+
+fn log_request<T, E>(
+    operation: &str,
+    params: &[(&str, &str)],
+    result: &std::result::Result<T, SdkError<E>>,
+) where
+    E: Error + 'static,
+{
+    let params = params
+        .iter()
+        .filter(|(_, v)| !v.is_empty())
+        .map(|(k, v)| format!("{}={:?}", k, v))
+        .join(" ");
+    match result {
+        Ok(_) => {
+            tracing::info!(target: LOG_TARGET, "{} {} succeeded", operation, params);
+        }
+        Err(e) => {
+            let e = DisplayErrorContext(e);
+            tracing::warn!(target: LOG_TARGET, "{} {} failed: {}", operation, params, e);
+        }
+    }
+}
+
+pub fn parse_path(path: &str, dir: bool) -> Vec<String> {
     let ss: Vec<String> = path.split(DELIMITER).map(String::from).collect();
     if dir {
         let n = ss.len() - 1;
@@ -383,3 +1075,949 @@ fn convert_datetime(dt: &aws_smithy_types::DateTime) -> chrono::DateTime<chrono:
     let nanos = dt.as_nanos();
     chrono::Local.timestamp_nanos(nanos as i64)
 }
+
+#[cfg(test)]
+pub mod test_util {
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex,
+        },
+    };
+
+    use aws_smithy_runtime_api::{
+        client::{
+            http::{
+                HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings,
+                SharedHttpConnector,
+            },
+            orchestrator::{HttpRequest, HttpResponse},
+            runtime_components::RuntimeComponents,
+        },
+        http::StatusCode,
+    };
+    use aws_smithy_types::body::SdkBody;
+
+    use super::*;
+
+    /// Returns the response bodies in order (the last one repeatedly) and records the headers of the received requests.
+    ///
+    /// The requests for `denied_keys` are responded with 403 and an empty body.
+    ///
+    /// If `delay` is set, the responses are returned after it, recording the maximum number of the requests in flight.
+    #[derive(Debug, Clone, Default)]
+    pub struct MockHttpClient {
+        // the last one is returned for the rest of the requests, empty if not set
+        pub bodies: Vec<&'static str>,
+        // 200 if not set
+        pub status: Option<u16>,
+        // all the headers of each request, with the names in lowercase
+        pub request_headers: Arc<Mutex<Vec<HashMap<String, String>>>>,
+        pub uris: Arc<Mutex<Vec<String>>>,
+        pub denied_keys: Vec<&'static str>,
+        pub delay: Option<Duration>,
+        pub in_flight: Arc<AtomicUsize>,
+        pub max_in_flight: Arc<AtomicUsize>,
+    }
+
+    impl HttpConnector for MockHttpClient {
+        fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+            let all_headers = request
+                .headers()
+                .iter()
+                .map(|(name, value)| (name.to_lowercase(), value.to_string()))
+                .collect();
+            let mut request_headers = self.request_headers.lock().unwrap();
+            request_headers.push(all_headers);
+            self.uris.lock().unwrap().push(request.uri().to_string());
+
+            let path = request.uri().split('?').next().unwrap_or_default();
+            let denied = self
+                .denied_keys
+                .iter()
+                .any(|key| path.ends_with(&format!("/{}", key)));
+
+            let (body, status) = if denied {
+                ("", 403)
+            } else {
+                let i = (request_headers.len() - 1).min(self.bodies.len().saturating_sub(1));
+                let body = self.bodies.get(i).copied().unwrap_or_default();
+                (body, self.status.unwrap_or(200))
+            };
+            let status = StatusCode::try_from(status).unwrap();
+            let response = HttpResponse::new(status, SdkBody::from(body));
+            let Some(delay) = self.delay else {
+                return HttpConnectorFuture::ready(Ok(response));
+            };
+            let in_flight = self.in_flight.clone();
+            let max_in_flight = self.max_in_flight.clone();
+            HttpConnectorFuture::new(async move {
+                let n = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(n, Ordering::SeqCst);
+                tokio::time::sleep(delay).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(response)
+            })
+        }
+    }
+
+    impl HttpClient for MockHttpClient {
+        fn http_connector(
+            &self,
+            _: &HttpConnectorSettings,
+            _: &RuntimeComponents,
+        ) -> SharedHttpConnector {
+            SharedHttpConnector::new(self.clone())
+        }
+    }
+
+    /// Returns the values of the header in the recorded requests, in order.
+    pub fn header_values(
+        request_headers: &Mutex<Vec<HashMap<String, String>>>,
+        name: &str,
+    ) -> Vec<Option<String>> {
+        let request_headers = request_headers.lock().unwrap();
+        request_headers
+            .iter()
+            .map(|headers| headers.get(name).cloned())
+            .collect()
+    }
+
+    pub fn anonymous_options() -> ClientOptions {
+        ClientOptions {
+            no_sign_request: true,
+            ..Default::default()
+        }
+    }
+
+    /// Returns a client sending the requests to `http_client`, with a bucket region cache of its own.
+    pub async fn test_client(http_client: MockHttpClient, options: ClientOptions) -> Client {
+        let config_loader = build_config_loader(
+            Some("us-east-1".into()),
+            None,
+            None,
+            options.client_config.clone(),
+            options.no_sign_request,
+        )
+        .http_client(http_client);
+        Client::from_config_loader(config_loader, false, options, test_cache(), None).await
+    }
+
+    /// Returns the client signing the requests with the example credentials, e.g. for presigning.
+    pub async fn test_client_with_credentials(http_client: MockHttpClient) -> Client {
+        let credentials = aws_sdk_s3::config::Credentials::new(
+            "AKIDEXAMPLE",
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            None,
+            None,
+            "test",
+        );
+        let options = ClientOptions::default();
+        let config_loader = build_config_loader(
+            Some("us-east-1".into()),
+            None,
+            None,
+            options.client_config.clone(),
+            options.no_sign_request,
+        )
+        .credentials_provider(credentials)
+        .http_client(http_client);
+        Client::from_config_loader(config_loader, false, options, test_cache(), None).await
+    }
+
+    fn test_cache() -> SimpleStringCache {
+        static CACHE_ID: AtomicUsize = AtomicUsize::new(0);
+
+        let cache_id = CACHE_ID.fetch_add(1, Ordering::SeqCst);
+        let cache_path = std::env::temp_dir().join(format!(
+            "stu-test-{}-{}-cache.txt",
+            std::process::id(),
+            cache_id
+        ));
+        SimpleStringCache::new(cache_path.to_string_lossy().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use aws_sdk_s3::{error::ErrorMetadata, operation::head_object::HeadObjectError};
+    use aws_smithy_runtime_api::{client::orchestrator::HttpResponse, http::StatusCode};
+    use aws_smithy_types::body::SdkBody;
+    use rstest::rstest;
+
+    use crate::error::AppErrorKind;
+
+    use super::{test_util::*, *};
+
+    #[tokio::test]
+    async fn test_list_all_objects_without_last_modified() {
+        let http_client = MockHttpClient {
+            bodies: vec![
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>bucket-1</Name>
+  <Prefix>dir1/</Prefix>
+  <KeyCount>1</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+  <Contents>
+    <Key>dir1/file1.txt</Key>
+    <Size>1024</Size>
+  </Contents>
+</ListBucketResult>"#,
+            ],
+            ..Default::default()
+        };
+        let client = test_client(http_client, anonymous_options()).await;
+
+        let e = client
+            .list_all_objects("bucket-1", "dir1/")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            e.notification_msg(),
+            "Last modified time of 'dir1/file1.txt' is missing"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_objects_no_sign_request() {
+        let http_client = MockHttpClient {
+            bodies: vec![
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>public-bucket</Name>
+  <Prefix></Prefix>
+  <KeyCount>2</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <Delimiter>/</Delimiter>
+  <IsTruncated>false</IsTruncated>
+  <Contents>
+    <Key>file1.txt</Key>
+    <LastModified>2024-01-02T13:01:02.000Z</LastModified>
+    <ETag>&quot;etag&quot;</ETag>
+    <Size>1024</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+  <CommonPrefixes>
+    <Prefix>dir1/</Prefix>
+  </CommonPrefixes>
+</ListBucketResult>"#,
+            ],
+            ..Default::default()
+        };
+        let request_headers = http_client.request_headers.clone();
+
+        let client = test_client(http_client, anonymous_options()).await;
+
+        let items = client
+            .load_objects("public-bucket", "", |_| {})
+            .await
+            .unwrap();
+        let names: Vec<&str> = items.iter().map(|item| item.name()).collect();
+        assert_eq!(names, ["dir1", "file1.txt"]);
+
+        assert!(client.is_anonymous());
+        assert_eq!(header_values(&request_headers, "authorization"), [None]);
+    }
+
+    #[tokio::test]
+    async fn test_limit_concurrent_requests() {
+        let http_client = MockHttpClient {
+            bodies: vec![
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>bucket-1</Name>
+  <Prefix></Prefix>
+  <KeyCount>0</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <Delimiter>/</Delimiter>
+  <IsTruncated>false</IsTruncated>
+</ListBucketResult>"#,
+            ],
+            delay: Some(Duration::from_millis(20)),
+            ..Default::default()
+        };
+        let max_in_flight = http_client.max_in_flight.clone();
+
+        let client_config = ClientConfig {
+            max_concurrent_requests: Some(2),
+            ..Default::default()
+        };
+        let options = ClientOptions {
+            client_config,
+            ..anonymous_options()
+        };
+        let client = Arc::new(test_client(http_client, options).await);
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client.load_objects("bucket-1", "", |_| {}).await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[rstest]
+    #[case(false, vec![None, None])]
+    #[case(true, vec![
+        Some(ObjectOwner { display_name: Some("owner-1".into()), id: Some("id-1".into()) }),
+        Some(ObjectOwner::default()),
+    ])]
+    #[tokio::test]
+    async fn test_load_objects_fetch_owner(
+        #[case] fetch_owner: bool,
+        #[case] expected: Vec<Option<ObjectOwner>>,
+    ) {
+        // the owner of the second object is omitted, as in buckets enforcing the bucket owner
+        let http_client = MockHttpClient {
+            bodies: vec![
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>bucket-1</Name>
+  <Prefix></Prefix>
+  <KeyCount>2</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <Delimiter>/</Delimiter>
+  <IsTruncated>false</IsTruncated>
+  <Contents>
+    <Key>file1.txt</Key>
+    <LastModified>2024-01-02T13:01:02.000Z</LastModified>
+    <ETag>&quot;etag&quot;</ETag>
+    <Size>1024</Size>
+    <Owner>
+      <DisplayName>owner-1</DisplayName>
+      <ID>id-1</ID>
+    </Owner>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+  <Contents>
+    <Key>file2.txt</Key>
+    <LastModified>2024-01-02T13:01:02.000Z</LastModified>
+    <ETag>&quot;etag&quot;</ETag>
+    <Size>2048</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+</ListBucketResult>"#,
+            ],
+            ..Default::default()
+        };
+
+        let client_config = ClientConfig {
+            fetch_owner,
+            ..Default::default()
+        };
+        let options = ClientOptions {
+            client_config,
+            ..anonymous_options()
+        };
+        let client = test_client(http_client, options).await;
+
+        let items = client.load_objects("bucket-1", "", |_| {}).await.unwrap();
+        let owners: Vec<Option<ObjectOwner>> = items
+            .into_iter()
+            .map(|item| match item {
+                ObjectItem::File { owner, .. } => owner,
+                ObjectItem::Dir { .. } => None,
+            })
+            .collect();
+        assert_eq!(owners, expected);
+    }
+
+    #[tokio::test]
+    async fn test_load_objects_pages() {
+        let http_client = MockHttpClient {
+            bodies: vec![
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>bucket-1</Name>
+  <Prefix></Prefix>
+  <KeyCount>2</KeyCount>
+  <MaxKeys>2</MaxKeys>
+  <Delimiter>/</Delimiter>
+  <IsTruncated>true</IsTruncated>
+  <NextContinuationToken>token1</NextContinuationToken>
+  <Contents>
+    <Key>file1.txt</Key>
+    <LastModified>2024-01-02T13:01:02.000Z</LastModified>
+    <ETag>&quot;etag&quot;</ETag>
+    <Size>1024</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+  <CommonPrefixes>
+    <Prefix>dir1/</Prefix>
+  </CommonPrefixes>
+</ListBucketResult>"#,
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>bucket-1</Name>
+  <Prefix></Prefix>
+  <KeyCount>2</KeyCount>
+  <MaxKeys>2</MaxKeys>
+  <Delimiter>/</Delimiter>
+  <IsTruncated>false</IsTruncated>
+  <ContinuationToken>token1</ContinuationToken>
+  <Contents>
+    <Key>file2.txt</Key>
+    <LastModified>2024-01-02T13:01:02.000Z</LastModified>
+    <ETag>&quot;etag&quot;</ETag>
+    <Size>1024</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+  <CommonPrefixes>
+    <Prefix>dir2/</Prefix>
+  </CommonPrefixes>
+</ListBucketResult>"#,
+            ],
+            ..Default::default()
+        };
+        let requests = http_client.request_headers.clone();
+
+        let client = test_client(http_client, anonymous_options()).await;
+
+        let mut pages: Vec<Vec<String>> = Vec::new();
+        let items = client
+            .load_objects("bucket-1", "", |items| {
+                pages.push(items.iter().map(|item| item.name().to_string()).collect());
+            })
+            .await
+            .unwrap();
+
+        // only the pages followed by another one are passed
+        assert_eq!(pages, [["dir1", "file1.txt"]]);
+        let names: Vec<&str> = items.iter().map(|item| item.name()).collect();
+        assert_eq!(names, ["dir1", "dir2", "file1.txt", "file2.txt"]);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_all_objects() {
+        let http_client = MockHttpClient {
+            bodies: vec![
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>bucket-1</Name>
+  <Prefix>dir1/</Prefix>
+  <KeyCount>2</KeyCount>
+  <MaxKeys>2</MaxKeys>
+  <IsTruncated>true</IsTruncated>
+  <NextContinuationToken>token1</NextContinuationToken>
+  <Contents>
+    <Key>dir1/</Key>
+    <LastModified>2024-01-02T13:01:02.000Z</LastModified>
+    <ETag>&quot;etag&quot;</ETag>
+    <Size>0</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+  <Contents>
+    <Key>dir1/file1.txt</Key>
+    <LastModified>2024-01-02T13:01:02.000Z</LastModified>
+    <ETag>&quot;etag&quot;</ETag>
+    <Size>1024</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+</ListBucketResult>"#,
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>bucket-1</Name>
+  <Prefix>dir1/</Prefix>
+  <KeyCount>1</KeyCount>
+  <MaxKeys>2</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+  <ContinuationToken>token1</ContinuationToken>
+  <Contents>
+    <Key>dir1/dir2/file2.txt</Key>
+    <LastModified>2024-01-02T13:01:02.000Z</LastModified>
+    <ETag>&quot;etag&quot;</ETag>
+    <Size>2048</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+</ListBucketResult>"#,
+            ],
+            ..Default::default()
+        };
+
+        let client = test_client(http_client, anonymous_options()).await;
+
+        let objects = client.list_all_objects("bucket-1", "dir1/").await.unwrap();
+        let objects: Vec<(&str, usize)> = objects
+            .iter()
+            .map(|obj| (obj.key.as_str(), obj.size_byte))
+            .collect();
+        assert_eq!(
+            objects,
+            [
+                ("dir1/", 0),
+                ("dir1/file1.txt", 1024),
+                ("dir1/dir2/file2.txt", 2048)
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("dir1/", Some("dir1/"), true)]
+    #[case("dir1/dir2/", Some("dir1/"), false)]
+    #[case("dir1/file", Some("dir1/file"), false)]
+    #[case("dir1/", None, false)]
+    fn test_is_folder_placeholder(
+        #[case] key: &str,
+        #[case] prefix: Option<&str>,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(is_folder_placeholder(key, prefix), expected);
+    }
+
+    #[rstest]
+    #[case(None, "host")]
+    #[case(Some("text/csv"), "content-type%3Bhost")]
+    #[tokio::test]
+    async fn test_presign_put(
+        #[case] content_type: Option<&str>,
+        #[case] expected_signed_headers: &str,
+    ) {
+        let client = test_client_with_credentials(MockHttpClient::default()).await;
+
+        let url = client
+            .presign_put(
+                "bucket-1",
+                "dir/file.txt",
+                Duration::from_secs(3600),
+                content_type,
+            )
+            .await
+            .unwrap();
+
+        assert!(url.starts_with("https://bucket-1.s3.us-east-1.amazonaws.com/dir/file.txt?"));
+        assert!(url.contains("X-Amz-Algorithm=AWS4-HMAC-SHA256"));
+        assert!(url.contains("X-Amz-Credential=AKIDEXAMPLE%2F"));
+        assert!(url.contains("X-Amz-Expires=3600"));
+        assert!(url.contains(&format!("X-Amz-SignedHeaders={}", expected_signed_headers)));
+        assert!(url.contains("X-Amz-Signature="));
+        assert!(url.contains("x-id=PutObject"));
+    }
+
+    #[rstest]
+    #[case(None, None, "0123456789")]
+    #[case(Some(4), Some("bytes=0-3"), "0123")]
+    #[tokio::test]
+    async fn test_download_object_range(
+        #[case] limit: Option<usize>,
+        #[case] expected_range: Option<&str>,
+        #[case] body: &'static str,
+    ) {
+        let http_client = MockHttpClient {
+            bodies: vec![body],
+            ..Default::default()
+        };
+        let request_headers = http_client.request_headers.clone();
+
+        let client = test_client(http_client, anonymous_options()).await;
+
+        let obj = client
+            .download_object("bucket-1", "file.txt", None, 10, limit, |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(obj.bytes, body.as_bytes());
+        let ranges = header_values(&request_headers, "range");
+        assert_eq!(ranges, [expected_range.map(String::from)]);
+    }
+
+    #[rstest]
+    #[case(false, None)]
+    #[case(true, Some("requester"))]
+    #[tokio::test]
+    async fn test_download_object_request_payer(
+        #[case] request_payer: bool,
+        #[case] expected: Option<&str>,
+    ) {
+        let http_client = MockHttpClient {
+            bodies: vec!["0123456789"],
+            ..Default::default()
+        };
+        let request_headers = http_client.request_headers.clone();
+
+        let options = ClientOptions {
+            request_payer,
+            ..anonymous_options()
+        };
+        let client = test_client(http_client, options).await;
+        assert_eq!(client.is_requester_pays(), request_payer);
+
+        client
+            .download_object("bucket-1", "file.txt", None, 10, None, |_| {})
+            .await
+            .unwrap();
+
+        let headers = header_values(&request_headers, "x-amz-request-payer");
+        assert_eq!(headers.last().unwrap().as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(false, None)]
+    #[case(true, Some("requester"))]
+    #[tokio::test]
+    async fn test_restore_object_request_payer(
+        #[case] request_payer: bool,
+        #[case] expected: Option<&str>,
+    ) {
+        let http_client = MockHttpClient {
+            bodies: vec![""],
+            ..Default::default()
+        };
+        let request_headers = http_client.request_headers.clone();
+
+        let options = ClientOptions {
+            request_payer,
+            ..anonymous_options()
+        };
+        let client = test_client(http_client, options).await;
+
+        client
+            .restore_object("bucket-1", "file.txt", None, RestoreTier::Standard, 1)
+            .await
+            .unwrap();
+
+        let headers = header_values(&request_headers, "x-amz-request-payer");
+        assert_eq!(headers.last().unwrap().as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(
+        false,
+        "Access denied (Failed to load object detail; if the bucket is requester pays, enable --request-payer)"
+    )]
+    #[case(true, "Access denied (Failed to load object detail)")]
+    #[tokio::test]
+    async fn test_load_object_detail_forbidden(
+        #[case] request_payer: bool,
+        #[case] expected: &str,
+    ) {
+        // HEAD responses have no error code in the body
+        let http_client = MockHttpClient {
+            bodies: vec![""],
+            status: Some(403),
+            ..Default::default()
+        };
+
+        let options = ClientOptions {
+            request_payer,
+            ..anonymous_options()
+        };
+        let client = test_client(http_client, options).await;
+
+        let e = client
+            .load_object_detail("bucket-1", "file.txt", "file.txt", 10, None)
+            .await
+            .unwrap_err();
+
+        assert!(e.is_access_denied());
+        assert_eq!(e.notification_msg(), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "file.txt",
+        StorageClass::StandardIa,
+        None,
+        None,
+        "bucket-1/file.txt",
+        "STANDARD_IA"
+    )]
+    #[case(
+        "dir 1/file+ü.txt",
+        StorageClass::DeepArchive,
+        Some("AES256"),
+        None,
+        "bucket-1/dir%201/file%2B%C3%BC.txt",
+        "DEEP_ARCHIVE"
+    )]
+    #[case(
+        "file.txt",
+        StorageClass::Glacier,
+        Some("aws:kms"),
+        Some("arn:aws:kms:us-east-1:123456789012:key/key-1"),
+        "bucket-1/file.txt",
+        "GLACIER"
+    )]
+    #[tokio::test]
+    async fn test_transition_storage_class(
+        #[case] key: &str,
+        #[case] storage_class: StorageClass,
+        #[case] encryption: Option<&str>,
+        #[case] kms_key_id: Option<&str>,
+        #[case] expected_copy_source: &str,
+        #[case] expected_storage_class: &str,
+    ) {
+        let http_client = MockHttpClient {
+            bodies: vec![
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<CopyObjectResult>
+  <LastModified>2024-01-02T13:01:02.000Z</LastModified>
+  <ETag>&quot;etag&quot;</ETag>
+</CopyObjectResult>"#,
+            ],
+            ..Default::default()
+        };
+        let request_headers = http_client.request_headers.clone();
+
+        let client = test_client(http_client, anonymous_options()).await;
+        let file_detail = FileDetail {
+            name: "file.txt".into(),
+            size_byte: 10,
+            last_modified: chrono::Local::now(),
+            e_tag: "etag".into(),
+            content_type: "text/plain".into(),
+            storage_class: "STANDARD".into(),
+            encryption: encryption.map(Into::into),
+            kms_key_id: kms_key_id.map(Into::into),
+            bucket_key_enabled: kms_key_id.map(|_| true),
+            content_encoding: None,
+            key: key.into(),
+            s3_uri: String::new(),
+            arn: String::new(),
+            object_url: String::new(),
+            restore_status: None,
+        };
+
+        client
+            .transition_storage_class("bucket-1", &file_detail, storage_class)
+            .await
+            .unwrap();
+
+        // copied onto itself, keeping the metadata and the encryption
+        let request_headers = request_headers.lock().unwrap();
+        let headers = request_headers.last().unwrap();
+        let header = |name: &str| headers.get(name).map(String::as_str);
+        assert_eq!(header("x-amz-copy-source"), Some(expected_copy_source));
+        assert_eq!(header("x-amz-storage-class"), Some(expected_storage_class));
+        assert_eq!(header("x-amz-metadata-directive"), Some("COPY"));
+        assert_eq!(header("x-amz-server-side-encryption"), encryption);
+        assert_eq!(
+            header("x-amz-server-side-encryption-aws-kms-key-id"),
+            kms_key_id
+        );
+        let bucket_key_enabled = kms_key_id.map(|_| "true");
+        assert_eq!(
+            header("x-amz-server-side-encryption-bucket-key-enabled"),
+            bucket_key_enabled
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_objects_through_access_point() {
+        let http_client = MockHttpClient {
+            bodies: vec![
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>my-ap</Name>
+  <Prefix>dir/</Prefix>
+  <KeyCount>1</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <Delimiter>/</Delimiter>
+  <IsTruncated>false</IsTruncated>
+  <Contents>
+    <Key>dir/file1.txt</Key>
+    <LastModified>2024-01-02T13:01:02.000Z</LastModified>
+    <ETag>&quot;etag&quot;</ETag>
+    <Size>1024</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+</ListBucketResult>"#,
+            ],
+            ..Default::default()
+        };
+        let uris = http_client.uris.clone();
+
+        let client = test_client(http_client, anonymous_options()).await;
+
+        let arn = "arn:aws:s3:us-east-1:123456789012:accesspoint/my-ap";
+        let bucket = client.load_bucket(arn).await.unwrap();
+        assert_eq!(bucket.name, arn);
+
+        let items = client.load_objects(arn, "dir/", |_| {}).await.unwrap();
+        assert_eq!(items.len(), 1);
+
+        let uri = uris.lock().unwrap()[0].clone();
+        assert!(
+            uri.starts_with("https://my-ap-123456789012.s3-accesspoint.us-east-1.amazonaws.com/?")
+        );
+
+        let e = client
+            .load_bucket("arn:aws:s3:eu-west-1:123456789012:accesspoint/my-ap")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            e.msg,
+            "Access point 'arn:aws:s3:eu-west-1:123456789012:accesspoint/my-ap' is in region 'eu-west-1', expected 'us-east-1'"
+        );
+        let e = client
+            .load_bucket("arn:aws:s3:::bucket-1")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            e.msg,
+            "Unsupported ARN resource 'bucket-1', only access points are supported"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_object_detail_denied_for_listed_object() {
+        let http_client = MockHttpClient {
+            bodies: vec![
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>bucket-1</Name>
+  <Prefix></Prefix>
+  <KeyCount>2</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <Delimiter>/</Delimiter>
+  <IsTruncated>false</IsTruncated>
+  <Contents>
+    <Key>file1.txt</Key>
+    <LastModified>2024-01-02T13:01:02.000Z</LastModified>
+    <ETag>&quot;etag&quot;</ETag>
+    <Size>1024</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+  <Contents>
+    <Key>secret.txt</Key>
+    <LastModified>2024-01-02T13:01:02.000Z</LastModified>
+    <ETag>&quot;etag&quot;</ETag>
+    <Size>2048</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Contents>
+</ListBucketResult>"#,
+            ],
+            denied_keys: vec!["secret.txt"],
+            ..Default::default()
+        };
+
+        let client = test_client(http_client, anonymous_options()).await;
+
+        let items = client.load_objects("bucket-1", "", |_| {}).await.unwrap();
+        let names: Vec<&str> = items.iter().map(|item| item.name()).collect();
+        assert_eq!(names, ["file1.txt", "secret.txt"]);
+
+        let e = client
+            .load_object_detail("bucket-1", "secret.txt", "secret.txt", 2048, None)
+            .await
+            .unwrap_err();
+        assert!(e.is_access_denied());
+        assert!(e
+            .notification_msg()
+            .starts_with("Access denied (Failed to load object detail"));
+    }
+
+    #[test]
+    fn test_to_app_error_timeout() {
+        let e: SdkError<HeadObjectError> = SdkError::timeout_error("timeout");
+        let actual = to_app_error("Failed to load object detail", e);
+        assert_eq!(actual.kind, AppErrorKind::Timeout);
+        assert_eq!(
+            actual.notification_msg(),
+            "Request timed out (Failed to load object detail)"
+        );
+    }
+
+    #[test]
+    fn test_to_app_error_other() {
+        let e: SdkError<HeadObjectError> = SdkError::construction_failure("failure");
+        let actual = to_app_error("Failed to load object detail", e);
+        assert_eq!(actual.kind, AppErrorKind::Other);
+        assert_eq!(actual.notification_msg(), "Failed to load object detail");
+    }
+
+    #[test]
+    fn test_to_app_error_access_denied() {
+        let meta = ErrorMetadata::builder().code("AccessDenied").build();
+        let status = StatusCode::try_from(403).unwrap();
+        let response = HttpResponse::new(status, SdkBody::empty());
+        let e = SdkError::service_error(HeadObjectError::generic(meta), response);
+        let actual = to_app_error("Failed to load bucket ACL", e);
+        assert_eq!(actual.kind, AppErrorKind::AccessDenied);
+        assert_eq!(
+            actual.notification_msg(),
+            "Access denied (Failed to load bucket ACL)"
+        );
+    }
+
+    #[rstest]
+    #[case(Some("AccessDenied"), AppErrorKind::AccessDenied)]
+    #[case(Some("AllAccessDisabled"), AppErrorKind::AccessDenied)]
+    #[case(Some("SignatureDoesNotMatch"), AppErrorKind::Other)]
+    #[case(Some("InvalidAccessKeyId"), AppErrorKind::Other)]
+    #[case(None, AppErrorKind::AccessDenied)]
+    fn test_to_app_error_forbidden(#[case] code: Option<&str>, #[case] expected: AppErrorKind) {
+        let mut meta = ErrorMetadata::builder();
+        if let Some(code) = code {
+            meta = meta.code(code);
+        }
+        let status = StatusCode::try_from(403).unwrap();
+        let response = HttpResponse::new(status, SdkBody::empty());
+        let e = SdkError::service_error(HeadObjectError::generic(meta.build()), response);
+        let actual = to_app_error("Failed to load object detail", e);
+        assert_eq!(actual.kind, expected);
+    }
+
+    #[rstest]
+    #[case(r#"ongoing-request="true""#, Some(RestoreStatus::InProgress))]
+    #[case(
+        r#"ongoing-request="false", expiry-date="Fri, 21 Dec 2012 00:00:00 GMT""#,
+        Some(RestoreStatus::Restored { expiry_date: Some("Fri, 21 Dec 2012 00:00:00 GMT".into()) })
+    )]
+    #[case(r#"ongoing-request="false""#, Some(RestoreStatus::Restored { expiry_date: None }))]
+    #[case("", None)]
+    fn test_parse_restore_status(#[case] s: &str, #[case] expected: Option<RestoreStatus>) {
+        assert_eq!(parse_restore_status(s), expected);
+    }
+
+    #[rstest]
+    #[case("bucket-1", "dir/file 1.txt", "bucket-1/dir/file%201.txt")]
+    #[case(
+        "bucket-1",
+        "dir/ファイル",
+        "bucket-1/dir/%E3%83%95%E3%82%A1%E3%82%A4%E3%83%AB"
+    )]
+    #[case(
+        "arn:aws:s3:us-west-2:123456789012:accesspoint/my-ap",
+        "reports/january 1.pdf",
+        "arn%3Aaws%3As3%3Aus-west-2%3A123456789012%3Aaccesspoint/my-ap/object/reports/january%201.pdf"
+    )]
+    fn test_encode_copy_source(#[case] bucket: &str, #[case] key: &str, #[case] expected: &str) {
+        assert_eq!(encode_copy_source(bucket, key), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "bucket-1",
+        "https://s3.console.aws.amazon.com/s3/buckets/bucket-1?region=us-east-1&prefix=dir/",
+        "https://s3.console.aws.amazon.com/s3/object/bucket-1?region=us-east-1&prefix=dir/file.txt"
+    )]
+    #[case(
+        "arn:aws:s3:us-east-1:123456789012:accesspoint/my-ap",
+        "https://s3.console.aws.amazon.com/s3/ap/123456789012/my-ap?region=us-east-1&prefix=dir/",
+        "https://s3.console.aws.amazon.com/s3/ap/123456789012/my-ap?region=us-east-1&prefix=dir/"
+    )]
+    #[tokio::test]
+    async fn test_management_console_url(
+        #[case] bucket: &str,
+        #[case] list_url: &str,
+        #[case] object_url: &str,
+    ) {
+        let client = test_client(MockHttpClient::default(), anonymous_options()).await;
+        assert_eq!(client.management_console_list_url(bucket, "dir/"), list_url);
+        assert_eq!(
+            client.management_console_object_url(bucket, "dir/", "file.txt"),
+            object_url
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::{env, path::PathBuf};
+use std::{env, fs, io, path::PathBuf};
 
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
@@ -9,21 +9,114 @@ const APP_BASE_DIR: &str = ".stu";
 const CONFIG_FILE_NAME: &str = "config.toml";
 const ERROR_LOG_FILE_NAME: &str = "error.log";
 const DEBUG_LOG_FILE_NAME: &str = "debug.log";
+const CLIENT_LOG_FILE_NAME: &str = "client.log";
 const DOWNLOAD_DIR: &str = "download";
 const CACHE_FILE_NAME: &str = "cache.txt";
+const RECENT_OBJECTS_FILE_NAME: &str = "recent.json";
+
+pub const MIN_LIST_WIDTH_PERCENTAGE: u16 = 20;
+pub const MAX_LIST_WIDTH_PERCENTAGE: u16 = 80;
+const DEFAULT_LIST_WIDTH_PERCENTAGE: u16 = 50;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
     #[serde(default = "default_download_dir")]
     pub download_dir: String,
     #[serde(default)]
+    pub verify_download_md5: bool,
+    #[serde(default = "default_wrap_selection")]
+    pub wrap_selection: bool,
+    #[serde(default)]
     pub preview: PreviewConfig,
+    #[serde(default)]
+    pub client: ClientConfig,
+    #[serde(default)]
+    pub layout: LayoutConfig,
 }
 
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PreviewConfig {
     #[serde(default)]
     pub highlight: bool,
+    #[serde(default)]
+    pub open_at_end: bool,
+    #[serde(default)]
+    pub wrap_width: Option<usize>,
+    #[serde(default = "default_number")]
+    pub number: bool,
+    #[serde(default)]
+    pub min_content_width: Option<usize>,
+    #[serde(default = "default_wrap")]
+    pub wrap: bool,
+    #[serde(default = "default_hyperlink")]
+    pub hyperlink: bool,
+    #[serde(default = "default_max_size_bytes")]
+    pub max_size_bytes: usize,
+}
+
+impl Default for PreviewConfig {
+    fn default() -> Self {
+        Self {
+            highlight: false,
+            open_at_end: false,
+            wrap_width: None,
+            number: default_number(),
+            min_content_width: None,
+            wrap: default_wrap(),
+            hyperlink: default_hyperlink(),
+            max_size_bytes: default_max_size_bytes(),
+        }
+    }
+}
+
+fn default_number() -> bool {
+    true
+}
+
+fn default_wrap() -> bool {
+    true
+}
+
+fn default_hyperlink() -> bool {
+    true
+}
+
+fn default_max_size_bytes() -> usize {
+    5 * 1024 * 1024 // 5 MiB
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ClientConfig {
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub operation_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub request_payer: bool,
+    #[serde(default)]
+    pub max_concurrent_requests: Option<usize>,
+    #[serde(default)]
+    pub max_requests_per_sec: Option<f64>,
+    #[serde(default)]
+    pub fetch_owner: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LayoutConfig {
+    #[serde(default = "default_list_width_percentage")]
+    pub list_width_percentage: u16,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            list_width_percentage: default_list_width_percentage(),
+        }
+    }
+}
+
+fn default_list_width_percentage() -> u16 {
+    DEFAULT_LIST_WIDTH_PERCENTAGE
 }
 
 impl Default for Config {
@@ -31,11 +124,19 @@ impl Default for Config {
         let download_dir = default_download_dir();
         Self {
             download_dir,
+            verify_download_md5: false,
+            wrap_selection: default_wrap_selection(),
             preview: PreviewConfig::default(),
+            client: ClientConfig::default(),
+            layout: LayoutConfig::default(),
         }
     }
 }
 
+fn default_wrap_selection() -> bool {
+    true
+}
+
 fn default_download_dir() -> String {
     match Config::get_app_base_dir() {
         Ok(dir) => {
@@ -53,6 +154,20 @@ impl Config {
         confy::load_path(path).context("Failed to load config file")
     }
 
+    /// Writes only `layout.list_width_percentage` to the config file, keeping the rest of the file as the user wrote it.
+    pub fn save_list_width_percentage(&self) -> anyhow::Result<()> {
+        let dir = Config::get_app_base_dir()?;
+        let path = dir.join(CONFIG_FILE_NAME);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e).context("Failed to load config file"),
+        };
+        let content = set_list_width_percentage(&content, self.layout.list_width_percentage)?;
+        fs::create_dir_all(&dir).context("Failed to save config file")?;
+        fs::write(&path, content).context("Failed to save config file")
+    }
+
     pub fn download_file_path(&self, name: &str) -> String {
         let dir = PathBuf::from(self.download_dir.clone());
         let path = dir.join(name);
@@ -71,12 +186,24 @@ impl Config {
         Ok(String::from(path.to_string_lossy()))
     }
 
+    pub fn client_log_path(&self) -> anyhow::Result<String> {
+        let dir = Config::get_app_base_dir()?;
+        let path = dir.join(CLIENT_LOG_FILE_NAME);
+        Ok(String::from(path.to_string_lossy()))
+    }
+
     pub fn cache_file_path() -> anyhow::Result<String> {
         let dir = Config::get_app_base_dir()?;
         let path = dir.join(CACHE_FILE_NAME);
         Ok(String::from(path.to_string_lossy()))
     }
 
+    pub fn recent_objects_file_path() -> anyhow::Result<String> {
+        let dir = Config::get_app_base_dir()?;
+        let path = dir.join(RECENT_OBJECTS_FILE_NAME);
+        Ok(String::from(path.to_string_lossy()))
+    }
+
     fn get_app_base_dir() -> anyhow::Result<PathBuf> {
         match env::var(STU_ROOT_DIR_ENV_VAR) {
             Ok(dir) => Ok(PathBuf::from(dir)),
@@ -89,3 +216,45 @@ impl Config {
         }
     }
 }
+
+fn set_list_width_percentage(content: &str, percentage: u16) -> anyhow::Result<String> {
+    let mut doc: toml_edit::DocumentMut = content.parse().context("Failed to parse config file")?;
+    let layout = doc.entry("layout").or_insert(toml_edit::table());
+    layout["list_width_percentage"] = toml_edit::value(i64::from(percentage));
+    Ok(doc.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_list_width_percentage_to_empty_file() {
+        let actual = set_list_width_percentage("", 30).unwrap();
+        assert_eq!(actual, "[layout]\nlist_width_percentage = 30\n");
+    }
+
+    #[test]
+    fn test_set_list_width_percentage_keeps_other_settings() {
+        let content = r#"# my settings
+download_dir = "/tmp/stu"
+
+[preview]
+highlight = true # enabled
+
+[layout]
+list_width_percentage = 50
+"#;
+        let expected = r#"# my settings
+download_dir = "/tmp/stu"
+
+[preview]
+highlight = true # enabled
+
+[layout]
+list_width_percentage = 65
+"#;
+        let actual = set_list_width_percentage(content, 65).unwrap();
+        assert_eq!(actual, expected);
+    }
+}
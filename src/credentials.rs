@@ -0,0 +1,177 @@
+use std::sync::{Arc, Mutex};
+
+use aws_config::{
+    default_provider::region::DefaultRegionChain, ecs::EcsCredentialsProvider,
+    environment::EnvironmentVariableCredentialsProvider,
+    imds::credentials::ImdsCredentialsProvider, profile::ProfileFileCredentialsProvider,
+    provider_config::ProviderConfig, web_identity_token::WebIdentityTokenCredentialsProvider,
+};
+use aws_credential_types::provider::{
+    self, error::CredentialsError, future, ProvideCredentials, SharedCredentialsProvider,
+};
+use aws_sdk_s3::config::Region;
+
+/// Chain of the credentials providers, in the same order as the default chain of the SDK,
+/// remembering the name of the provider that supplied the credentials.
+#[derive(Debug, Clone)]
+pub struct CredentialsChain {
+    providers: Arc<Vec<(String, SharedCredentialsProvider)>>,
+    source: Arc<Mutex<Option<String>>>,
+}
+
+impl CredentialsChain {
+    /// Creates the default chain, reading `profile` (or `AWS_PROFILE`) from the profile files.
+    pub async fn new(region: Option<String>, profile: Option<String>) -> CredentialsChain {
+        let region = match region {
+            Some(region) => Some(Region::new(region)),
+            None => {
+                let mut builder = DefaultRegionChain::builder();
+                if let Some(profile) = &profile {
+                    builder = builder.profile_name(profile);
+                }
+                builder.build().region().await
+            }
+        };
+        let conf = ProviderConfig::default().with_region(region);
+
+        let mut profile_builder = ProfileFileCredentialsProvider::builder().configure(&conf);
+        if let Some(profile) = &profile {
+            profile_builder = profile_builder.profile_name(profile);
+        }
+        let profile_name = profile
+            .or_else(|| std::env::var("AWS_PROFILE").ok())
+            .unwrap_or_else(|| "default".to_string());
+
+        CredentialsChain::from_providers(vec![
+            (
+                "environment".to_string(),
+                SharedCredentialsProvider::new(EnvironmentVariableCredentialsProvider::new()),
+            ),
+            (
+                format!("profile {}", profile_name),
+                SharedCredentialsProvider::new(profile_builder.build()),
+            ),
+            (
+                "web identity token".to_string(),
+                SharedCredentialsProvider::new(
+                    WebIdentityTokenCredentialsProvider::builder()
+                        .configure(&conf)
+                        .build(),
+                ),
+            ),
+            (
+                "ECS container".to_string(),
+                SharedCredentialsProvider::new(
+                    EcsCredentialsProvider::builder().configure(&conf).build(),
+                ),
+            ),
+            (
+                "IMDS".to_string(),
+                SharedCredentialsProvider::new(
+                    ImdsCredentialsProvider::builder().configure(&conf).build(),
+                ),
+            ),
+        ])
+    }
+
+    fn from_providers(providers: Vec<(String, SharedCredentialsProvider)>) -> CredentialsChain {
+        CredentialsChain {
+            providers: Arc::new(providers),
+            source: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns the name of the provider that supplied the credentials last, `None` if not resolved yet.
+    pub fn source(&self) -> Option<String> {
+        self.source.lock().unwrap().clone()
+    }
+
+    async fn credentials(&self) -> provider::Result {
+        for (name, provider) in self.providers.iter() {
+            match provider.provide_credentials().await {
+                Ok(credentials) => {
+                    *self.source.lock().unwrap() = Some(name.clone());
+                    return Ok(credentials);
+                }
+                // try the next provider only if this one is not configured, like the chain of the SDK
+                Err(CredentialsError::CredentialsNotLoaded(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Err(CredentialsError::not_loaded(
+            "no providers in chain provided credentials",
+        ))
+    }
+}
+
+impl ProvideCredentials for CredentialsChain {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(self.credentials())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_credential_types::{credential_fn::provide_credentials_fn, Credentials};
+
+    use super::*;
+
+    fn not_loaded() -> SharedCredentialsProvider {
+        SharedCredentialsProvider::new(provide_credentials_fn(|| async {
+            Err(CredentialsError::not_loaded("not configured"))
+        }))
+    }
+
+    fn failed() -> SharedCredentialsProvider {
+        SharedCredentialsProvider::new(provide_credentials_fn(|| async {
+            Err(CredentialsError::provider_error("failed"))
+        }))
+    }
+
+    fn loaded() -> SharedCredentialsProvider {
+        let credentials = Credentials::new("AKIDEXAMPLE", "secret", None, None, "test");
+        SharedCredentialsProvider::new(credentials)
+    }
+
+    #[tokio::test]
+    async fn test_credentials_chain_source() {
+        let chain = CredentialsChain::from_providers(vec![
+            ("environment".into(), not_loaded()),
+            ("profile dev".into(), loaded()),
+            ("IMDS".into(), loaded()),
+        ]);
+        assert_eq!(chain.source(), None);
+
+        let credentials = chain.provide_credentials().await.unwrap();
+        assert_eq!(credentials.access_key_id(), "AKIDEXAMPLE");
+        assert_eq!(chain.source(), Some("profile dev".into()));
+    }
+
+    #[tokio::test]
+    async fn test_credentials_chain_stops_at_failed_provider() {
+        let chain = CredentialsChain::from_providers(vec![
+            ("environment".into(), not_loaded()),
+            ("profile dev".into(), failed()),
+            ("IMDS".into(), loaded()),
+        ]);
+
+        let result = chain.provide_credentials().await;
+        assert!(matches!(result, Err(CredentialsError::ProviderError(_))));
+        assert_eq!(chain.source(), None);
+    }
+
+    #[tokio::test]
+    async fn test_credentials_chain_not_loaded() {
+        let chain = CredentialsChain::from_providers(vec![("environment".into(), not_loaded())]);
+
+        let result = chain.provide_credentials().await;
+        assert!(matches!(
+            result,
+            Err(CredentialsError::CredentialsNotLoaded(_))
+        ));
+        assert_eq!(chain.source(), None);
+    }
+}
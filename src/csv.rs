@@ -0,0 +1,127 @@
+use std::mem;
+
+use crate::util::extension_from_file_name;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Csv {
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// True if the content has more rows than parsed.
+    pub truncated: bool,
+}
+
+impl Csv {
+    /// Parses the content as CSV (RFC 4180), up to `max_rows` rows after the header.
+    ///
+    /// Returns `None` if there is no header. The rows are kept even if their number of fields differs from the header.
+    pub fn parse(s: &str, max_rows: usize) -> Option<Csv> {
+        let (mut records, truncated) = parse_records(s, max_rows + 1);
+        if records.is_empty() {
+            return None;
+        }
+        let header = records.remove(0);
+        Some(Csv {
+            header,
+            rows: records,
+            truncated,
+        })
+    }
+}
+
+pub fn is_csv(content_type: &str, name: &str) -> bool {
+    content_type.starts_with("text/csv")
+        || extension_from_file_name(name).eq_ignore_ascii_case("csv")
+}
+
+fn parse_records(s: &str, max_records: usize) -> (Vec<Vec<String>>, bool) {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            // quotes in the middle of an unquoted field are kept as is
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(mem::take(&mut field));
+                records.push(mem::take(&mut record));
+                if records.len() >= max_records {
+                    return (records, chars.peek().is_some());
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    (records, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn strings(ss: &[&str]) -> Vec<String> {
+        ss.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let s = "id,name,note\r\n1,foo,\"a, b\"\r\n2,\"bar \"\"baz\"\"\",\"multi\nline\"\n3,qux\n";
+        let csv = Csv::parse(s, 10).unwrap();
+
+        assert_eq!(csv.header, strings(&["id", "name", "note"]));
+        assert_eq!(
+            csv.rows,
+            vec![
+                strings(&["1", "foo", "a, b"]),
+                strings(&["2", "bar \"baz\"", "multi\nline"]),
+                strings(&["3", "qux"]),
+            ]
+        );
+        assert!(!csv.truncated);
+    }
+
+    #[test]
+    fn test_parse_csv_max_rows() {
+        let s = "a,b\n1,2\n3,4\n5,6";
+
+        let csv = Csv::parse(s, 2).unwrap();
+        assert_eq!(csv.rows, vec![strings(&["1", "2"]), strings(&["3", "4"])]);
+        assert!(csv.truncated);
+
+        let csv = Csv::parse(s, 3).unwrap();
+        assert_eq!(csv.rows.len(), 3);
+        assert!(!csv.truncated);
+
+        assert_eq!(Csv::parse("", 10), None);
+    }
+
+    #[rstest]
+    #[case("text/csv", "data", true)]
+    #[case("text/csv; charset=utf-8", "data", true)]
+    #[case("binary/octet-stream", "data.CSV", true)]
+    #[case("text/plain", "data.txt", false)]
+    fn test_is_csv(#[case] content_type: &str, #[case] name: &str, #[case] expected: bool) {
+        assert_eq!(is_csv(content_type, name), expected);
+    }
+}
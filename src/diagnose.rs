@@ -0,0 +1,134 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    client::Client,
+    error::{AppError, Result},
+    object::ObjectItem,
+};
+
+const S3_URI_SCHEME: &str = "s3://";
+
+/// Runs the requests of the client against `uri` without the TUI, printing the latencies to stdout.
+///
+/// Stops at the first failed request, so that its error is reported as is.
+pub async fn diagnose(client: &Client, uri: &str) -> Result<()> {
+    let (bucket, prefix) = parse_s3_uri(uri)?;
+
+    println!("region: {}", client.region());
+    println!("endpoint: {}", client.endpoint_url().unwrap_or("default"));
+
+    let start = Instant::now();
+    let source = client.credentials_source().await?;
+    println!(
+        "credentials: {} ({})",
+        source,
+        format_elapsed(start.elapsed())
+    );
+
+    if client.is_anonymous() {
+        println!("ListBuckets: skipped (unsigned requests)");
+    } else {
+        let start = Instant::now();
+        let buckets = client.load_all_buckets().await?;
+        println!(
+            "ListBuckets: {} buckets in {} ({})",
+            buckets.len(),
+            client.region(),
+            format_elapsed(start.elapsed())
+        );
+    }
+
+    let start = Instant::now();
+    let mut first_page = None;
+    let mut pages = 1;
+    let items = client
+        .load_objects(&bucket, &prefix, |_| {
+            first_page.get_or_insert_with(|| start.elapsed());
+            pages += 1;
+        })
+        .await?;
+    let elapsed = start.elapsed();
+    println!(
+        "ListObjectsV2: {} items in {} pages ({}, first page {})",
+        items.len(),
+        pages,
+        format_elapsed(elapsed),
+        format_elapsed(first_page.unwrap_or(elapsed))
+    );
+
+    let file = items.iter().find_map(|item| match item {
+        ObjectItem::File {
+            name, size_byte, ..
+        } => Some((name, *size_byte)),
+        ObjectItem::Dir { .. } => None,
+    });
+    match file {
+        Some((name, size_byte)) => {
+            let key = format!("{}{}", dir_of_prefix(&prefix), name);
+            let start = Instant::now();
+            client
+                .load_object_detail(&bucket, &key, name, size_byte, None)
+                .await?;
+            println!(
+                "HeadObject: {}{}/{} ({})",
+                S3_URI_SCHEME,
+                bucket,
+                key,
+                format_elapsed(start.elapsed())
+            );
+        }
+        None => println!("HeadObject: skipped (no objects under the prefix)"),
+    }
+
+    Ok(())
+}
+
+pub fn parse_s3_uri(uri: &str) -> Result<(String, String)> {
+    let path = uri
+        .strip_prefix(S3_URI_SCHEME)
+        .ok_or_else(|| AppError::msg(format!("Invalid S3 URI: {}", uri)))?;
+    let (bucket, prefix) = path.split_once('/').unwrap_or((path, ""));
+    if bucket.is_empty() {
+        return Err(AppError::msg(format!("Bucket is not specified: {}", uri)));
+    }
+    Ok((bucket.to_string(), prefix.to_string()))
+}
+
+// the names of the listed items are relative to the last delimiter of the prefix
+pub fn dir_of_prefix(prefix: &str) -> &str {
+    prefix.rfind('/').map_or("", |i| &prefix[..=i])
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    format!("{}ms", elapsed.as_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("s3://bucket-1", Some(("bucket-1", "")))]
+    #[case("s3://bucket-1/", Some(("bucket-1", "")))]
+    #[case("s3://bucket-1/dir1/dir2/", Some(("bucket-1", "dir1/dir2/")))]
+    #[case("s3://bucket-1/dir1/file", Some(("bucket-1", "dir1/file")))]
+    #[case("s3://", None)]
+    #[case("s3:///dir1/", None)]
+    #[case("bucket-1/dir1/", None)]
+    fn test_parse_s3_uri(#[case] uri: &str, #[case] expected: Option<(&str, &str)>) {
+        let actual = parse_s3_uri(uri).ok();
+        let actual = actual.as_ref().map(|(b, p)| (b.as_str(), p.as_str()));
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("", "")]
+    #[case("file", "")]
+    #[case("dir1/", "dir1/")]
+    #[case("dir1/dir2/fi", "dir1/dir2/")]
+    fn test_dir_of_prefix(#[case] prefix: &str, #[case] expected: &str) {
+        assert_eq!(dir_of_prefix(prefix), expected);
+    }
+}
@@ -0,0 +1,153 @@
+use std::io::Read;
+
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+
+/// Maximum size of the decoded content to preview, as a small object may expand to a huge one.
+pub const MAX_DECODED_SIZE_BYTES: usize = 64 * 1024 * 1024; // 64 MiB
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Gzip,
+    Deflate,
+}
+
+impl ContentEncoding {
+    /// Parses the `Content-Encoding` of the object.
+    ///
+    /// Returns `None` if the content is not encoded, and `Err` with the value as is if the encoding is not supported.
+    pub fn parse(value: &str) -> Option<Result<ContentEncoding, String>> {
+        let value = value.trim();
+        match value.to_lowercase().as_str() {
+            "" | "identity" => None,
+            "gzip" | "x-gzip" => Some(Ok(ContentEncoding::Gzip)),
+            "deflate" => Some(Ok(ContentEncoding::Deflate)),
+            _ => Some(Err(value.to_string())),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Deflate => "deflate",
+        }
+    }
+
+    /// Decodes the bytes up to `limit` bytes of the decoded content.
+    ///
+    /// A truncated or corrupt stream is an error, unless it is hidden by the content over the limit.
+    pub fn decode(&self, bytes: &[u8], limit: usize) -> std::io::Result<Decoded> {
+        // one more byte tells whether the content exceeds the limit
+        let take = limit as u64 + 1;
+        let mut decoded = Vec::new();
+        match self {
+            ContentEncoding::Gzip => {
+                GzDecoder::new(bytes).take(take).read_to_end(&mut decoded)?;
+            }
+            ContentEncoding::Deflate => {
+                // deflate is supposed to be zlib wrapped, but some servers send raw deflate
+                if ZlibDecoder::new(bytes)
+                    .take(take)
+                    .read_to_end(&mut decoded)
+                    .is_err()
+                {
+                    decoded.clear();
+                    DeflateDecoder::new(bytes)
+                        .take(take)
+                        .read_to_end(&mut decoded)?;
+                }
+            }
+        }
+        let truncated = decoded.len() > limit;
+        decoded.truncate(limit);
+        Ok(Decoded {
+            bytes: decoded,
+            truncated,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Decoded {
+    pub bytes: Vec<u8>,
+    /// whether only the first part of the content up to the limit is decoded
+    pub truncated: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{
+        write::{DeflateEncoder, GzEncoder, ZlibEncoder},
+        Compression,
+    };
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("gzip", Some(Ok(ContentEncoding::Gzip)))]
+    #[case(" GZIP ", Some(Ok(ContentEncoding::Gzip)))]
+    #[case("x-gzip", Some(Ok(ContentEncoding::Gzip)))]
+    #[case("deflate", Some(Ok(ContentEncoding::Deflate)))]
+    #[case("br", Some(Err("br".to_string())))]
+    #[case("identity", None)]
+    #[case("", None)]
+    fn test_parse_content_encoding(
+        #[case] value: &str,
+        #[case] expected: Option<Result<ContentEncoding, String>>,
+    ) {
+        assert_eq!(ContentEncoding::parse(value), expected);
+    }
+
+    #[test]
+    fn test_decode() {
+        let content = b"hello, world\n".repeat(10);
+        let decoded = |bytes: Vec<u8>| Decoded {
+            bytes,
+            truncated: false,
+        };
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&content).unwrap();
+        let gzip = encoder.finish().unwrap();
+        let actual = ContentEncoding::Gzip.decode(&gzip, 1024).unwrap();
+        assert_eq!(actual, decoded(content.clone()));
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&content).unwrap();
+        let zlib = encoder.finish().unwrap();
+        let actual = ContentEncoding::Deflate.decode(&zlib, 1024).unwrap();
+        assert_eq!(actual, decoded(content.clone()));
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&content).unwrap();
+        let deflate = encoder.finish().unwrap();
+        let actual = ContentEncoding::Deflate.decode(&deflate, 1024).unwrap();
+        assert_eq!(actual, decoded(content.clone()));
+
+        let truncated = &gzip[..gzip.len() / 2];
+        assert!(ContentEncoding::Gzip.decode(truncated, 1024).is_err());
+        assert!(ContentEncoding::Gzip
+            .decode(b"not compressed", 1024)
+            .is_err());
+    }
+
+    #[rstest]
+    #[case(130, false)]
+    #[case(129, true)]
+    #[case(13, true)]
+    fn test_decode_with_limit(#[case] limit: usize, #[case] truncated: bool) {
+        let content = b"hello, world\n".repeat(10);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&content).unwrap();
+        let gzip = encoder.finish().unwrap();
+
+        let actual = ContentEncoding::Gzip.decode(&gzip, limit).unwrap();
+        let expected = Decoded {
+            bytes: content[..limit].to_vec(),
+            truncated,
+        };
+        assert_eq!(actual, expected);
+    }
+}
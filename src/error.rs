@@ -2,9 +2,18 @@ use std::error::Error;
 
 pub type Result<T> = std::result::Result<T, AppError>;
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AppErrorKind {
+    #[default]
+    Other,
+    Timeout,
+    AccessDenied,
+}
+
 #[derive(Debug)]
 pub struct AppError {
     pub msg: String,
+    pub kind: AppErrorKind,
     pub cause: Option<Box<dyn Error + Send + 'static>>,
 }
 
@@ -12,6 +21,7 @@ impl AppError {
     pub fn new<E: Error + Send + 'static>(msg: impl Into<String>, e: E) -> AppError {
         AppError {
             msg: msg.into(),
+            kind: AppErrorKind::Other,
             cause: Some(Box::new(e)),
         }
     }
@@ -19,6 +29,7 @@ impl AppError {
     pub fn msg(msg: impl Into<String>) -> AppError {
         AppError {
             msg: msg.into(),
+            kind: AppErrorKind::Other,
             cause: None,
         }
     }
@@ -26,7 +37,36 @@ impl AppError {
     pub fn error<E: Error + Send + 'static>(e: E) -> AppError {
         AppError {
             msg: e.to_string(),
+            kind: AppErrorKind::Other,
+            cause: Some(Box::new(e)),
+        }
+    }
+
+    pub fn timeout<E: Error + Send + 'static>(msg: impl Into<String>, e: E) -> AppError {
+        AppError {
+            msg: msg.into(),
+            kind: AppErrorKind::Timeout,
             cause: Some(Box::new(e)),
         }
     }
+
+    pub fn access_denied<E: Error + Send + 'static>(msg: impl Into<String>, e: E) -> AppError {
+        AppError {
+            msg: msg.into(),
+            kind: AppErrorKind::AccessDenied,
+            cause: Some(Box::new(e)),
+        }
+    }
+
+    pub fn is_access_denied(&self) -> bool {
+        self.kind == AppErrorKind::AccessDenied
+    }
+
+    pub fn notification_msg(&self) -> String {
+        match self.kind {
+            AppErrorKind::Timeout => format!("Request timed out ({})", self.msg),
+            AppErrorKind::AccessDenied => format!("Access denied ({})", self.msg),
+            AppErrorKind::Other => self.msg.clone(),
+        }
+    }
 }
@@ -0,0 +1,86 @@
+use md5::{Digest, Md5};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EtagCheck {
+    Match,
+    Mismatch {
+        md5: String,
+    },
+    /// The ETag is not the MD5 digest of the content, so it cannot be compared.
+    Skipped(&'static str),
+}
+
+/// Compares the MD5 digest of the downloaded bytes with the ETag of the object.
+///
+/// The ETag is the MD5 digest only for the objects uploaded in a single part and not encrypted with SSE-KMS or SSE-C.
+pub fn verify_md5_etag(e_tag: &str, encryption: Option<&str>, bytes: &[u8]) -> EtagCheck {
+    let e_tag = e_tag.trim_matches('"');
+    if is_multipart_etag(e_tag) {
+        return EtagCheck::Skipped("uploaded in multiple parts");
+    }
+    match encryption {
+        Some("SSE-C") => return EtagCheck::Skipped("encrypted with a customer key"),
+        Some(e) if e.starts_with("aws:kms") => return EtagCheck::Skipped("encrypted with KMS"),
+        _ => {}
+    }
+    if e_tag.len() != 32 || !e_tag.chars().all(|c| c.is_ascii_hexdigit()) {
+        return EtagCheck::Skipped("ETag is not an MD5 digest");
+    }
+
+    let md5 = format!("{:x}", Md5::digest(bytes));
+    if md5.eq_ignore_ascii_case(e_tag) {
+        EtagCheck::Match
+    } else {
+        EtagCheck::Mismatch { md5 }
+    }
+}
+
+/// The ETag of an object uploaded with multipart upload is `<md5 of the part md5s>-<number of parts>`.
+fn is_multipart_etag(e_tag: &str) -> bool {
+    e_tag
+        .rsplit_once('-')
+        .is_some_and(|(_, count)| !count.is_empty() && count.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("5eb63bbbe01eeed093cb22bb8f5acdc3", None, EtagCheck::Match)]
+    #[case(
+        "\"5EB63BBBE01EEED093CB22BB8F5ACDC3\"",
+        Some("AES256"),
+        EtagCheck::Match
+    )]
+    #[case(
+        "0123456789abcdef0123456789abcdef",
+        None,
+        EtagCheck::Mismatch { md5: "5eb63bbbe01eeed093cb22bb8f5acdc3".into() }
+    )]
+    #[case(
+        "d41d8cd98f00b204e9800998ecf8427e-12",
+        None,
+        EtagCheck::Skipped("uploaded in multiple parts")
+    )]
+    #[case(
+        "0123456789abcdef0123456789abcdef",
+        Some("aws:kms"),
+        EtagCheck::Skipped("encrypted with KMS")
+    )]
+    #[case(
+        "0123456789abcdef0123456789abcdef",
+        Some("SSE-C"),
+        EtagCheck::Skipped("encrypted with a customer key")
+    )]
+    #[case("etag", None, EtagCheck::Skipped("ETag is not an MD5 digest"))]
+    fn test_verify_md5_etag(
+        #[case] e_tag: &str,
+        #[case] encryption: Option<&str>,
+        #[case] expected: EtagCheck,
+    ) {
+        assert_eq!(verify_md5_etag(e_tag, encryption, b"hello world"), expected);
+    }
+}
@@ -9,7 +9,11 @@ use ratatui::crossterm::event::KeyEvent;
 use crate::{
     client::Client,
     error::{AppError, Result},
-    object::{BucketItem, FileDetail, FileVersion, ObjectItem, ObjectKey, RawObject},
+    etag::EtagCheck,
+    object::{
+        BucketAccess, BucketGrant, BucketItem, FileDetail, FileVersion, ObjectItem, ObjectKey,
+        PublicAccessBlock, RawObject, Readable, RestoreTier, StorageClass,
+    },
 };
 
 #[derive(Debug)]
@@ -18,35 +22,66 @@ pub enum AppEventType {
     Resize(usize, usize),
     Initialize(Client, Option<String>),
     CompleteInitialize(Result<CompleteInitializeResult>),
+    CompleteSetRegion(Client),
     LoadObjects,
-    CompleteLoadObjects(Result<CompleteLoadObjectsResult>),
+    LoadObjectsPage(ObjectKey, u64, Vec<ObjectItem>),
+    CompleteLoadObjects(ObjectKey, u64, Result<CompleteLoadObjectsResult>),
     LoadObject,
     CompleteLoadObject(Result<CompleteLoadObjectResult>),
+    CompleteRefreshObjects(ObjectKey, Result<CompleteLoadObjectsResult>),
+    CompleteRefreshObject(Result<CompleteLoadObjectResult>),
+    CompletePrefetchObjectDetail(ObjectKey, Result<Box<FileDetail>>),
     DownloadObject(FileDetail, Option<String>),
     DownloadObjectAs(FileDetail, String, Option<String>),
     CompleteDownloadObject(Result<CompleteDownloadObjectResult>),
-    PreviewObject(FileDetail, Option<String>),
+    DownloadPrefix(String),
+    CompleteDownloadPrefix(Result<CompleteDownloadPrefixResult>),
+    LoadVersionDiff(FileDetail, FileVersion, FileVersion),
+    CompleteLoadVersionDiff(Result<CompleteLoadVersionDiffResult>),
+    RestoreObject(FileDetail, Option<String>, RestoreTier, i32),
+    CompleteRestoreObject(Result<CompleteRestoreObjectResult>),
+    TransitionStorageClass(FileDetail, StorageClass),
+    CompleteTransitionStorageClass(StorageClass, Result<CompleteLoadObjectResult>),
+    PreviewObject(FileDetail, Option<String>, Option<usize>),
+    PreviewRefreshObject(FileDetail),
+    CompletePreviewRefreshObject(Result<CompleteDownloadObjectResult>),
     CompletePreviewObject(Result<CompletePreviewObjectResult>),
     BucketListMoveDown,
+    LoadBucketAccess(String),
+    CompleteLoadBucketAccess(Result<CompleteLoadBucketAccessResult>),
     ObjectListMoveDown,
     ObjectListMoveUp,
+    ObjectListMoveUpTo(usize),
+    RevealObject(String),
+    OpenRecentObjects,
+    ToggleCollapseDirs,
+    OpenRecentObject(String, String),
+    RemoveRecentObject(String, String),
     BackToBucketList,
     OpenPreview(FileDetail, Option<String>),
+    OpenPartialPreview(FileDetail, Option<String>),
     DetailDownloadObject(FileDetail, Option<String>),
-    DetailDownloadObjectAs(FileDetail, String, Option<String>),
+    DetailDownloadObjectAs(FileDetail, String, Option<String>, bool),
     PreviewDownloadObject(RawObject, String),
-    PreviewDownloadObjectAs(FileDetail, String, Option<String>),
+    PreviewDownloadObjectAs(FileDetail, String, Option<String>, bool),
     BucketListOpenManagementConsole,
     ObjectListOpenManagementConsole,
     ObjectDetailOpenManagementConsole(String),
     CloseCurrentPage,
     OpenHelp,
+    SaveListWidthPercentage(u16),
+    SetPreviewOptions(bool, bool),
     CopyToClipboard(String, String),
+    CopyPresignedPutUrl(String, Option<String>),
+    CompletePresignPut(String, Result<String>),
+    CreateFolder(String),
+    CompleteCreateFolder(ObjectKey, String, Result<()>),
     NotifyInfo(String),
     NotifySuccess(String),
     NotifyWarn(String),
     NotifyError(AppError),
     Quit,
+    QuitWithLocation(String),
 }
 
 #[derive(Debug)]
@@ -96,19 +131,112 @@ impl CompleteLoadObjectResult {
     }
 }
 
+#[derive(Debug)]
+pub struct CompleteLoadVersionDiffResult {
+    pub old_version_id: String,
+    pub new_version_id: String,
+    pub old_detail: Box<FileDetail>,
+    pub new_detail: Box<FileDetail>,
+}
+
+impl CompleteLoadVersionDiffResult {
+    pub fn new(
+        old_version_id: String,
+        new_version_id: String,
+        old_detail: Result<FileDetail>,
+        new_detail: Result<FileDetail>,
+    ) -> Result<CompleteLoadVersionDiffResult> {
+        let old_detail = Box::new(old_detail?);
+        let new_detail = Box::new(new_detail?);
+        Ok(CompleteLoadVersionDiffResult {
+            old_version_id,
+            new_version_id,
+            old_detail,
+            new_detail,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteLoadBucketAccessResult {
+    pub bucket: String,
+    pub access: BucketAccess,
+}
+
+impl CompleteLoadBucketAccessResult {
+    pub fn new(
+        bucket: String,
+        policy_public: Result<Option<bool>>,
+        public_access_block: Result<Option<PublicAccessBlock>>,
+        grants: Result<Vec<BucketGrant>>,
+    ) -> Result<CompleteLoadBucketAccessResult> {
+        // denials are shown in the dialog instead of failing the whole request
+        let access = BucketAccess {
+            policy_public: Readable::from_result(policy_public)?,
+            public_access_block: Readable::from_result(public_access_block)?,
+            grants: Readable::from_result(grants)?,
+        };
+        Ok(CompleteLoadBucketAccessResult { bucket, access })
+    }
+}
+
+#[derive(Debug)]
+pub struct CompleteRestoreObjectResult {
+    pub map_key: ObjectKey,
+    pub version_id: Option<String>,
+    pub tier: RestoreTier,
+    pub days: i32,
+}
+
+impl CompleteRestoreObjectResult {
+    pub fn new(
+        result: Result<()>,
+        map_key: ObjectKey,
+        version_id: Option<String>,
+        tier: RestoreTier,
+        days: i32,
+    ) -> Result<CompleteRestoreObjectResult> {
+        result?;
+        Ok(CompleteRestoreObjectResult {
+            map_key,
+            version_id,
+            tier,
+            days,
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct CompleteDownloadObjectResult {
     pub obj: RawObject,
     pub path: String,
+    // set if the downloaded bytes are compared with the ETag
+    pub etag_check: Option<EtagCheck>,
 }
 
 impl CompleteDownloadObjectResult {
-    pub fn new(obj: Result<RawObject>, path: String) -> Result<CompleteDownloadObjectResult> {
+    pub fn new(
+        obj: Result<RawObject>,
+        path: String,
+        etag_check: Option<EtagCheck>,
+    ) -> Result<CompleteDownloadObjectResult> {
         let obj = obj?;
-        Ok(CompleteDownloadObjectResult { obj, path })
+        Ok(CompleteDownloadObjectResult {
+            obj,
+            path,
+            etag_check,
+        })
     }
 }
 
+#[derive(Debug, Default)]
+pub struct CompleteDownloadPrefixResult {
+    pub dir: String,
+    pub downloaded: usize,
+    pub skipped: usize,
+    pub failed: Vec<(String, AppError)>,
+}
+
 #[derive(Debug)]
 pub struct CompletePreviewObjectResult {
     pub obj: RawObject,
@@ -187,3 +315,10 @@ pub fn new() -> (Sender, Receiver) {
 
     (tx, rx)
 }
+
+/// Creates the channel without reading the terminal events, which would be mixed into the received events.
+#[cfg(test)]
+pub fn new_without_terminal() -> (Sender, Receiver) {
+    let (tx, rx) = mpsc::channel();
+    (Sender { tx }, Receiver { rx })
+}
@@ -3,7 +3,7 @@ use chrono::Local;
 use std::{
     fs::{File, OpenOptions},
     io::{BufWriter, Write},
-    path::Path,
+    path::{Component, Path, PathBuf},
 };
 
 use crate::error::{AppError, Result};
@@ -43,6 +43,71 @@ pub fn open_or_create_append_file(path: &str) -> std::io::Result<File> {
     OpenOptions::new().create(true).append(true).open(path)
 }
 
+/// Returns the first `name (n).ext` style name for which `exists` returns false.
+pub fn next_available_file_name<F>(name: &str, exists: F) -> String
+where
+    F: Fn(&str) -> bool,
+{
+    (1..)
+        .map(|n| numbered_file_name(name, n))
+        .find(|name| !exists(name))
+        .unwrap()
+}
+
+fn numbered_file_name(name: &str, n: usize) -> String {
+    let path = Path::new(name);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy())
+        .unwrap_or_default();
+    let file_name = match path.extension() {
+        Some(ext) => format!("{} ({}).{}", stem, n, ext.to_string_lossy()),
+        None => format!("{} ({})", stem, n),
+    };
+    let path = match path.parent() {
+        Some(parent) => parent.join(file_name),
+        None => PathBuf::from(file_name),
+    };
+    String::from(path.to_string_lossy())
+}
+
+/// Converts the key (relative to the downloaded prefix) to the path under `dir`.
+///
+/// Keys that would point outside of `dir`, such as ones containing `..` or starting with `/`, are rejected.
+pub fn prefix_download_path(dir: &Path, relative_key: &str) -> Result<PathBuf> {
+    let unsafe_key = || AppError::msg(format!("Refused to download unsafe key: {}", relative_key));
+    if relative_key.starts_with('/') {
+        return Err(unsafe_key());
+    }
+    let mut path = dir.to_path_buf();
+    let mut depth = 0;
+    for segment in relative_key.split('/') {
+        if segment.is_empty() || segment == "." {
+            continue;
+        }
+        // each segment must be a plain name (rejects `..`, and also `C:` or `a\b` on Windows)
+        let mut components = Path::new(segment).components();
+        if !matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        ) {
+            return Err(unsafe_key());
+        }
+        path.push(segment);
+        depth += 1;
+    }
+    if depth == 0 {
+        return Err(unsafe_key());
+    }
+    Ok(path)
+}
+
+/// Returns true if the file already exists with the same size, so that the download can be skipped.
+pub fn is_downloaded(path: &Path, size_byte: usize) -> bool {
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.len() == size_byte as u64)
+}
+
 fn create_dirs(path: &str) -> Result<()> {
     let path = Path::new(path);
     match path.parent() {
@@ -57,3 +122,46 @@ pub fn copy_to_clipboard(value: String) -> Result<()> {
         .and_then(|mut c| c.set_text(value))
         .map_err(|e| AppError::new("Failed to copy to clipboard", e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("file.txt", Some("dl/file.txt"))]
+    #[case("dir/sub/file.txt", Some("dl/dir/sub/file.txt"))]
+    #[case("dir//./file.txt", Some("dl/dir/file.txt"))]
+    #[case("dir/../../etc/passwd", None)]
+    #[case("..", None)]
+    #[case("/etc/passwd", None)]
+    #[case("dir/", Some("dl/dir"))]
+    #[case("", None)]
+    fn test_prefix_download_path(#[case] key: &str, #[case] expected: Option<&str>) {
+        let actual = prefix_download_path(Path::new("dl"), key).ok();
+        assert_eq!(actual, expected.map(PathBuf::from));
+    }
+
+    #[rstest]
+    #[case("file.txt", "file (1).txt")]
+    #[case("file", "file (1)")]
+    #[case("archive.tar.gz", "archive.tar (1).gz")]
+    #[case(".bashrc", ".bashrc (1)")]
+    #[case("dir.d/file", "dir.d/file (1)")]
+    #[case("dir/file.txt", "dir/file (1).txt")]
+    fn test_next_available_file_name(#[case] name: &str, #[case] expected: &str) {
+        let actual = next_available_file_name(name, |_| false);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_next_available_file_name_skips_existing() {
+        let existing = ["file (1).txt", "file (2).txt"];
+        let actual = next_available_file_name("file.txt", |name| existing.contains(&name));
+        assert_eq!(actual, "file (3).txt");
+
+        let existing = ["file (1)"];
+        let actual = next_available_file_name("file", |name| existing.contains(&name));
+        assert_eq!(actual, "file (2)");
+    }
+}
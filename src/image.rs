@@ -0,0 +1,274 @@
+use crate::util::extension_from_file_name;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+}
+
+impl ImageFormat {
+    /// Detects the format from the content type, or from the extension of the name if the content type is not specific.
+    pub fn detect(content_type: &str, name: &str) -> Option<ImageFormat> {
+        let from_content_type = match content_type {
+            "image/png" => Some(ImageFormat::Png),
+            "image/jpeg" | "image/jpg" => Some(ImageFormat::Jpeg),
+            "image/gif" => Some(ImageFormat::Gif),
+            "image/webp" => Some(ImageFormat::Webp),
+            _ => None,
+        };
+        from_content_type.or_else(
+            || match extension_from_file_name(name).to_lowercase().as_str() {
+                "png" => Some(ImageFormat::Png),
+                "jpg" | "jpeg" => Some(ImageFormat::Jpeg),
+                "gif" => Some(ImageFormat::Gif),
+                "webp" => Some(ImageFormat::Webp),
+                _ => None,
+            },
+        )
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "PNG",
+            ImageFormat::Jpeg => "JPEG",
+            ImageFormat::Gif => "GIF",
+            ImageFormat::Webp => "WebP",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageInfo {
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+    pub color: String,
+}
+
+impl ImageInfo {
+    /// Decodes only the header of the image. The format is determined by the signature of the bytes, not by the name.
+    pub fn decode(bytes: &[u8]) -> Option<ImageInfo> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            decode_png(bytes)
+        } else if bytes.starts_with(&[0xff, 0xd8]) {
+            decode_jpeg(bytes)
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            decode_gif(bytes)
+        } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+            decode_webp(bytes)
+        } else {
+            None
+        }
+    }
+}
+
+fn u16_be(bytes: &[u8], i: usize) -> Option<u32> {
+    let b = bytes.get(i..i + 2)?;
+    Some(u16::from_be_bytes([b[0], b[1]]) as u32)
+}
+
+fn u16_le(bytes: &[u8], i: usize) -> Option<u32> {
+    let b = bytes.get(i..i + 2)?;
+    Some(u16::from_le_bytes([b[0], b[1]]) as u32)
+}
+
+fn u24_le(bytes: &[u8], i: usize) -> Option<u32> {
+    let b = bytes.get(i..i + 3)?;
+    Some(u32::from_le_bytes([b[0], b[1], b[2], 0]))
+}
+
+fn u32_be(bytes: &[u8], i: usize) -> Option<u32> {
+    let b = bytes.get(i..i + 4)?;
+    Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn decode_png(bytes: &[u8]) -> Option<ImageInfo> {
+    // the first chunk must be IHDR
+    if bytes.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    let width = u32_be(bytes, 16)?;
+    let height = u32_be(bytes, 20)?;
+    let bit_depth = *bytes.get(24)?;
+    let color_type = match bytes.get(25)? {
+        0 => "Grayscale",
+        2 => "RGB",
+        3 => "Indexed",
+        4 => "Grayscale with alpha",
+        6 => "RGBA",
+        _ => return None,
+    };
+    Some(ImageInfo {
+        format: ImageFormat::Png,
+        width,
+        height,
+        color: format!("{}, {}-bit", color_type, bit_depth),
+    })
+}
+
+fn decode_jpeg(bytes: &[u8]) -> Option<ImageInfo> {
+    let mut i = 2;
+    loop {
+        if *bytes.get(i)? != 0xff {
+            return None;
+        }
+        let marker = *bytes.get(i + 1)?;
+        match marker {
+            // padding
+            0xff => {
+                i += 1;
+                continue;
+            }
+            // markers without length
+            0x01 | 0xd0..=0xd7 => {
+                i += 2;
+                continue;
+            }
+            // start of frame (except DHT, JPG and DAC)
+            0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                let precision = *bytes.get(i + 4)?;
+                let height = u16_be(bytes, i + 5)?;
+                let width = u16_be(bytes, i + 7)?;
+                let color_type = match bytes.get(i + 9)? {
+                    1 => "Grayscale",
+                    3 => "YCbCr",
+                    4 => "CMYK",
+                    _ => return None,
+                };
+                return Some(ImageInfo {
+                    format: ImageFormat::Jpeg,
+                    width,
+                    height,
+                    color: format!("{}, {}-bit", color_type, precision),
+                });
+            }
+            // start of scan or end of image before any frame
+            0xd9 | 0xda => return None,
+            _ => {
+                i += 2 + u16_be(bytes, i + 2)? as usize;
+            }
+        }
+    }
+}
+
+fn decode_gif(bytes: &[u8]) -> Option<ImageInfo> {
+    let width = u16_le(bytes, 6)?;
+    let height = u16_le(bytes, 8)?;
+    let packed = *bytes.get(10)?;
+    // the global color table may be absent, then each frame has its own table
+    let color = if packed & 0x80 != 0 {
+        format!("Indexed, {} colors", 1 << ((packed & 0x07) + 1))
+    } else {
+        "Indexed".to_string()
+    };
+    Some(ImageInfo {
+        format: ImageFormat::Gif,
+        width,
+        height,
+        color,
+    })
+}
+
+fn decode_webp(bytes: &[u8]) -> Option<ImageInfo> {
+    let (width, height, alpha) = match bytes.get(12..16)? {
+        b"VP8X" => {
+            let alpha = *bytes.get(20)? & 0x10 != 0;
+            (u24_le(bytes, 24)? + 1, u24_le(bytes, 27)? + 1, alpha)
+        }
+        b"VP8L" => {
+            if *bytes.get(20)? != 0x2f {
+                return None;
+            }
+            let b = bytes.get(21..25)?;
+            let bits = u32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+            let width = (bits & 0x3fff) + 1;
+            let height = ((bits >> 14) & 0x3fff) + 1;
+            (width, height, bits & (1 << 28) != 0)
+        }
+        b"VP8 " => {
+            if bytes.get(23..26)? != [0x9d, 0x01, 0x2a] {
+                return None;
+            }
+            // the upper 2 bits are the scale
+            (
+                u16_le(bytes, 26)? & 0x3fff,
+                u16_le(bytes, 28)? & 0x3fff,
+                false,
+            )
+        }
+        _ => return None,
+    };
+    let color = if alpha { "RGBA" } else { "RGB" };
+    Some(ImageInfo {
+        format: ImageFormat::Webp,
+        width,
+        height,
+        color: color.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("image/png", "a.bin", Some(ImageFormat::Png))]
+    #[case("image/jpeg", "a", Some(ImageFormat::Jpeg))]
+    #[case("binary/octet-stream", "a.JPG", Some(ImageFormat::Jpeg))]
+    #[case("application/octet-stream", "a.webp", Some(ImageFormat::Webp))]
+    #[case("", "a.gif", Some(ImageFormat::Gif))]
+    #[case("image/svg+xml", "a.svg", None)]
+    #[case("text/plain", "a.txt", None)]
+    fn test_detect_image_format(
+        #[case] content_type: &str,
+        #[case] name: &str,
+        #[case] expected: Option<ImageFormat>,
+    ) {
+        assert_eq!(ImageFormat::detect(content_type, name), expected);
+    }
+
+    fn info(format: ImageFormat, width: u32, height: u32, color: &str) -> Option<ImageInfo> {
+        Some(ImageInfo {
+            format,
+            width,
+            height,
+            color: color.into(),
+        })
+    }
+
+    #[rstest]
+    #[case(
+        b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x02\x80\x00\x00\x01\xe0\x08\x06\x00\x00\x00",
+        info(ImageFormat::Png, 640, 480, "RGBA, 8-bit")
+    )]
+    #[case(
+        b"\xff\xd8\xff\xe0\x00\x04\x00\x00\xff\xc0\x00\x11\x08\x00\x20\x00\x40\x03",
+        info(ImageFormat::Jpeg, 64, 32, "YCbCr, 8-bit")
+    )]
+    #[case(
+        b"GIF89a\x0a\x00\x14\x00\xf7\x00\x00",
+        info(ImageFormat::Gif, 10, 20, "Indexed, 256 colors")
+    )]
+    #[case(
+        b"RIFF\x00\x00\x00\x00WEBPVP8X\x0a\x00\x00\x00\x10\x00\x00\x00\x3f\x01\x00\xef\x00\x00",
+        info(ImageFormat::Webp, 320, 240, "RGBA")
+    )]
+    #[case(
+        b"RIFF\x00\x00\x00\x00WEBPVP8L\x00\x00\x00\x00\x2f\x09\xc0\x02\x00",
+        info(ImageFormat::Webp, 10, 12, "RGB")
+    )]
+    #[case(
+        b"RIFF\x00\x00\x00\x00WEBPVP8 \x00\x00\x00\x00\x00\x00\x00\x9d\x01\x2a\x40\x01\xf0\x00",
+        info(ImageFormat::Webp, 320, 240, "RGB")
+    )]
+    #[case(b"\x89PNG\r\n\x1a\n\x00\x00", None)]
+    #[case(b"\xff\xd8\xff\xda\x00\x02", None)]
+    #[case(b"not an image", None)]
+    fn test_decode_image_info(#[case] bytes: &[u8], #[case] expected: Option<ImageInfo>) {
+        assert_eq!(ImageInfo::decode(bytes), expected);
+    }
+}
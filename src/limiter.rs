@@ -0,0 +1,122 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Limits the requests sent by the client, by the number of requests in flight and by the number of requests per second.
+#[derive(Debug)]
+pub struct RequestLimiter {
+    semaphore: Option<Arc<Semaphore>>,
+    token_bucket: Option<Mutex<TokenBucket>>,
+}
+
+/// Held while the request is in flight.
+pub struct RequestPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl RequestLimiter {
+    /// `None` or zero means no limit.
+    pub fn new(max_concurrent: Option<usize>, max_per_sec: Option<f64>) -> RequestLimiter {
+        let semaphore = max_concurrent
+            .filter(|n| *n > 0)
+            .map(|n| Arc::new(Semaphore::new(n)));
+        let token_bucket = max_per_sec
+            .filter(|rate| *rate > 0.0)
+            .map(|rate| Mutex::new(TokenBucket::new(rate, Instant::now())));
+        RequestLimiter {
+            semaphore,
+            token_bucket,
+        }
+    }
+
+    pub async fn acquire(&self) -> RequestPermit {
+        // wait for the rate while holding the permit, so that the waiting requests are not sent at once
+        let permit = match &self.semaphore {
+            // the semaphore is never closed
+            Some(semaphore) => Some(semaphore.clone().acquire_owned().await.unwrap()),
+            None => None,
+        };
+        if let Some(token_bucket) = &self.token_bucket {
+            loop {
+                let wait = token_bucket.lock().unwrap().take(Instant::now());
+                match wait {
+                    Some(wait) => tokio::time::sleep(wait).await,
+                    None => break,
+                }
+            }
+        }
+        RequestPermit { _permit: permit }
+    }
+}
+
+/// Allows bursts of up to one second worth of requests.
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, now: Instant) -> TokenBucket {
+        let capacity = rate.max(1.0);
+        TokenBucket {
+            rate,
+            capacity,
+            tokens: capacity,
+            updated: now,
+        }
+    }
+
+    /// Takes a token if available, otherwise returns how long to wait until the next one.
+    fn take(&mut self, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.updated = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - self.tokens) / self.rate))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket() {
+        let now = Instant::now();
+        let mut token_bucket = TokenBucket::new(2.0, now);
+
+        assert_eq!(token_bucket.take(now), None);
+        assert_eq!(token_bucket.take(now), None);
+        assert_eq!(token_bucket.take(now), Some(Duration::from_millis(500)));
+
+        let now = now + Duration::from_millis(500);
+        assert_eq!(token_bucket.take(now), None);
+        assert_eq!(token_bucket.take(now), Some(Duration::from_millis(500)));
+
+        // the tokens do not exceed the capacity
+        let now = now + Duration::from_secs(10);
+        assert_eq!(token_bucket.take(now), None);
+        assert_eq!(token_bucket.take(now), None);
+        assert!(token_bucket.take(now).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_without_limits() {
+        // zero is no limit, so the permits are never exhausted
+        let limiter = RequestLimiter::new(Some(0), Some(0.0));
+        let mut permits = Vec::new();
+        for _ in 0..100 {
+            permits.push(limiter.acquire().await);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use std::io::{self, Write};
+
+use chrono::{DateTime, Local, SecondsFormat};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    client::Client,
+    diagnose::{dir_of_prefix, parse_s3_uri},
+    error::{AppError, Result},
+    object::ObjectItem,
+    ui::common::{format_datetime, format_size_byte},
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListFormat {
+    #[default]
+    Plain,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Serialize)]
+struct ListedItem {
+    key: String,
+    #[serde(rename = "type")]
+    item_type: &'static str,
+    size_byte: Option<usize>,
+    #[serde(serialize_with = "serialize_utc_datetime")]
+    last_modified: Option<DateTime<Local>>,
+}
+
+impl ListedItem {
+    fn dir(key: String) -> ListedItem {
+        ListedItem {
+            key,
+            item_type: "dir",
+            size_byte: None,
+            last_modified: None,
+        }
+    }
+
+    fn file(key: String, size_byte: usize, last_modified: DateTime<Local>) -> ListedItem {
+        ListedItem {
+            key,
+            item_type: "file",
+            size_byte: Some(size_byte),
+            last_modified: Some(last_modified),
+        }
+    }
+}
+
+/// Prints the objects under `uri` to stdout without the TUI.
+///
+/// Unless `recursive`, the objects in the nested folders are not listed, and the folders are listed instead.
+pub async fn list(client: &Client, uri: &str, recursive: bool, format: ListFormat) -> Result<()> {
+    let (bucket, prefix) = parse_s3_uri(uri)?;
+    // fails early if the bucket is in another region, or the access point ARN is not supported
+    client.load_bucket(&bucket).await?;
+
+    let items: Vec<ListedItem> = if recursive {
+        let objects = client.list_all_objects(&bucket, &prefix).await?;
+        objects
+            .into_iter()
+            .map(|obj| ListedItem::file(obj.key, obj.size_byte, obj.last_modified))
+            .collect()
+    } else {
+        let items = client.load_objects(&bucket, &prefix, |_| {}).await?;
+        let dir = dir_of_prefix(&prefix);
+        items
+            .into_iter()
+            .map(|item| match item {
+                ObjectItem::Dir { name } => ListedItem::dir(format!("{}{}/", dir, name)),
+                ObjectItem::File {
+                    name,
+                    size_byte,
+                    last_modified,
+                    ..
+                } => ListedItem::file(format!("{}{}", dir, name), size_byte, last_modified),
+            })
+            .collect()
+    };
+
+    let mut out = io::stdout().lock();
+    match write_items(&mut out, &items, format) {
+        Ok(()) => Ok(()),
+        // the reader may exit before reading all, e.g. `stu --list ... | head`
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        Err(e) => Err(AppError::new("Failed to write the listing", e)),
+    }
+}
+
+fn write_items<W: Write>(w: &mut W, items: &[ListedItem], format: ListFormat) -> io::Result<()> {
+    match format {
+        ListFormat::Plain => write_plain(w, items)?,
+        ListFormat::Json => {
+            serde_json::to_writer_pretty(&mut *w, items)?;
+            writeln!(w)?;
+        }
+        ListFormat::Csv => write_csv(w, items)?,
+    }
+    w.flush()
+}
+
+fn write_plain<W: Write>(w: &mut W, items: &[ListedItem]) -> io::Result<()> {
+    for item in items {
+        let date = item
+            .last_modified
+            .as_ref()
+            .map(format_datetime)
+            .unwrap_or_default();
+        let size = item
+            .size_byte
+            .map(format_size_byte)
+            .unwrap_or_else(|| "DIR".to_string());
+        writeln!(w, "{:<19}  {:>10}  {}", date, size, item.key)?;
+    }
+    Ok(())
+}
+
+fn write_csv<W: Write>(w: &mut W, items: &[ListedItem]) -> io::Result<()> {
+    writeln!(w, "key,type,size_byte,last_modified")?;
+    for item in items {
+        let size = item.size_byte.map(|s| s.to_string()).unwrap_or_default();
+        let date = item
+            .last_modified
+            .as_ref()
+            .map(to_utc_string)
+            .unwrap_or_default();
+        writeln!(
+            w,
+            "{},{},{},{}",
+            escape_csv_field(&item.key),
+            item.item_type,
+            size,
+            date
+        )?;
+    }
+    Ok(())
+}
+
+/// Quotes the field if needed (RFC 4180), since keys may contain commas, quotes and newlines.
+fn escape_csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn to_utc_string(dt: &DateTime<Local>) -> String {
+    dt.to_utc().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn serialize_utc_datetime<S: serde::Serializer>(
+    dt: &Option<DateTime<Local>>,
+    s: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    match dt {
+        Some(dt) => s.serialize_str(&to_utc_string(dt)),
+        None => s.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn items() -> Vec<ListedItem> {
+        let last_modified = Utc
+            .with_ymd_and_hms(2024, 1, 2, 13, 1, 2)
+            .unwrap()
+            .with_timezone(&Local);
+        vec![
+            ListedItem::dir("dir1/dir2/".into()),
+            ListedItem::file("dir1/file1.txt".into(), 1024 + 10, last_modified),
+            ListedItem::file("dir1/a,\"b\".txt".into(), 0, last_modified),
+        ]
+    }
+
+    fn write_to_string(format: ListFormat) -> String {
+        let mut buf = Vec::new();
+        write_items(&mut buf, &items(), format).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_write_plain() {
+        let date = format_datetime(&items()[1].last_modified.unwrap());
+        let expected = format!(
+            "                            DIR  dir1/dir2/\n\
+             {date}    1.01 KiB  dir1/file1.txt\n\
+             {date}         0 B  dir1/a,\"b\".txt\n"
+        );
+        assert_eq!(write_to_string(ListFormat::Plain), expected);
+    }
+
+    #[test]
+    fn test_write_json() {
+        let expected = r#"[
+  {
+    "key": "dir1/dir2/",
+    "type": "dir",
+    "size_byte": null,
+    "last_modified": null
+  },
+  {
+    "key": "dir1/file1.txt",
+    "type": "file",
+    "size_byte": 1034,
+    "last_modified": "2024-01-02T13:01:02Z"
+  },
+  {
+    "key": "dir1/a,\"b\".txt",
+    "type": "file",
+    "size_byte": 0,
+    "last_modified": "2024-01-02T13:01:02Z"
+  }
+]
+"#;
+        assert_eq!(write_to_string(ListFormat::Json), expected);
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let expected = "key,type,size_byte,last_modified\n\
+                        dir1/dir2/,dir,,\n\
+                        dir1/file1.txt,file,1034,2024-01-02T13:01:02Z\n\
+                        \"dir1/a,\"\"b\"\".txt\",file,0,2024-01-02T13:01:02Z\n";
+        assert_eq!(write_to_string(ListFormat::Csv), expected);
+    }
+}
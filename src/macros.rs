@@ -28,6 +28,27 @@ macro_rules! key_code_char {
     };
 }
 
+// a struct literal, so that it can be used in the static helps of the pages
+#[macro_export]
+macro_rules! key_event {
+    ( $code:expr ) => {
+        ratatui::crossterm::event::KeyEvent {
+            code: $code,
+            modifiers: ratatui::crossterm::event::KeyModifiers::NONE,
+            kind: ratatui::crossterm::event::KeyEventKind::Press,
+            state: ratatui::crossterm::event::KeyEventState::NONE,
+        }
+    };
+    ( $code:expr, Ctrl ) => {
+        ratatui::crossterm::event::KeyEvent {
+            code: $code,
+            modifiers: ratatui::crossterm::event::KeyModifiers::CONTROL,
+            kind: ratatui::crossterm::event::KeyEventKind::Press,
+            state: ratatui::crossterm::event::KeyEventState::NONE,
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! lines {
     ( $($s:expr),* $(,)? ) => {
@@ -1,45 +1,57 @@
 mod app;
+mod arn;
 mod cache;
 mod client;
 mod config;
 mod constant;
+mod credentials;
+mod csv;
+mod diagnose;
+mod encoding;
 mod error;
+mod etag;
 mod event;
 mod file;
+mod image;
+mod limiter;
+mod list;
 mod macros;
 mod object;
 mod pages;
+mod recent;
 mod run;
+mod terminal;
 mod ui;
 mod util;
 mod widget;
 
-use clap::{arg, Parser};
+use clap::{ArgGroup, Parser};
 use event::AppEventType;
 use file::open_or_create_append_file;
-use ratatui::{
-    backend::{Backend, CrosstermBackend},
-    crossterm::{
-        execute,
-        terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    },
-    Terminal,
-};
-use std::{
-    io::{stdout, Stdout},
-    panic,
-    sync::Mutex,
-};
+use ratatui::{backend::Backend, Terminal};
+use std::{env, io::Write, sync::Mutex};
 use tokio::spawn;
-use tracing_subscriber::fmt::time::ChronoLocal;
+use tracing::Level;
+use tracing_subscriber::{
+    filter::{LevelFilter, Targets},
+    fmt::time::ChronoLocal,
+    layer::SubscriberExt,
+    util::SubscriberInitExt,
+    Layer,
+};
 
 use crate::app::App;
-use crate::client::Client;
+use crate::client::{Client, ClientOptions};
 use crate::config::Config;
+use crate::list::ListFormat;
+use crate::terminal::{initialize_panic_handler, TerminalGuard};
+
+const LOG_FILE_ENV_VAR: &str = "STU_LOG_FILE";
 
 /// STU - S3 Terminal UI
 #[derive(Parser)]
 #[command(version)]
+#[command(group(ArgGroup::new("target").args(["bucket", "list", "diagnose"]).multiple(true)))]
 struct Args {
     /// AWS region
     #[arg(short, long)]
@@ -53,13 +65,47 @@ struct Args {
     #[arg(short, long, value_name = "NAME")]
     profile: Option<String>,
 
-    /// Target bucket name
+    /// Target bucket name, or access point / Object Lambda access point ARN
     #[arg(short, long, value_name = "NAME")]
     bucket: Option<String>,
 
+    /// Send unsigned requests without loading credentials (requires --bucket or --list)
+    #[arg(long, requires = "target")]
+    no_sign_request: bool,
+
+    /// Send requests as the requester of requester-pays buckets (you are charged for them)
+    #[arg(long)]
+    request_payer: bool,
+
+    /// Print the objects under the location to stdout without starting the UI
+    #[arg(long, value_name = "S3_URI", conflicts_with_all = ["bucket", "diagnose"])]
+    list: Option<String>,
+
+    /// List the objects in the nested folders too (with --list)
+    #[arg(long, requires = "list")]
+    recursive: bool,
+
+    /// Output format of --list
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        value_enum,
+        default_value_t,
+        requires = "list"
+    )]
+    format: ListFormat,
+
     /// Output debug logs
     #[arg(long)]
     debug: bool,
+
+    /// Output logs of S3 requests (can also be enabled by STU_LOG_FILE=1)
+    #[arg(long)]
+    log_file: bool,
+
+    /// Time the requests to S3 against the location and print the results without starting the UI
+    #[arg(long, value_name = "S3_URI", hide = true)]
+    diagnose: Option<String>,
 }
 
 #[tokio::main]
@@ -67,44 +113,75 @@ async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
     let config = Config::load()?;
 
-    initialize_debug_log(&args, &config)?;
-    initialize_panic_handler();
+    initialize_log(&args, &config)?;
 
-    let mut terminal = setup()?;
-    let ret = run(&mut terminal, args, config).await;
+    if let Some(uri) = &args.diagnose {
+        let client = Client::new(args.region.clone(), client_options(&args, &config)).await;
+        if let Err(e) = diagnose::diagnose(&client, uri).await {
+            eprintln!("{}", e.notification_msg());
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
-    shutdown()?;
+    if let Some(uri) = &args.list {
+        let client = Client::new(args.region.clone(), client_options(&args, &config)).await;
+        if let Err(e) = list::list(&client, uri, args.recursive, args.format).await {
+            // the cause tells why the listing failed, since there is no log to check as in the UI
+            match &e.cause {
+                Some(cause) => eprintln!("{}: {}", e.notification_msg(), cause),
+                None => eprintln!("{}", e.notification_msg()),
+            }
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
-    ret
-}
+    initialize_panic_handler();
 
-fn setup() -> anyhow::Result<Terminal<CrosstermBackend<Stdout>>> {
-    enable_raw_mode()?;
-    execute!(stdout(), EnterAlternateScreen)?;
+    // before the setup, so that raw mode is disabled also when the setup fails partway
+    let guard = TerminalGuard::new();
+    let mut terminal = terminal::setup()?;
+    let ret = run(&mut terminal, args, config).await;
+
+    drop(guard);
 
-    let backend = CrosstermBackend::new(stdout());
-    let terminal = Terminal::new(backend)?;
+    // print after the terminal is restored so that it can be captured by the shell
+    if let Some(location) = ret? {
+        println!("{}", location);
+    }
 
-    Ok(terminal)
+    Ok(())
 }
 
-async fn run<B: Backend>(
+async fn run<B: Backend + Write>(
     terminal: &mut Terminal<B>,
     args: Args,
     config: Config,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Option<String>> {
     let (tx, rx) = event::new();
     let (width, height) = get_frame_size(terminal);
+    let options = client_options(&args, &config);
     let mut app = App::new(config, tx.clone(), width, height);
 
     spawn(async move {
-        let client = Client::new(args.region, args.endpoint_url, args.profile).await;
+        let client = Client::new(args.region, options).await;
         tx.send(AppEventType::Initialize(client, args.bucket));
     });
 
-    run::run(&mut app, terminal, rx).await?;
+    let location = run::run(&mut app, terminal, rx).await?;
 
-    Ok(())
+    Ok(location)
+}
+
+fn client_options(args: &Args, config: &Config) -> ClientOptions {
+    ClientOptions {
+        endpoint_url: args.endpoint_url.clone(),
+        profile: args.profile.clone(),
+        client_config: config.client.clone(),
+        no_sign_request: args.no_sign_request,
+        request_payer: args.request_payer,
+    }
 }
 
 fn get_frame_size<B: Backend>(terminal: &mut Terminal<B>) -> (usize, usize) {
@@ -112,30 +189,64 @@ fn get_frame_size<B: Backend>(terminal: &mut Terminal<B>) -> (usize, usize) {
     (size.width as usize, size.height as usize)
 }
 
-fn shutdown() -> anyhow::Result<()> {
-    execute!(stdout(), LeaveAlternateScreen)?;
-    disable_raw_mode()?;
-    Ok(())
-}
-
-fn initialize_panic_handler() {
-    let original_hook = panic::take_hook();
-    panic::set_hook(Box::new(move |panic_info| {
-        shutdown().unwrap();
-        original_hook(panic_info);
-    }));
-}
-
-fn initialize_debug_log(args: &Args, config: &Config) -> anyhow::Result<()> {
-    if args.debug {
+fn initialize_log(args: &Args, config: &Config) -> anyhow::Result<()> {
+    // logs are written only to files, since the terminal is owned by the TUI
+    let debug_layer = if args.debug {
         let path = config.debug_log_path()?;
         let file = open_or_create_append_file(&path)?;
-        tracing_subscriber::fmt()
+        let layer = tracing_subscriber::fmt::layer()
             .with_ansi(false)
             .with_timer(ChronoLocal::rfc_3339())
-            .with_max_level(tracing::Level::DEBUG)
             .with_writer(Mutex::new(file))
+            .with_filter(LevelFilter::DEBUG);
+        Some(layer)
+    } else {
+        None
+    };
+
+    let client_layer = if args.log_file || log_file_env_enabled() {
+        let path = config.client_log_path()?;
+        let file = open_or_create_append_file(&path)?;
+        let layer = tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_timer(ChronoLocal::rfc_3339())
+            .with_writer(Mutex::new(file))
+            .with_filter(Targets::new().with_target(client::LOG_TARGET, Level::INFO));
+        Some(layer)
+    } else {
+        None
+    };
+
+    if debug_layer.is_some() || client_layer.is_some() {
+        tracing_subscriber::registry()
+            .with(debug_layer)
+            .with(client_layer)
             .init();
     }
     Ok(())
 }
+
+fn log_file_env_enabled() -> bool {
+    env::var(LOG_FILE_ENV_VAR).is_ok_and(|v| !v.is_empty() && v != "0" && v != "false")
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(&["stu", "--no-sign-request", "--bucket", "bucket-1"])]
+    #[case(&["stu", "--no-sign-request", "--list", "s3://bucket-1/dir1/"])]
+    #[case(&["stu", "--no-sign-request", "--diagnose", "s3://bucket-1/dir1/"])]
+    fn test_parse_no_sign_request(#[case] args: &[&str]) {
+        let args = Args::try_parse_from(args).unwrap();
+        assert!(args.no_sign_request);
+    }
+
+    #[test]
+    fn test_parse_no_sign_request_without_target() {
+        assert!(Args::try_parse_from(["stu", "--no-sign-request"]).is_err());
+    }
+}
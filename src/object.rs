@@ -1,9 +1,15 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::{self, Debug, Formatter},
 };
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, SecondsFormat};
+use itsuki::zero_indexed_enum;
+use serde::{Serialize, Serializer};
+
+use crate::{error::Result, util::json_to_yaml_string};
+
+pub const DELIMITER: &str = "/";
 
 #[derive(Clone, Debug)]
 pub struct BucketItem {
@@ -19,9 +25,28 @@ pub enum ObjectItem {
         name: String,
         size_byte: usize,
         last_modified: DateTime<Local>,
+        /// `None` if the owner is not fetched
+        owner: Option<ObjectOwner>,
     },
 }
 
+/// Owner of the object, which may be omitted by S3 even if requested (e.g. the bucket enforces the bucket owner).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObjectOwner {
+    pub display_name: Option<String>,
+    pub id: Option<String>,
+}
+
+impl ObjectOwner {
+    pub fn display(&self) -> String {
+        match (&self.display_name, &self.id) {
+            (Some(name), Some(id)) => format!("{} ({})", name, id),
+            (Some(s), None) | (None, Some(s)) => s.clone(),
+            (None, None) => "\u{2014}".to_string(),
+        }
+    }
+}
+
 impl ObjectItem {
     pub fn name(&self) -> &str {
         match self {
@@ -45,18 +70,244 @@ impl ObjectItem {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Returns the key to identify the item in a listing, since a folder and a file may have the same name.
+pub fn item_identity(item: &ObjectItem) -> (bool, &str) {
+    (matches!(item, ObjectItem::Dir { .. }), item.name())
+}
+
+/// Items added and removed by reloading a listing.
+#[derive(Debug, Default)]
+pub struct ObjectItemsDiff {
+    pub added: Vec<ObjectItem>,
+    pub removed: Vec<ObjectItem>,
+}
+
+impl ObjectItemsDiff {
+    pub fn new(old_items: &[ObjectItem], new_items: &[ObjectItem]) -> ObjectItemsDiff {
+        let old_keys: HashSet<(bool, &str)> = old_items.iter().map(item_identity).collect();
+        let new_keys: HashSet<(bool, &str)> = new_items.iter().map(item_identity).collect();
+        let added = new_items
+            .iter()
+            .filter(|item| !old_keys.contains(&item_identity(item)))
+            .cloned()
+            .collect();
+        let removed = old_items
+            .iter()
+            .filter(|item| !new_keys.contains(&item_identity(item)))
+            .cloned()
+            .collect();
+        ObjectItemsDiff { added, removed }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct FileDetail {
     pub name: String,
     pub size_byte: usize,
+    #[serde(serialize_with = "serialize_utc_datetime")]
     pub last_modified: DateTime<Local>,
     pub e_tag: String,
     pub content_type: String,
     pub storage_class: String,
+    /// `AES256`, `aws:kms`, `aws:kms:dsse` or `SSE-C`
+    pub encryption: Option<String>,
+    /// The KMS key of `aws:kms` and `aws:kms:dsse`
+    pub kms_key_id: Option<String>,
+    pub bucket_key_enabled: Option<bool>,
+    pub content_encoding: Option<String>,
     pub key: String,
     pub s3_uri: String,
     pub arn: String,
     pub object_url: String,
+    pub restore_status: Option<RestoreStatus>,
+}
+
+impl FileDetail {
+    /// Returns true if the object is stored in a storage class that must be restored before it can be read.
+    pub fn is_archived(&self) -> bool {
+        matches!(self.storage_class.as_str(), "GLACIER" | "DEEP_ARCHIVE")
+    }
+
+    /// Returns the storage class, which is not returned by the head request for `STANDARD`.
+    pub fn storage_class_name(&self) -> &str {
+        if self.storage_class.is_empty() {
+            StorageClass::Standard.name()
+        } else {
+            &self.storage_class
+        }
+    }
+
+    pub fn requires_restore(&self) -> bool {
+        self.is_archived() && !matches!(self.restore_status, Some(RestoreStatus::Restored { .. }))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn to_yaml(&self) -> String {
+        json_to_yaml_string(&serde_json::to_value(self).unwrap())
+    }
+}
+
+/// Serializes in ISO-8601 in UTC (e.g. `2024-01-02T13:01:02Z`), so that the output does not depend on the local timezone.
+fn serialize_utc_datetime<S: Serializer>(
+    dt: &DateTime<Local>,
+    s: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    s.serialize_str(&dt.to_utc().to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+/// Status of the restoration of an archived object (`x-amz-restore` header)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum RestoreStatus {
+    InProgress,
+    Restored { expiry_date: Option<String> },
+}
+
+#[derive(Default)]
+#[zero_indexed_enum]
+pub enum RestoreTier {
+    Expedited,
+    #[default]
+    Standard,
+    Bulk,
+}
+
+impl RestoreTier {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RestoreTier::Expedited => "Expedited",
+            RestoreTier::Standard => "Standard",
+            RestoreTier::Bulk => "Bulk",
+        }
+    }
+}
+
+/// Storage class that an object can be transitioned to.
+#[zero_indexed_enum]
+pub enum StorageClass {
+    Standard,
+    IntelligentTiering,
+    StandardIa,
+    OnezoneIa,
+    GlacierIr,
+    Glacier,
+    DeepArchive,
+}
+
+impl StorageClass {
+    /// Returns the name used in the API (and shown in the object detail).
+    pub fn name(&self) -> &'static str {
+        match self {
+            StorageClass::Standard => "STANDARD",
+            StorageClass::IntelligentTiering => "INTELLIGENT_TIERING",
+            StorageClass::StandardIa => "STANDARD_IA",
+            StorageClass::OnezoneIa => "ONEZONE_IA",
+            StorageClass::GlacierIr => "GLACIER_IR",
+            StorageClass::Glacier => "GLACIER",
+            StorageClass::DeepArchive => "DEEP_ARCHIVE",
+        }
+    }
+
+    /// Returns the minimum number of days the object is charged for once transitioned, if any.
+    pub fn min_storage_days(&self) -> Option<u32> {
+        match self {
+            StorageClass::Standard | StorageClass::IntelligentTiering => None,
+            StorageClass::StandardIa | StorageClass::OnezoneIa => Some(30),
+            StorageClass::GlacierIr | StorageClass::Glacier => Some(90),
+            StorageClass::DeepArchive => Some(180),
+        }
+    }
+
+    pub fn is_archive(&self) -> bool {
+        matches!(self, StorageClass::Glacier | StorageClass::DeepArchive)
+    }
+}
+
+/// Value that may not be readable because of the permissions of the caller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Readable<T> {
+    Value(T),
+    Denied,
+}
+
+impl<T> Readable<T> {
+    /// Converts the access denied error to `Denied`, other errors are returned as is.
+    pub fn from_result(result: Result<T>) -> Result<Readable<T>> {
+        match result {
+            Ok(v) => Ok(Readable::Value(v)),
+            Err(e) if e.is_access_denied() => Ok(Readable::Denied),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Readable::Value(v) => Some(v),
+            Readable::Denied => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PublicAccessBlock {
+    pub block_public_acls: bool,
+    pub ignore_public_acls: bool,
+    pub block_public_policy: bool,
+    pub restrict_public_buckets: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BucketGrant {
+    pub grantee: String,
+    pub permission: String,
+    // granted to everyone or to any authenticated AWS user
+    pub public: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BucketAccess {
+    /// `None` if the bucket has no policy
+    pub policy_public: Readable<Option<bool>>,
+    /// `None` if the public access block is not configured for the bucket
+    pub public_access_block: Readable<Option<PublicAccessBlock>>,
+    pub grants: Readable<Vec<BucketGrant>>,
+}
+
+impl BucketAccess {
+    /// Returns whether the bucket is public, taking the public access block into account.
+    ///
+    /// Returns `None` if it cannot be determined because some settings are not readable.
+    pub fn is_public(&self) -> Option<bool> {
+        let block = self
+            .public_access_block
+            .value()
+            .map(|b| b.clone().unwrap_or_default());
+        let policy = self.policy_public.value().map(|p| p.unwrap_or(false));
+        let acl = self.grants.value().map(|gs| gs.iter().any(|g| g.public));
+
+        let by_policy = effective_public(policy, block.as_ref().map(|b| b.restrict_public_buckets));
+        let by_acl = effective_public(acl, block.as_ref().map(|b| b.ignore_public_acls));
+        match (by_policy, by_acl) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        }
+    }
+}
+
+fn effective_public(public: Option<bool>, blocked: Option<bool>) -> Option<bool> {
+    match (public, blocked) {
+        (Some(false), _) | (_, Some(true)) => Some(false),
+        (Some(true), Some(false)) => Some(true),
+        _ => None,
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -64,16 +315,32 @@ pub struct FileVersion {
     pub version_id: String,
     pub size_byte: usize,
     pub last_modified: DateTime<Local>,
-    #[allow(dead_code)]
     pub is_latest: bool,
 }
 
+/// An object listed recursively under a prefix, with its full key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixObject {
+    pub key: String,
+    pub size_byte: usize,
+    pub last_modified: DateTime<Local>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PreviewPosition {
+    pub e_tag: String,
+    pub version_id: Option<String>,
+    pub v_offset: usize,
+    pub h_offset: usize,
+}
+
 #[derive(Debug, Default)]
 pub struct AppObjects {
     bucket_items: Vec<BucketItem>,
     object_items_map: HashMap<ObjectKey, Vec<ObjectItem>>,
     detail_map: HashMap<ObjectKey, FileDetail>,
     versions_map: HashMap<ObjectKey, Vec<FileVersion>>,
+    preview_position_map: HashMap<ObjectKey, PreviewPosition>,
 }
 
 impl AppObjects {
@@ -101,6 +368,10 @@ impl AppObjects {
         self.versions_map.get(key)
     }
 
+    pub fn set_object_detail(&mut self, key: ObjectKey, detail: FileDetail) {
+        self.detail_map.insert(key, detail);
+    }
+
     pub fn set_object_details(
         &mut self,
         key: ObjectKey,
@@ -110,6 +381,25 @@ impl AppObjects {
         self.detail_map.insert(key.to_owned(), detail);
         self.versions_map.insert(key.to_owned(), versions);
     }
+
+    /// Removes the cached details and versions of the objects directly under `prefix_key`.
+    pub fn remove_object_details_under(&mut self, prefix_key: &ObjectKey) {
+        self.detail_map.retain(|key, _| !key.is_item_of(prefix_key));
+        self.versions_map
+            .retain(|key, _| !key.is_item_of(prefix_key));
+    }
+
+    pub fn get_preview_position(&self, key: &ObjectKey) -> Option<&PreviewPosition> {
+        self.preview_position_map.get(key)
+    }
+
+    pub fn set_preview_position(&mut self, key: ObjectKey, position: PreviewPosition) {
+        self.preview_position_map.insert(key, position);
+    }
+
+    pub fn remove_preview_position(&mut self, key: &ObjectKey) {
+        self.preview_position_map.remove(key);
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
@@ -118,6 +408,44 @@ pub struct ObjectKey {
     pub object_path: Vec<String>,
 }
 
+impl ObjectKey {
+    /// Returns the prefix of the objects in this location, empty at the bucket root.
+    pub fn prefix(&self) -> String {
+        self.object_path
+            .iter()
+            .map(|p| format!("{}{}", p, DELIMITER))
+            .collect()
+    }
+
+    /// Returns the key of the item named `name` in this location.
+    pub fn join(&self, name: impl Into<String>) -> ObjectKey {
+        let mut object_path = self.object_path.clone();
+        object_path.push(name.into());
+        ObjectKey {
+            bucket_name: self.bucket_name.clone(),
+            object_path,
+        }
+    }
+
+    /// Returns true if this is the key of an item directly in `parent`.
+    pub fn is_item_of(&self, parent: &ObjectKey) -> bool {
+        self.bucket_name == parent.bucket_name
+            && self.object_path.len() == parent.object_path.len() + 1
+            && self.object_path.starts_with(&parent.object_path)
+    }
+
+    /// Returns the full key of the item in this location.
+    /// The key of a folder ends with the delimiter, as the prefix of its objects does.
+    pub fn item_key(&self, item: &ObjectItem) -> String {
+        let mut key = self.prefix();
+        key.push_str(item.name());
+        if let ObjectItem::Dir { .. } = item {
+            key.push_str(DELIMITER);
+        }
+        key
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct RawObject {
     pub bytes: Vec<u8>,
@@ -128,3 +456,232 @@ impl Debug for RawObject {
         write!(f, "RawObject {{ bytes: [u8; {}] }}", self.bytes.len())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(&[], dir("dir1"), "dir1/")]
+    #[case(&[], file("file1.txt"), "file1.txt")]
+    #[case(&["dir1", "dir2"], dir("dir3"), "dir1/dir2/dir3/")]
+    #[case(&["dir1", "dir2"], file("file1.txt"), "dir1/dir2/file1.txt")]
+    fn test_item_key(#[case] paths: &[&str], #[case] item: ObjectItem, #[case] expected: &str) {
+        let object_key = ObjectKey {
+            bucket_name: "bucket-1".to_string(),
+            object_path: paths.iter().map(|s| s.to_string()).collect(),
+        };
+        assert_eq!(object_key.item_key(&item), expected);
+    }
+
+    #[rstest]
+    #[case(Readable::Value(None), Readable::Value(None), vec![], Some(false))]
+    #[case(Readable::Value(Some(true)), Readable::Value(None), vec![], Some(true))]
+    #[case(Readable::Value(None), Readable::Value(None), vec![true], Some(true))]
+    #[case(Readable::Value(Some(true)), Readable::Value(Some(block(true))), vec![true], Some(false))]
+    #[case(Readable::Denied, Readable::Value(None), vec![], None)]
+    #[case(Readable::Denied, Readable::Value(Some(block(true))), vec![], Some(false))]
+    #[case(Readable::Value(Some(true)), Readable::Denied, vec![], None)]
+    #[case(Readable::Value(Some(false)), Readable::Denied, vec![false], Some(false))]
+    fn test_bucket_access_is_public(
+        #[case] policy_public: Readable<Option<bool>>,
+        #[case] public_access_block: Readable<Option<PublicAccessBlock>>,
+        #[case] public_grants: Vec<bool>,
+        #[case] expected: Option<bool>,
+    ) {
+        let grants = public_grants
+            .into_iter()
+            .map(|public| BucketGrant {
+                grantee: "grantee".to_string(),
+                permission: "READ".to_string(),
+                public,
+            })
+            .collect();
+        let access = BucketAccess {
+            policy_public,
+            public_access_block,
+            grants: Readable::Value(grants),
+        };
+        assert_eq!(access.is_public(), expected);
+    }
+
+    fn block(all: bool) -> PublicAccessBlock {
+        PublicAccessBlock {
+            block_public_acls: all,
+            ignore_public_acls: all,
+            block_public_policy: all,
+            restrict_public_buckets: all,
+        }
+    }
+
+    #[rstest]
+    #[case(Some("owner-1"), Some("id-1"), "owner-1 (id-1)")]
+    #[case(Some("owner-1"), None, "owner-1")]
+    #[case(None, Some("id-1"), "id-1")]
+    #[case(None, None, "\u{2014}")]
+    fn test_object_owner_display(
+        #[case] display_name: Option<&str>,
+        #[case] id: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let owner = ObjectOwner {
+            display_name: display_name.map(String::from),
+            id: id.map(String::from),
+        };
+        assert_eq!(owner.display(), expected);
+    }
+
+    #[test]
+    fn test_remove_object_details_under() {
+        let key = |paths: &[&str]| ObjectKey {
+            bucket_name: "bucket-1".to_string(),
+            object_path: paths.iter().map(|s| s.to_string()).collect(),
+        };
+        let mut app_objects = AppObjects::default();
+        for paths in [&["a.txt"][..], &["dir", "b.txt"], &["dir", "sub", "c.txt"]] {
+            app_objects.set_object_details(key(paths), file_detail(), vec![]);
+        }
+
+        app_objects.remove_object_details_under(&key(&["dir"]));
+
+        assert!(app_objects.get_object_detail(&key(&["a.txt"])).is_some());
+        assert!(app_objects
+            .get_object_detail(&key(&["dir", "b.txt"]))
+            .is_none());
+        assert!(app_objects
+            .get_object_versions(&key(&["dir", "b.txt"]))
+            .is_none());
+        assert!(app_objects
+            .get_object_detail(&key(&["dir", "sub", "c.txt"]))
+            .is_some());
+    }
+
+    #[test]
+    fn test_serialize_file_detail() {
+        let file_detail = FileDetail {
+            name: "file.txt".to_string(),
+            size_byte: 1024,
+            last_modified: Utc
+                .with_ymd_and_hms(2024, 1, 2, 13, 1, 2)
+                .unwrap()
+                .with_timezone(&Local),
+            e_tag: "etag".to_string(),
+            content_type: "text/plain".to_string(),
+            storage_class: "GLACIER".to_string(),
+            encryption: Some("AES256".to_string()),
+            kms_key_id: None,
+            bucket_key_enabled: None,
+            content_encoding: Some("gzip".to_string()),
+            key: "dir/file.txt".to_string(),
+            s3_uri: "s3://bucket-1/dir/file.txt".to_string(),
+            arn: "arn:aws:s3:::bucket-1/dir/file.txt".to_string(),
+            object_url: "https://bucket-1.s3.us-east-1.amazonaws.com/dir/file.txt".to_string(),
+            restore_status: Some(RestoreStatus::Restored {
+                expiry_date: Some("Fri, 21 Dec 2012 00:00:00 GMT".to_string()),
+            }),
+        };
+
+        let expected = r#"{
+  "name": "file.txt",
+  "size_byte": 1024,
+  "last_modified": "2024-01-02T13:01:02Z",
+  "e_tag": "etag",
+  "content_type": "text/plain",
+  "storage_class": "GLACIER",
+  "encryption": "AES256",
+  "kms_key_id": null,
+  "bucket_key_enabled": null,
+  "content_encoding": "gzip",
+  "key": "dir/file.txt",
+  "s3_uri": "s3://bucket-1/dir/file.txt",
+  "arn": "arn:aws:s3:::bucket-1/dir/file.txt",
+  "object_url": "https://bucket-1.s3.us-east-1.amazonaws.com/dir/file.txt",
+  "restore_status": {
+    "state": "restored",
+    "expiry_date": "Fri, 21 Dec 2012 00:00:00 GMT"
+  }
+}"#;
+        assert_eq!(file_detail.to_json(), expected);
+
+        let expected = r#"name: file.txt
+size_byte: 1024
+last_modified: "2024-01-02T13:01:02Z"
+e_tag: etag
+content_type: text/plain
+storage_class: GLACIER
+encryption: AES256
+kms_key_id: ~
+bucket_key_enabled: ~
+content_encoding: gzip
+key: dir/file.txt
+s3_uri: "s3://bucket-1/dir/file.txt"
+arn: "arn:aws:s3:::bucket-1/dir/file.txt"
+object_url: "https://bucket-1.s3.us-east-1.amazonaws.com/dir/file.txt"
+restore_status:
+  state: restored
+  expiry_date: "Fri, 21 Dec 2012 00:00:00 GMT""#;
+        assert_eq!(file_detail.to_yaml(), expected);
+    }
+
+    fn file_detail() -> FileDetail {
+        FileDetail {
+            name: "name".to_string(),
+            size_byte: 0,
+            last_modified: Local::now(),
+            e_tag: "etag".to_string(),
+            content_type: "text/plain".to_string(),
+            storage_class: "STANDARD".to_string(),
+            encryption: None,
+            kms_key_id: None,
+            bucket_key_enabled: None,
+            content_encoding: None,
+            key: "key".to_string(),
+            s3_uri: String::new(),
+            arn: String::new(),
+            object_url: String::new(),
+            restore_status: None,
+        }
+    }
+
+    #[test]
+    fn test_object_items_diff() {
+        let names = |items: &[ObjectItem]| -> Vec<String> {
+            items.iter().map(|item| item.name().to_string()).collect()
+        };
+        let old_items = [dir("a"), dir("b"), file("b"), file("c"), file("d")];
+        let new_items = [dir("a"), file("a"), dir("b"), file("d"), file("e")];
+
+        let diff = ObjectItemsDiff::new(&old_items, &new_items);
+        assert_eq!(names(&diff.added), ["a", "e"]);
+        assert_eq!(names(&diff.removed), ["b", "c"]);
+        // the folder with the same name as the added file is kept
+        assert!(matches!(diff.added[0], ObjectItem::File { .. }));
+        assert!(matches!(diff.removed[0], ObjectItem::File { .. }));
+        assert!(!diff.is_empty());
+
+        let diff = ObjectItemsDiff::new(&old_items, &old_items);
+        assert!(diff.is_empty());
+
+        let diff = ObjectItemsDiff::new(&[], &new_items);
+        assert_eq!(diff.added.len(), 5);
+        assert!(diff.removed.is_empty());
+    }
+
+    fn dir(name: &str) -> ObjectItem {
+        ObjectItem::Dir {
+            name: name.to_string(),
+        }
+    }
+
+    fn file(name: &str) -> ObjectItem {
+        ObjectItem::File {
+            name: name.to_string(),
+            size_byte: 0,
+            last_modified: Local::now(),
+            owner: None,
+        }
+    }
+}
@@ -3,8 +3,10 @@ pub mod page;
 pub mod bucket_list;
 pub mod help;
 pub mod initializing;
+pub mod object_denied;
 pub mod object_detail;
 pub mod object_list;
 pub mod object_preview;
+pub mod recent_objects;
 
 mod util;
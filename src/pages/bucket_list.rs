@@ -3,7 +3,7 @@ use std::cmp::Ordering;
 use ratatui::{
     crossterm::event::{KeyCode, KeyEvent},
     layout::Rect,
-    style::{Color, Style, Stylize},
+    style::{Color, Style},
     text::Line,
     widgets::ListItem,
     Frame,
@@ -11,19 +11,19 @@ use ratatui::{
 
 use crate::{
     event::{AppEventType, Sender},
-    key_code, key_code_char,
+    key_code, key_code_char, key_event,
     object::BucketItem,
-    pages::util::{build_helps, build_short_helps},
-    util::split_str,
+    pages::util::{build_commands, build_helps, build_short_helps, HelpGroup},
+    util::{substring_match_indices, truncate_with_ellipsis},
     widget::{
-        BucketListSortDialog, BucketListSortDialogState, BucketListSortType, InputDialog,
-        InputDialogState, ScrollList, ScrollListState,
+        highlighted_spans, BucketAccessDialog, BucketAccessDialogState, BucketListSortDialog,
+        BucketListSortDialogState, BucketListSortType, InputDialog, InputDialogState,
+        PaletteCommand, ScrollList, ScrollListState,
     },
 };
 
 const SELECTED_COLOR: Color = Color::Cyan;
 const SELECTED_ITEM_TEXT_COLOR: Color = Color::Black;
-const HIGHLIGHTED_ITEM_TEXT_COLOR: Color = Color::Red;
 
 #[derive(Debug)]
 pub struct BucketListPage {
@@ -43,6 +43,7 @@ enum ViewState {
     Default,
     FilterDialog,
     SortDialog,
+    AccessDialog(Box<BucketAccessDialogState>),
 }
 
 impl BucketListPage {
@@ -91,6 +92,9 @@ impl BucketListPage {
                 key_code_char!('b') if self.non_empty() => {
                     self.select_prev_page();
                 }
+                key_code_char!('y') if self.non_empty() => {
+                    self.copy_bucket_name();
+                }
                 key_code_char!('x') if self.non_empty() => {
                     self.tx.send(AppEventType::BucketListOpenManagementConsole);
                 }
@@ -100,6 +104,12 @@ impl BucketListPage {
                 key_code_char!('o') => {
                     self.open_sort_dialog();
                 }
+                key_code_char!('r') => {
+                    self.tx.send(AppEventType::OpenRecentObjects);
+                }
+                key_code_char!('a') if self.non_empty() => {
+                    self.load_access();
+                }
                 key_code_char!('?') => {
                     self.tx.send(AppEventType::OpenHelp);
                 }
@@ -138,6 +148,15 @@ impl BucketListPage {
                 }
                 _ => {}
             },
+            ViewState::AccessDialog(_) => match key {
+                key_code!(KeyCode::Esc) | key_code!(KeyCode::Backspace) => {
+                    self.close_access_dialog();
+                }
+                key_code_char!('?') => {
+                    self.tx.send(AppEventType::OpenHelp);
+                }
+                _ => {}
+            },
         }
     }
 
@@ -169,51 +188,184 @@ impl BucketListPage {
             let sort_dialog = BucketListSortDialog::new(self.sort_dialog_state);
             f.render_widget(sort_dialog, area);
         }
+
+        if let ViewState::AccessDialog(state) = &self.view_state {
+            let access_dialog = BucketAccessDialog::new(state);
+            f.render_widget(access_dialog, area);
+        }
     }
 
-    pub fn helps(&self) -> Vec<String> {
-        let helps: &[(&[&str], &str)] = match self.view_state {
+    fn help_groups(&self) -> &'static [HelpGroup<'static>] {
+        match self.view_state {
             ViewState::Default => {
                 if self.filter_input_state.input().is_empty() {
                     &[
-                        (&["Esc", "Ctrl-c"], "Quit app"),
-                        (&["j/k"], "Select item"),
-                        (&["g/G"], "Go to top/bottom"),
-                        (&["f"], "Scroll page forward"),
-                        (&["b"], "Scroll page backward"),
-                        (&["Enter"], "Open bucket"),
-                        (&["/"], "Filter bucket list"),
-                        (&["o"], "Sort bucket list"),
-                        (&["x"], "Open management console in browser"),
+                        (
+                            "General",
+                            &[
+                                (
+                                    &["Esc", "Ctrl-c"],
+                                    "Quit app",
+                                    Some(key_event!(KeyCode::Esc)),
+                                ),
+                                (
+                                    &["Ctrl-g"],
+                                    "Switch region",
+                                    Some(key_event!(KeyCode::Char('g'), Ctrl)),
+                                ),
+                            ],
+                        ),
+                        (
+                            "Navigation",
+                            &[
+                                (&["j/k"], "Select item", None),
+                                (&["g/G"], "Go to top/bottom", None),
+                                (&["f"], "Scroll page forward", None),
+                                (&["b"], "Scroll page backward", None),
+                                (&["Enter"], "Open bucket", Some(key_event!(KeyCode::Enter))),
+                                (
+                                    &["/"],
+                                    "Filter bucket list",
+                                    Some(key_event!(KeyCode::Char('/'))),
+                                ),
+                                (
+                                    &["o"],
+                                    "Sort bucket list",
+                                    Some(key_event!(KeyCode::Char('o'))),
+                                ),
+                                (
+                                    &["r"],
+                                    "Open recent objects",
+                                    Some(key_event!(KeyCode::Char('r'))),
+                                ),
+                            ],
+                        ),
+                        (
+                            "Copy",
+                            &[(
+                                &["y"],
+                                "Copy bucket name",
+                                Some(key_event!(KeyCode::Char('y'))),
+                            )],
+                        ),
+                        (
+                            "Operations",
+                            &[
+                                (
+                                    &["a"],
+                                    "Show public access of bucket",
+                                    Some(key_event!(KeyCode::Char('a'))),
+                                ),
+                                (
+                                    &["x"],
+                                    "Open management console in browser",
+                                    Some(key_event!(KeyCode::Char('x'))),
+                                ),
+                            ],
+                        ),
                     ]
                 } else {
                     &[
-                        (&["Ctrl-c"], "Quit app"),
-                        (&["Esc"], "Clear filter"),
-                        (&["j/k"], "Select item"),
-                        (&["g/G"], "Go to top/bottom"),
-                        (&["f"], "Scroll page forward"),
-                        (&["b"], "Scroll page backward"),
-                        (&["Enter"], "Open bucket"),
-                        (&["/"], "Filter bucket list"),
-                        (&["o"], "Sort bucket list"),
-                        (&["x"], "Open management console in browser"),
+                        (
+                            "General",
+                            &[
+                                (&["Ctrl-c"], "Quit app", None),
+                                (
+                                    &["Ctrl-g"],
+                                    "Switch region",
+                                    Some(key_event!(KeyCode::Char('g'), Ctrl)),
+                                ),
+                            ],
+                        ),
+                        (
+                            "Navigation",
+                            &[
+                                (&["Esc"], "Clear filter", Some(key_event!(KeyCode::Esc))),
+                                (&["j/k"], "Select item", None),
+                                (&["g/G"], "Go to top/bottom", None),
+                                (&["f"], "Scroll page forward", None),
+                                (&["b"], "Scroll page backward", None),
+                                (&["Enter"], "Open bucket", Some(key_event!(KeyCode::Enter))),
+                                (
+                                    &["/"],
+                                    "Filter bucket list",
+                                    Some(key_event!(KeyCode::Char('/'))),
+                                ),
+                                (
+                                    &["o"],
+                                    "Sort bucket list",
+                                    Some(key_event!(KeyCode::Char('o'))),
+                                ),
+                                (
+                                    &["r"],
+                                    "Open recent objects",
+                                    Some(key_event!(KeyCode::Char('r'))),
+                                ),
+                            ],
+                        ),
+                        (
+                            "Copy",
+                            &[(
+                                &["y"],
+                                "Copy bucket name",
+                                Some(key_event!(KeyCode::Char('y'))),
+                            )],
+                        ),
+                        (
+                            "Operations",
+                            &[
+                                (
+                                    &["a"],
+                                    "Show public access of bucket",
+                                    Some(key_event!(KeyCode::Char('a'))),
+                                ),
+                                (
+                                    &["x"],
+                                    "Open management console in browser",
+                                    Some(key_event!(KeyCode::Char('x'))),
+                                ),
+                            ],
+                        ),
                     ]
                 }
             }
             ViewState::FilterDialog => &[
-                (&["Ctrl-c"], "Quit app"),
-                (&["Esc"], "Close filter dialog"),
-                (&["Enter"], "Apply filter"),
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc"], "Close filter dialog", None),
+                        (&["Enter"], "Apply filter", None),
+                    ],
+                ),
             ],
             ViewState::SortDialog => &[
-                (&["Ctrl-c"], "Quit app"),
-                (&["Esc"], "Close sort dialog"),
-                (&["j/k"], "Select item"),
-                (&["Enter"], "Apply sort"),
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc"], "Close sort dialog", None),
+                        (&["j/k"], "Select item", None),
+                        (&["Enter"], "Apply sort", None),
+                    ],
+                ),
             ],
-        };
-        build_helps(helps)
+            ViewState::AccessDialog(_) => &[
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[(&["Esc", "Backspace"], "Close access dialog", None)],
+                ),
+            ],
+        }
+    }
+
+    pub fn helps(&self) -> Vec<(String, Vec<String>)> {
+        build_helps(self.help_groups())
+    }
+
+    pub fn commands(&self) -> Vec<PaletteCommand> {
+        build_commands(self.help_groups())
     }
 
     pub fn short_helps(&self) -> Vec<(String, usize)> {
@@ -252,12 +404,32 @@ impl BucketListPage {
                 (&["Enter"], "Sort", 1),
                 (&["?"], "Help", 0),
             ],
+            ViewState::AccessDialog(_) => &[(&["Esc"], "Close", 0), (&["?"], "Help", 0)],
         };
         build_short_helps(helps)
     }
 }
 
 impl BucketListPage {
+    pub fn open_access_dialog(&mut self, state: BucketAccessDialogState) {
+        self.view_state = ViewState::AccessDialog(Box::new(state));
+    }
+
+    fn close_access_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn load_access(&self) {
+        let bucket = self.current_selected_item().name.clone();
+        self.tx.send(AppEventType::LoadBucketAccess(bucket));
+    }
+
+    fn copy_bucket_name(&self) {
+        let bucket = self.current_selected_item().name.clone();
+        self.tx
+            .send(AppEventType::CopyToClipboard("bucket name".into(), bucket));
+    }
+
     fn select_next(&mut self) {
         self.list_state.select_next();
     }
@@ -324,7 +496,8 @@ impl BucketListPage {
             .map(|(idx, _)| idx)
             .collect();
         // reset list state
-        self.list_state = ScrollListState::new(self.view_indices.len());
+        self.list_state =
+            ScrollListState::new(self.view_indices.len()).with_wrap(self.list_state.wrap());
 
         self.sort_view_indices();
     }
@@ -361,6 +534,36 @@ impl BucketListPage {
         self.view_indices.sort_by(sort_func);
     }
 
+    /// Returns whether the bucket is in the list, even if it is hidden by the filter.
+    pub fn has_item_by_name(&self, name: &str) -> bool {
+        self.bucket_items.iter().any(|item| item.name == name)
+    }
+
+    /// Selects the bucket with the given name, clearing the filter if it hides the bucket.
+    pub fn select_item_by_name(&mut self, name: &str) -> bool {
+        if !self.has_item_by_name(name) {
+            return false;
+        }
+        if !self
+            .view_indices
+            .iter()
+            .any(|&i| self.bucket_items[i].name == name)
+        {
+            self.reset_filter();
+        }
+        let index = self
+            .view_indices
+            .iter()
+            .position(|&i| self.bucket_items[i].name == name)
+            .unwrap();
+        self.list_state.select(index);
+        true
+    }
+
+    pub fn set_wrap_selection(&mut self, wrap: bool) {
+        self.list_state.set_wrap(wrap);
+    }
+
     pub fn current_selected_item(&self) -> &BucketItem {
         let i = self
             .view_indices
@@ -394,7 +597,7 @@ fn build_list_items<'a>(
     selected: usize,
     area: Rect,
 ) -> Vec<ListItem<'a>> {
-    let show_item_count = (area.height as usize) - 2 /* border */;
+    let show_item_count = (area.height as usize).saturating_sub(2 /* border */);
     view_indices
         .iter()
         .map(|&original_idx| &current_items[original_idx])
@@ -403,24 +606,20 @@ fn build_list_items<'a>(
         .enumerate()
         .map(|(idx, item)| {
             let selected = idx + offset == selected;
-            build_list_item(&item.name, selected, filter)
+            build_list_item(&item.name, selected, filter, area.width)
         })
         .collect()
 }
 
-fn build_list_item<'a>(name: &'a str, selected: bool, filter: &'a str) -> ListItem<'a> {
-    let line = if filter.is_empty() {
-        Line::from(vec![" ".into(), name.into(), " ".into()])
-    } else {
-        let (before, highlighted, after) = split_str(name, filter).unwrap();
-        Line::from(vec![
-            " ".into(),
-            before.into(),
-            highlighted.fg(HIGHLIGHTED_ITEM_TEXT_COLOR),
-            after.into(),
-            " ".into(),
-        ])
-    };
+fn build_list_item<'a>(name: &'a str, selected: bool, filter: &'a str, width: u16) -> ListItem<'a> {
+    let name_w = (width as usize).saturating_sub(2 /* spaces */ + 4 /* border + pad */);
+    let name = truncate_with_ellipsis(name, name_w);
+    // the matched part may have been truncated
+    let indices = substring_match_indices(filter, &name).unwrap_or_default();
+    let mut spans = vec![" ".into()];
+    spans.extend(highlighted_spans(&name, &indices, Style::default()));
+    spans.push(" ".into());
+    let line = Line::from(spans);
 
     let style = if selected {
         Style::default()
@@ -478,6 +677,48 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_render_long_names() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = setup_terminal()?;
+
+        terminal.draw(|f| {
+            let items = [
+                "bucket-with-a-very-long-long-name",
+                "バケットとても長い長い長い名前",
+            ]
+            .iter()
+            .map(|name| BucketItem {
+                name: name.to_string(),
+            })
+            .collect();
+            let mut page = BucketListPage::new(items, tx);
+            let area = Rect::new(0, 0, 30, 10);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌───────────────────── 1 / 2 ┐",
+            "│  bucket-with-a-very-long…  │",
+            "│  バケットとても長い長い…   │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "└────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            (2..28, [1]) => bg: Color::Cyan, fg: Color::Black,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
     #[test]
     fn test_render_with_scroll() -> std::io::Result<()> {
         let (tx, _) = event::new();
@@ -678,6 +919,34 @@ mod tests {
         assert_eq!(page.view_indices, vec![0, 1, 2, 3, 4]);
     }
 
+    #[test]
+    fn test_select_item_by_name() {
+        let (tx, _) = event::new();
+
+        let items = ["foo", "bar", "baz"]
+            .iter()
+            .map(|name| BucketItem {
+                name: name.to_string(),
+            })
+            .collect();
+        let mut page = BucketListPage::new(items, tx);
+
+        page.handle_key(KeyEvent::from(KeyCode::Char('/')));
+        page.handle_key(KeyEvent::from(KeyCode::Char('b')));
+        page.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(page.view_indices, vec![1, 2]);
+
+        assert!(!page.has_item_by_name("qux"));
+        assert!(!page.select_item_by_name("qux"));
+        assert_eq!(page.view_indices, vec![1, 2]);
+
+        // the filter is cleared to select the hidden bucket
+        assert!(page.has_item_by_name("foo"));
+        assert!(page.select_item_by_name("foo"));
+        assert_eq!(page.view_indices, vec![0, 1, 2]);
+        assert_eq!(page.current_selected_item().name, "foo");
+    }
+
     #[test]
     fn test_sort_items() {
         let (tx, _) = event::new();
@@ -754,6 +1023,24 @@ mod tests {
         assert_eq!(page.view_indices, vec![0, 4]);
     }
 
+    #[test]
+    fn test_render_small_area() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = Terminal::new(TestBackend::new(3, 3))?;
+
+        // should not panic
+        terminal.draw(|f| {
+            let items = vec![BucketItem {
+                name: "bucket1".to_string(),
+            }];
+            let mut page = BucketListPage::new(items, tx);
+            let area = Rect::new(0, 0, 3, 3);
+            page.render(f, area);
+        })?;
+
+        Ok(())
+    }
+
     fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
         let backend = TestBackend::new(30, 10);
         let mut terminal = Terminal::new(backend)?;
@@ -2,7 +2,7 @@ use ratatui::{
     buffer::Buffer,
     crossterm::event::{KeyCode, KeyEvent},
     layout::{Constraint, Layout, Rect},
-    style::{Color, Stylize},
+    style::{Color, Modifier, Stylize},
     text::Line,
     widgets::{Block, Borders, Padding, Paragraph, Widget},
     Frame,
@@ -18,16 +18,19 @@ use crate::{
 };
 
 const LINK_TEXT_COLOR: Color = Color::Blue;
+const GROUP_HEADER_COLOR: Color = Color::Cyan;
+
+const GROUP_INDENT: &str = "  ";
 
 #[derive(Debug)]
 pub struct HelpPage {
-    helps: Vec<String>,
+    helps: Vec<(String, Vec<String>)>,
 
     tx: Sender,
 }
 
 impl HelpPage {
-    pub fn new(helps: Vec<String>, tx: Sender) -> Self {
+    pub fn new(helps: Vec<(String, Vec<String>)>, tx: Sender) -> Self {
         Self { helps, tx }
     }
 
@@ -111,11 +114,11 @@ impl Widget for About<'_> {
 
 #[derive(Debug)]
 struct Help<'a> {
-    helps: &'a [String],
+    helps: &'a [(String, Vec<String>)],
 }
 
 impl<'a> Help<'a> {
-    fn new(helps: &'a [String]) -> Self {
+    fn new(helps: &'a [(String, Vec<String>)]) -> Self {
         Self { helps }
     }
 }
@@ -123,7 +126,7 @@ impl<'a> Help<'a> {
 impl Widget for Help<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let max_help_width: usize = 80;
-        let max_width = max_help_width.min(area.width as usize) - 2;
+        let max_width = max_help_width.min(area.width as usize).saturating_sub(2);
 
         let help = build_help_lines(self.helps, max_width);
 
@@ -136,14 +139,36 @@ impl Widget for Help<'_> {
     }
 }
 
-fn build_help_lines(helps: &[String], max_width: usize) -> Vec<Line> {
+fn build_help_lines(helps: &[(String, Vec<String>)], max_width: usize) -> Vec<Line<'_>> {
     let delimiter = ",  ";
-    let word_groups = group_strings_to_fit_width(helps, max_width, delimiter);
-    let lines: Vec<Line> = word_groups
+    let max_width = max_width.saturating_sub(GROUP_INDENT.len());
+    let groups: Vec<Vec<Line>> = helps
         .iter()
-        .map(|ws| Line::from(ws.join(delimiter)))
+        .filter(|(_, helps)| !helps.is_empty())
+        .map(|(group, helps)| {
+            let header = Line::from(
+                group
+                    .as_str()
+                    .fg(GROUP_HEADER_COLOR)
+                    .add_modifier(Modifier::BOLD),
+            );
+            let word_groups = group_strings_to_fit_width(helps, max_width, delimiter);
+            let lines = word_groups
+                .iter()
+                .map(|ws| Line::from(format!("{}{}", GROUP_INDENT, ws.join(delimiter))));
+            std::iter::once(header).chain(lines).collect()
+        })
         .collect();
-    with_empty_lines(lines)
+    // separate groups by an empty line
+    let n = groups.len();
+    let mut ret = Vec::new();
+    for (i, lines) in groups.into_iter().enumerate() {
+        ret.extend(lines);
+        if i != n - 1 {
+            ret.push(Line::raw(""));
+        }
+    }
+    ret
 }
 
 fn with_empty_lines(lines: Vec<Line>) -> Vec<Line> {
@@ -171,15 +196,22 @@ mod tests {
         let mut terminal = setup_terminal()?;
 
         terminal.draw(|f| {
-            let helps = [
-                "<key1>: action1",
-                "<key2>: action2",
-                "<key3>: action3",
-                "<key4>: action4",
-            ]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
+            let helps = vec![
+                (
+                    "Group1".to_string(),
+                    [
+                        "<key1>: action1",
+                        "<key2>: action2",
+                        "<key3>: action3",
+                        "<key4>: action4",
+                    ]
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+                ),
+                ("Group2".to_string(), vec!["<key5>: action5".to_string()]),
+                ("Empty".to_string(), vec![]),
+            ];
             let mut page = HelpPage::new(helps, tx);
             let area = Rect::new(0, 0, 70, 20);
             page.render(f, area);
@@ -198,12 +230,12 @@ mod tests {
             "│                                                                    │",
             "│ ────────────────────────────────────────────────────────────────── │",
             "│                                                                    │",
-            "│  <key1>: action1,  <key2>: action2,  <key3>: action3               │",
-            "│                                                                    │",
-            "│  <key4>: action4                                                   │",
-            "│                                                                    │",
-            "│                                                                    │",
+            "│  Group1                                                            │",
+            "│    <key1>: action1,  <key2>: action2,  <key3>: action3             │",
+            "│    <key4>: action4                                                 │",
             "│                                                                    │",
+            "│  Group2                                                            │",
+            "│    <key5>: action5                                                 │",
             "│                                                                    │",
             "│                                                                    │",
             "│                                                                    │",
@@ -214,6 +246,8 @@ mod tests {
             (3..37, [6]) => fg: Color::Blue,
             // divider
             (2..68, [8]) => fg: Color::DarkGray,
+            // group headers
+            (3..9, [10, 14]) => fg: Color::Cyan, modifier: Modifier::BOLD,
         }
 
         terminal.backend().assert_buffer(&expected);
@@ -221,6 +255,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_render_small_area() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = Terminal::new(TestBackend::new(3, 3))?;
+
+        // should not panic
+        terminal.draw(|f| {
+            let helps = vec![("Group1".to_string(), vec!["<key1>: action1".to_string()])];
+            let mut page = HelpPage::new(helps, tx);
+            let area = Rect::new(0, 0, 3, 3);
+            page.render(f, area);
+        })?;
+
+        Ok(())
+    }
+
     fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
         let backend = TestBackend::new(70, 20);
         let mut terminal = Terminal::new(backend)?;
@@ -0,0 +1,237 @@
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{Block, Padding, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    error::AppError,
+    event::{AppEventType, Sender},
+    key_code, key_code_char, key_event,
+    object::ObjectItem,
+    pages::{
+        object_detail::{build_list_items_from_object_items, split_panes},
+        util::{build_commands, build_helps, build_short_helps, HelpGroup},
+    },
+    widget::{PaletteCommand, ScrollList, ScrollListState},
+};
+
+const DENIED_COLOR: Color = Color::Red;
+
+/// Shown instead of the detail when the object is listed but its detail cannot be read.
+#[derive(Debug)]
+pub struct ObjectDeniedPage {
+    key: String,
+    error_msg: String,
+
+    object_items: Vec<ObjectItem>,
+    list_state: ScrollListState,
+    list_width_percentage: u16,
+    tx: Sender,
+}
+
+impl ObjectDeniedPage {
+    pub fn new(
+        key: String,
+        error: AppError,
+        object_items: Vec<ObjectItem>,
+        list_state: ScrollListState,
+        list_width_percentage: u16,
+        tx: Sender,
+    ) -> Self {
+        Self {
+            key,
+            error_msg: error.notification_msg(),
+            object_items,
+            list_state,
+            list_width_percentage,
+            tx,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        match key {
+            key_code!(KeyCode::Esc) => {
+                self.tx.send(AppEventType::Quit);
+            }
+            key_code!(KeyCode::Backspace) => {
+                self.tx.send(AppEventType::CloseCurrentPage);
+            }
+            key_code_char!('y') => {
+                self.copy_key();
+            }
+            key_code_char!('?') => {
+                self.tx.send(AppEventType::OpenHelp);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = split_panes(area, self.list_width_percentage);
+
+        let offset = self.list_state.offset;
+        let selected = self.list_state.selected;
+
+        let list_items =
+            build_list_items_from_object_items(&self.object_items, offset, selected, chunks[0]);
+
+        let list = ScrollList::new(list_items);
+        f.render_stateful_widget(list, chunks[0], &mut self.list_state);
+
+        let lines = vec![
+            Line::from("Access denied for this object".bold().fg(DENIED_COLOR)),
+            Line::from(""),
+            Line::from("Key:".bold()),
+            Line::from(format!(" {}", self.key)),
+            Line::from(""),
+            Line::from("Error:".bold()),
+            Line::from(format!(" {}", self.error_msg)),
+            Line::from(""),
+            Line::from("The object is listed, but reading its detail requires the s3:GetObject permission on it."),
+        ];
+        let panel = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().padding(Padding::horizontal(1)));
+        f.render_widget(panel, chunks[1]);
+    }
+
+    fn help_groups(&self) -> &'static [HelpGroup<'static>] {
+        &[
+            (
+                "General",
+                &[(
+                    &["Esc", "Ctrl-c"],
+                    "Quit app",
+                    Some(key_event!(KeyCode::Esc)),
+                )],
+            ),
+            (
+                "Navigation",
+                &[(
+                    &["Backspace"],
+                    "Close detail panel",
+                    Some(key_event!(KeyCode::Backspace)),
+                )],
+            ),
+            (
+                "Copy",
+                &[(
+                    &["y"],
+                    "Copy object key",
+                    Some(key_event!(KeyCode::Char('y'))),
+                )],
+            ),
+        ]
+    }
+
+    pub fn helps(&self) -> Vec<(String, Vec<String>)> {
+        build_helps(self.help_groups())
+    }
+
+    pub fn commands(&self) -> Vec<PaletteCommand> {
+        build_commands(self.help_groups())
+    }
+
+    pub fn short_helps(&self) -> Vec<(String, usize)> {
+        let helps: &[(&[&str], &str, usize)] = &[
+            (&["Esc"], "Quit", 0),
+            (&["y"], "Copy key", 2),
+            (&["Backspace"], "Close", 1),
+            (&["?"], "Help", 0),
+        ];
+        build_short_helps(helps)
+    }
+
+    fn copy_key(&self) {
+        let key = self.key.clone();
+        self.tx
+            .send(AppEventType::CopyToClipboard("key".into(), key));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Local, NaiveDateTime};
+    use ratatui::{backend::TestBackend, buffer::Buffer, style::Modifier, Terminal};
+
+    use crate::{event, set_cells};
+
+    use super::*;
+
+    #[test]
+    fn test_render() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = Terminal::new(TestBackend::new(60, 16))?;
+
+        terminal.draw(|f| {
+            let items = vec![
+                ObjectItem::File {
+                    name: "secret".to_string(),
+                    size_byte: 2048,
+                    last_modified: parse_datetime("2024-01-02 13:01:02"),
+                    owner: None,
+                },
+                ObjectItem::File {
+                    name: "file2".to_string(),
+                    size_byte: 1024,
+                    last_modified: parse_datetime("2024-01-02 13:01:02"),
+                    owner: None,
+                },
+            ];
+            let list_state = ScrollListState::new(items.len());
+            let error = AppError::access_denied(
+                "Failed to load object detail",
+                std::io::Error::other("denied"),
+            );
+            let mut page =
+                ObjectDeniedPage::new("dir/secret".into(), error, items, list_state, 50, tx);
+            let area = Rect::new(0, 0, 60, 16);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌───────────────────── 1 / 2 ┐┌────────────────────────────┐",
+            "│  secret                    ││ Access denied for this     │",
+            "│  file2                     ││ object                     │",
+            "│                            ││                            │",
+            "│                            ││ Key:                       │",
+            "│                            ││  dir/secret                │",
+            "│                            ││                            │",
+            "│                            ││ Error:                     │",
+            "│                            ││  Access denied (Failed to  │",
+            "│                            ││ load object detail)        │",
+            "│                            ││                            │",
+            "│                            ││ The object is listed, but  │",
+            "│                            ││ reading its detail         │",
+            "│                            ││ requires the s3:GetObject  │",
+            "│                            ││ permission on it.          │",
+            "└────────────────────────────┘└────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // selected item
+            (2..28, [1]) => bg: Color::DarkGray, fg: Color::Black,
+            // denied message
+            (32..54, [1]) => fg: Color::Red, modifier: Modifier::BOLD,
+            (32..38, [2]) => fg: Color::Red, modifier: Modifier::BOLD,
+            // labels
+            (32..36, [4]) => modifier: Modifier::BOLD,
+            (32..38, [7]) => modifier: Modifier::BOLD,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    fn parse_datetime(s: &str) -> DateTime<Local> {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+    }
+}
@@ -10,31 +10,48 @@ use ratatui::{
 };
 
 use crate::{
+    client::ARCHIVED_OBJECT_MSG,
+    config::{MAX_LIST_WIDTH_PERCENTAGE, MIN_LIST_WIDTH_PERCENTAGE},
     event::{AppEventType, Sender},
-    key_code, key_code_char,
-    object::{FileDetail, FileVersion, ObjectItem},
-    pages::util::{build_helps, build_short_helps},
+    key_code, key_code_char, key_event,
+    object::{FileDetail, FileVersion, ObjectItem, ObjectOwner, RestoreStatus},
+    pages::util::{build_commands, build_helps, build_short_helps, HelpGroup},
     ui::common::{format_datetime, format_size_byte, format_version},
+    util::{pad_to_width, truncate_with_ellipsis},
     widget::{
         Bar, CopyDetailDialog, CopyDetailDialogState, Divider, InputDialog, InputDialogState,
+        LargeObjectDialog, LargeObjectDialogState, PaletteCommand, RestoreDialog,
+        RestoreDialogState, SaveConfirmAction, SaveConfirmDialog, SaveConfirmDialogState,
         ScrollLines, ScrollLinesOptions, ScrollLinesState, ScrollList, ScrollListState,
+        StorageClassDialog, StorageClassDialogState, VersionDiffDialog, VersionDiffDialogState,
     },
 };
 
 const SELECTED_COLOR: Color = Color::Cyan;
 const SELECTED_ITEM_TEXT_COLOR: Color = Color::Black;
+const MARKED_COLOR: Color = Color::Yellow;
 const SELECTED_DISABLED_COLOR: Color = Color::DarkGray;
 
+const LIST_WIDTH_PERCENTAGE_STEP: u16 = 5;
+const MIN_PANE_WIDTH: u16 = 20;
+
+// larger objects must be copied with multipart upload
+const MAX_COPY_SIZE_BYTE: usize = 5 * 1024 * 1024 * 1024;
+
 #[derive(Debug)]
 pub struct ObjectDetailPage {
     file_detail: FileDetail,
     file_versions: Vec<FileVersion>,
+    bucket_name: String,
+    // only listed, not included in the detail
+    owner: Option<ObjectOwner>,
 
     tab: Tab,
     view_state: ViewState,
 
     object_items: Vec<ObjectItem>,
     list_state: ScrollListState,
+    list_width_percentage: u16,
     detail_tab_state: DetailTabState,
     version_tab_state: VersionTabState,
     tx: Sender,
@@ -53,26 +70,40 @@ enum ViewState {
     #[default]
     Default,
     SaveDialog(InputDialogState),
+    SaveConfirmDialog(SaveConfirmDialogState),
     CopyDetailDialog(CopyDetailDialogState),
+    RestoreDialog(RestoreDialogState),
+    StorageClassDialog(StorageClassDialogState),
+    VersionDiffDialog(Box<VersionDiffDialogState>),
+    LargeObjectDialog(LargeObjectDialogState),
 }
 
 impl ObjectDetailPage {
     pub fn new(
         file_detail: FileDetail,
         file_versions: Vec<FileVersion>,
+        bucket_name: String,
         object_items: Vec<ObjectItem>,
         list_state: ScrollListState,
+        list_width_percentage: u16,
         tx: Sender,
     ) -> Self {
-        let detail_tab_state = DetailTabState::new(&file_detail);
+        let owner = match object_items.get(list_state.selected) {
+            Some(ObjectItem::File { owner, .. }) => owner.clone(),
+            _ => None,
+        };
+        let detail_tab_state = DetailTabState::new(&file_detail, owner.as_ref());
         let version_tab_state = VersionTabState::new(&file_versions);
         Self {
             file_detail,
             file_versions,
+            bucket_name,
+            owner,
             tab: Tab::Detail,
             view_state: ViewState::Default,
             object_items,
             list_state,
+            list_width_percentage,
             detail_tab_state,
             version_tab_state,
             tx,
@@ -117,6 +148,16 @@ impl ObjectDetailPage {
                         self.version_tab_state.select_last();
                     }
                 }
+                key_code_char!('m') => {
+                    if self.tab == Tab::Version {
+                        self.version_tab_state.toggle_mark();
+                    }
+                }
+                key_code_char!('d') => {
+                    if self.tab == Tab::Version {
+                        self.load_version_diff();
+                    }
+                }
                 key_code_char!('s') => {
                     self.download();
                 }
@@ -129,9 +170,71 @@ impl ObjectDetailPage {
                 key_code_char!('r') => {
                     self.open_copy_detail_dialog();
                 }
+                key_code_char!('y') => {
+                    self.copy_key();
+                }
+                key_code_char!('Y') => {
+                    self.copy_bucket_name();
+                }
                 key_code_char!('x') => {
                     self.open_management_console();
                 }
+                key_code_char!('R') => {
+                    self.open_restore_dialog();
+                }
+                key_code_char!('C') => {
+                    self.open_storage_class_dialog();
+                }
+                key_code_char!('<') => {
+                    self.resize_list(false);
+                }
+                key_code_char!('>') => {
+                    self.resize_list(true);
+                }
+                key_code_char!('?') => {
+                    self.tx.send(AppEventType::OpenHelp);
+                }
+                _ => {}
+            },
+            ViewState::RestoreDialog(ref mut state) => match key {
+                key_code!(KeyCode::Esc) | key_code!(KeyCode::Backspace) => {
+                    self.close_restore_dialog();
+                }
+                key_code!(KeyCode::Enter) => {
+                    let state = *state;
+                    self.restore(state);
+                }
+                key_code_char!('j') => {
+                    state.select_next();
+                }
+                key_code_char!('k') => {
+                    state.select_prev();
+                }
+                key_code_char!('l') => {
+                    state.increase_days();
+                }
+                key_code_char!('h') => {
+                    state.decrease_days();
+                }
+                key_code_char!('?') => {
+                    self.tx.send(AppEventType::OpenHelp);
+                }
+                _ => {}
+            },
+            ViewState::StorageClassDialog(ref mut state) => match key {
+                key_code!(KeyCode::Esc) | key_code!(KeyCode::Backspace) => {
+                    self.close_storage_class_dialog();
+                }
+                key_code!(KeyCode::Enter) => {
+                    let state = state.clone();
+                    self.transition_storage_class(state);
+                }
+                key_code_char!('j') => {
+                    state.select_next();
+                }
+                key_code_char!('k') => {
+                    state.select_prev();
+                }
                 key_code_char!('?') => {
                     self.tx.send(AppEventType::OpenHelp);
                 }
@@ -152,6 +255,51 @@ impl ObjectDetailPage {
                     state.handle_key_event(key);
                 }
             },
+            ViewState::SaveConfirmDialog(ref mut state) => match key {
+                key_code!(KeyCode::Esc) | key_code!(KeyCode::Backspace) => {
+                    self.close_save_dialog();
+                }
+                key_code!(KeyCode::Enter) => {
+                    let state = state.clone();
+                    self.apply_save_confirm(state);
+                }
+                key_code_char!('j') => {
+                    state.select_next();
+                }
+                key_code_char!('k') => {
+                    state.select_prev();
+                }
+                key_code_char!('?') => {
+                    self.tx.send(AppEventType::OpenHelp);
+                }
+                _ => {}
+            },
+            ViewState::VersionDiffDialog(_) => match key {
+                key_code!(KeyCode::Esc) | key_code!(KeyCode::Backspace) => {
+                    self.close_version_diff_dialog();
+                }
+                key_code_char!('?') => {
+                    self.tx.send(AppEventType::OpenHelp);
+                }
+                _ => {}
+            },
+            ViewState::LargeObjectDialog(_) => match key {
+                key_code!(KeyCode::Esc) | key_code!(KeyCode::Backspace) => {
+                    self.close_large_object_dialog();
+                }
+                key_code_char!('d') => {
+                    self.close_large_object_dialog();
+                    self.download();
+                }
+                key_code_char!('p') => {
+                    self.close_large_object_dialog();
+                    self.preview_partial();
+                }
+                key_code_char!('?') => {
+                    self.tx.send(AppEventType::OpenHelp);
+                }
+                _ => {}
+            },
             ViewState::CopyDetailDialog(ref mut state) => match key {
                 key_code!(KeyCode::Esc) | key_code!(KeyCode::Backspace) => {
                     self.close_copy_detail_dialog();
@@ -175,7 +323,7 @@ impl ObjectDetailPage {
     }
 
     pub fn render(&mut self, f: &mut Frame, area: Rect) {
-        let chunks = Layout::horizontal(Constraint::from_percentages([50, 50])).split(area);
+        let chunks = split_panes(area, self.list_width_percentage);
 
         let offset = self.list_state.offset;
         let selected = self.list_state.selected;
@@ -215,52 +363,328 @@ impl ObjectDetailPage {
             f.set_cursor(cursor_x, cursor_y);
         }
 
+        if let ViewState::VersionDiffDialog(state) = &self.view_state {
+            let version_diff_dialog = VersionDiffDialog::new(state);
+            f.render_widget(version_diff_dialog, area);
+        }
+
+        if let ViewState::SaveConfirmDialog(state) = &self.view_state {
+            let save_confirm_dialog = SaveConfirmDialog::new(state);
+            f.render_widget(save_confirm_dialog, area);
+        }
+
         if let ViewState::CopyDetailDialog(state) = &self.view_state {
             let copy_detail_dialog = CopyDetailDialog::new(*state, &self.file_detail);
             f.render_widget(copy_detail_dialog, area);
         }
+
+        if let ViewState::RestoreDialog(state) = &self.view_state {
+            let restore_dialog = RestoreDialog::new(*state);
+            f.render_widget(restore_dialog, area);
+        }
+
+        if let ViewState::StorageClassDialog(state) = &self.view_state {
+            let storage_class_dialog = StorageClassDialog::new(state);
+            f.render_widget(storage_class_dialog, area);
+        }
+
+        if let ViewState::LargeObjectDialog(state) = &self.view_state {
+            let large_object_dialog = LargeObjectDialog::new(*state);
+            f.render_widget(large_object_dialog, area);
+        }
     }
 
-    pub fn helps(&self) -> Vec<String> {
-        let helps: &[(&[&str], &str)] = match self.view_state {
+    fn help_groups(&self) -> &'static [HelpGroup<'static>] {
+        match self.view_state {
             ViewState::Default => match self.tab {
                 Tab::Detail => &[
-                    (&["Esc", "Ctrl-c"], "Quit app"),
-                    (&["h/l"], "Select tabs"),
-                    (&["Backspace"], "Close detail panel"),
-                    (&["j/k"], "Scroll forward/backward"),
-                    (&["r"], "Open copy dialog"),
-                    (&["s"], "Download object"),
-                    (&["S"], "Download object as"),
-                    (&["p"], "Preview object"),
-                    (&["x"], "Open management console in browser"),
+                    (
+                        "General",
+                        &[(
+                            &["Esc", "Ctrl-c"],
+                            "Quit app",
+                            Some(key_event!(KeyCode::Esc)),
+                        )],
+                    ),
+                    (
+                        "Navigation",
+                        &[
+                            (
+                                &["h/l"],
+                                "Select tabs",
+                                Some(key_event!(KeyCode::Char('l'))),
+                            ),
+                            (
+                                &["Backspace"],
+                                "Close detail panel",
+                                Some(key_event!(KeyCode::Backspace)),
+                            ),
+                            (&["j/k"], "Scroll forward/backward", None),
+                            (
+                                &["<"],
+                                "Shrink object list",
+                                Some(key_event!(KeyCode::Char('<'))),
+                            ),
+                            (
+                                &[">"],
+                                "Widen object list",
+                                Some(key_event!(KeyCode::Char('>'))),
+                            ),
+                        ],
+                    ),
+                    (
+                        "Preview",
+                        &[(
+                            &["p"],
+                            "Preview object",
+                            Some(key_event!(KeyCode::Char('p'))),
+                        )],
+                    ),
+                    (
+                        "Copy",
+                        &[
+                            (
+                                &["r"],
+                                "Open copy dialog",
+                                Some(key_event!(KeyCode::Char('r'))),
+                            ),
+                            (
+                                &["y"],
+                                "Copy object key",
+                                Some(key_event!(KeyCode::Char('y'))),
+                            ),
+                            (
+                                &["Y"],
+                                "Copy bucket name",
+                                Some(key_event!(KeyCode::Char('Y'))),
+                            ),
+                        ],
+                    ),
+                    (
+                        "Operations",
+                        &[
+                            (
+                                &["s"],
+                                "Download object",
+                                Some(key_event!(KeyCode::Char('s'))),
+                            ),
+                            (
+                                &["S"],
+                                "Download object as",
+                                Some(key_event!(KeyCode::Char('S'))),
+                            ),
+                            (
+                                &["R"],
+                                "Restore archived object",
+                                Some(key_event!(KeyCode::Char('R'))),
+                            ),
+                            (
+                                &["C"],
+                                "Change storage class",
+                                Some(key_event!(KeyCode::Char('C'))),
+                            ),
+                            (
+                                &["Ctrl-r"],
+                                "Refresh object detail",
+                                Some(key_event!(KeyCode::Char('r'), Ctrl)),
+                            ),
+                            (
+                                &["x"],
+                                "Open management console in browser",
+                                Some(key_event!(KeyCode::Char('x'))),
+                            ),
+                        ],
+                    ),
                 ],
                 Tab::Version => &[
-                    (&["Esc", "Ctrl-c"], "Quit app"),
-                    (&["h/l"], "Select tabs"),
-                    (&["j/k"], "Select version"),
-                    (&["g/G"], "Go to top/bottom"),
-                    (&["Backspace"], "Close detail panel"),
-                    (&["r"], "Open copy dialog"),
-                    (&["s"], "Download object"),
-                    (&["S"], "Download object as"),
-                    (&["p"], "Preview object"),
-                    (&["x"], "Open management console in browser"),
+                    (
+                        "General",
+                        &[(
+                            &["Esc", "Ctrl-c"],
+                            "Quit app",
+                            Some(key_event!(KeyCode::Esc)),
+                        )],
+                    ),
+                    (
+                        "Navigation",
+                        &[
+                            (
+                                &["h/l"],
+                                "Select tabs",
+                                Some(key_event!(KeyCode::Char('l'))),
+                            ),
+                            (&["j/k"], "Select version", None),
+                            (&["g/G"], "Go to top/bottom", None),
+                            (
+                                &["Backspace"],
+                                "Close detail panel",
+                                Some(key_event!(KeyCode::Backspace)),
+                            ),
+                            (
+                                &["<"],
+                                "Shrink object list",
+                                Some(key_event!(KeyCode::Char('<'))),
+                            ),
+                            (
+                                &[">"],
+                                "Widen object list",
+                                Some(key_event!(KeyCode::Char('>'))),
+                            ),
+                        ],
+                    ),
+                    (
+                        "Preview",
+                        &[(
+                            &["p"],
+                            "Preview object",
+                            Some(key_event!(KeyCode::Char('p'))),
+                        )],
+                    ),
+                    (
+                        "Copy",
+                        &[
+                            (
+                                &["r"],
+                                "Open copy dialog",
+                                Some(key_event!(KeyCode::Char('r'))),
+                            ),
+                            (
+                                &["y"],
+                                "Copy object key",
+                                Some(key_event!(KeyCode::Char('y'))),
+                            ),
+                            (
+                                &["Y"],
+                                "Copy bucket name",
+                                Some(key_event!(KeyCode::Char('Y'))),
+                            ),
+                        ],
+                    ),
+                    (
+                        "Operations",
+                        &[
+                            (&["m"], "Mark version to compare", None),
+                            (&["d"], "Compare marked version with selected", None),
+                            (
+                                &["s"],
+                                "Download object",
+                                Some(key_event!(KeyCode::Char('s'))),
+                            ),
+                            (
+                                &["S"],
+                                "Download object as",
+                                Some(key_event!(KeyCode::Char('S'))),
+                            ),
+                            (
+                                &["R"],
+                                "Restore archived object",
+                                Some(key_event!(KeyCode::Char('R'))),
+                            ),
+                            (
+                                &["C"],
+                                "Change storage class",
+                                Some(key_event!(KeyCode::Char('C'))),
+                            ),
+                            (
+                                &["Ctrl-r"],
+                                "Refresh object detail",
+                                Some(key_event!(KeyCode::Char('r'), Ctrl)),
+                            ),
+                            (
+                                &["x"],
+                                "Open management console in browser",
+                                Some(key_event!(KeyCode::Char('x'))),
+                            ),
+                        ],
+                    ),
                 ],
             },
             ViewState::SaveDialog(_) => &[
-                (&["Ctrl-c"], "Quit app"),
-                (&["Esc"], "Close save dialog"),
-                (&["Enter"], "Download object"),
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                ("Navigation", &[(&["Esc"], "Close save dialog", None)]),
+                ("Operations", &[(&["Enter"], "Download object", None)]),
+            ],
+            ViewState::SaveConfirmDialog(_) => &[
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc", "Backspace"], "Close dialog", None),
+                        (&["j/k"], "Select item", None),
+                    ],
+                ),
+                ("Operations", &[(&["Enter"], "Apply selected action", None)]),
+            ],
+            ViewState::VersionDiffDialog(_) => &[
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[(&["Esc", "Backspace"], "Close dialog", None)],
+                ),
             ],
             ViewState::CopyDetailDialog(_) => &[
-                (&["Ctrl-c"], "Quit app"),
-                (&["Esc", "Backspace"], "Close copy dialog"),
-                (&["j/k"], "Select item"),
-                (&["Enter"], "Copy selected value to clipboard"),
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc", "Backspace"], "Close copy dialog", None),
+                        (&["j/k"], "Select item", None),
+                    ],
+                ),
+                (
+                    "Copy",
+                    &[(&["Enter"], "Copy selected value to clipboard", None)],
+                ),
             ],
-        };
-        build_helps(helps)
+            ViewState::RestoreDialog(_) => &[
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc", "Backspace"], "Close restore dialog", None),
+                        (&["j/k"], "Select tier", None),
+                    ],
+                ),
+                (
+                    "Operations",
+                    &[
+                        (&["h/l"], "Decrease/increase days", None),
+                        (&["Enter"], "Restore object", None),
+                    ],
+                ),
+            ],
+            ViewState::StorageClassDialog(_) => &[
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc", "Backspace"], "Close storage class dialog", None),
+                        (&["j/k"], "Select storage class", None),
+                    ],
+                ),
+                ("Operations", &[(&["Enter"], "Change storage class", None)]),
+            ],
+            ViewState::LargeObjectDialog(_) => &[
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[(&["Esc", "Backspace"], "Close dialog", None)],
+                ),
+                (
+                    "Preview",
+                    &[(&["p"], "Preview the first part of object", None)],
+                ),
+                ("Operations", &[(&["d"], "Download object", None)]),
+            ],
+        }
+    }
+
+    pub fn helps(&self) -> Vec<(String, Vec<String>)> {
+        build_helps(self.help_groups())
+    }
+
+    pub fn commands(&self) -> Vec<PaletteCommand> {
+        build_commands(self.help_groups())
     }
 
     pub fn short_helps(&self) -> Vec<(String, usize)> {
@@ -290,12 +714,38 @@ impl ObjectDetailPage {
                 (&["Enter"], "Download", 1),
                 (&["?"], "Help", 0),
             ],
+            ViewState::SaveConfirmDialog(_) => &[
+                (&["Esc"], "Close", 2),
+                (&["j/k"], "Select", 3),
+                (&["Enter"], "Apply", 1),
+                (&["?"], "Help", 0),
+            ],
+            ViewState::VersionDiffDialog(_) => &[(&["Esc"], "Close", 0), (&["?"], "Help", 0)],
             ViewState::CopyDetailDialog(_) => &[
                 (&["Esc"], "Close", 2),
                 (&["j/k"], "Select", 3),
                 (&["Enter"], "Copy", 1),
                 (&["?"], "Help", 0),
             ],
+            ViewState::RestoreDialog(_) => &[
+                (&["Esc"], "Close", 2),
+                (&["j/k"], "Select", 3),
+                (&["h/l"], "Days", 4),
+                (&["Enter"], "Restore", 1),
+                (&["?"], "Help", 0),
+            ],
+            ViewState::StorageClassDialog(_) => &[
+                (&["Esc"], "Close", 2),
+                (&["j/k"], "Select", 3),
+                (&["Enter"], "Change", 1),
+                (&["?"], "Help", 0),
+            ],
+            ViewState::LargeObjectDialog(_) => &[
+                (&["Esc"], "Close", 2),
+                (&["d"], "Download", 1),
+                (&["p"], "Preview partially", 3),
+                (&["?"], "Help", 0),
+            ],
         };
 
         build_short_helps(helps)
@@ -303,11 +753,38 @@ impl ObjectDetailPage {
 }
 
 impl ObjectDetailPage {
+    pub fn file_detail(&self) -> &FileDetail {
+        &self.file_detail
+    }
+
+    /// Replaces the detail and versions with the reloaded ones, keeping the selected and marked versions if they still exist.
+    pub fn update_detail(&mut self, file_detail: FileDetail, file_versions: Vec<FileVersion>) {
+        let version_id_at = |i: usize| self.file_versions.get(i).map(|v| v.version_id.as_str());
+        let index_of = |id: Option<&str>| {
+            id.and_then(|id| file_versions.iter().position(|v| v.version_id == id))
+        };
+        let selected = index_of(version_id_at(self.version_tab_state.selected));
+        let marked = index_of(self.version_tab_state.marked.and_then(version_id_at));
+
+        let lines = build_detail_content_lines(&file_detail, self.owner.as_ref());
+        self.detail_tab_state
+            .scroll_lines_state
+            .replace_lines(lines);
+        self.version_tab_state
+            .update(&file_versions, selected.unwrap_or_default(), marked);
+
+        self.file_detail = file_detail;
+        self.file_versions = file_versions;
+    }
+
     fn toggle_tab(&mut self) {
         self.tab = self.tab.next();
     }
 
     fn open_save_dialog(&mut self) {
+        if self.warn_if_requires_restore() {
+            return;
+        }
         self.view_state = ViewState::SaveDialog(InputDialogState::default());
     }
 
@@ -315,6 +792,59 @@ impl ObjectDetailPage {
         self.view_state = ViewState::Default;
     }
 
+    pub fn open_save_confirm_dialog(&mut self, input: String, renamed_input: String) {
+        self.view_state =
+            ViewState::SaveConfirmDialog(SaveConfirmDialogState::new(input, renamed_input));
+    }
+
+    fn apply_save_confirm(&mut self, state: SaveConfirmDialogState) {
+        match state.selected() {
+            SaveConfirmAction::Overwrite => {
+                self.send_download_as(state.input().into(), true);
+            }
+            SaveConfirmAction::Rename => {
+                self.send_download_as(state.renamed_input().into(), false);
+            }
+            SaveConfirmAction::Cancel => {
+                self.close_save_dialog();
+            }
+        }
+    }
+
+    pub fn open_version_diff_dialog(&mut self, state: VersionDiffDialogState) {
+        self.view_state = ViewState::VersionDiffDialog(Box::new(state));
+    }
+
+    fn close_version_diff_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn load_version_diff(&self) {
+        let selected = self.version_tab_state.selected;
+        let marked = match self.version_tab_state.marked {
+            Some(marked) if marked != selected => marked,
+            _ => {
+                let msg = "Mark another version with 'm' to compare".to_string();
+                self.tx.send(AppEventType::NotifyWarn(msg));
+                return;
+            }
+        };
+        // versions are sorted from newest to oldest
+        let (old, new) = if marked > selected {
+            (marked, selected)
+        } else {
+            (selected, marked)
+        };
+        let file_detail = self.file_detail.clone();
+        let old_version = self.file_versions[old].clone();
+        let new_version = self.file_versions[new].clone();
+        self.tx.send(AppEventType::LoadVersionDiff(
+            file_detail,
+            old_version,
+            new_version,
+        ));
+    }
+
     fn open_copy_detail_dialog(&mut self) {
         self.view_state = ViewState::CopyDetailDialog(CopyDetailDialogState::default());
     }
@@ -324,6 +854,9 @@ impl ObjectDetailPage {
     }
 
     fn download(&self) {
+        if self.warn_if_requires_restore() {
+            return;
+        }
         let file_detail = self.file_detail.clone();
         let version_id = self.current_selected_version_id();
         self.tx
@@ -336,22 +869,167 @@ impl ObjectDetailPage {
             return;
         }
 
+        self.send_download_as(input, false);
+    }
+
+    fn send_download_as(&self, input: String, overwrite: bool) {
         let file_detail = self.file_detail.clone();
         let version_id = self.current_selected_version_id();
         self.tx.send(AppEventType::DetailDownloadObjectAs(
             file_detail,
             input,
             version_id,
+            overwrite,
         ));
     }
 
     fn preview(&self) {
+        if self.warn_if_requires_restore() {
+            return;
+        }
         let file_detail = self.file_detail.clone();
         let version_id = self.current_selected_version_id();
         self.tx
             .send(AppEventType::OpenPreview(file_detail, version_id));
     }
 
+    fn preview_partial(&self) {
+        let file_detail = self.file_detail.clone();
+        let version_id = self.current_selected_version_id();
+        self.tx
+            .send(AppEventType::OpenPartialPreview(file_detail, version_id));
+    }
+
+    pub fn open_large_object_dialog(&mut self, max_size_byte: usize) {
+        let state = LargeObjectDialogState::new(self.file_detail.size_byte, max_size_byte);
+        self.view_state = ViewState::LargeObjectDialog(state);
+    }
+
+    fn close_large_object_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn resize_list(&mut self, widen: bool) {
+        let percentage = if widen {
+            self.list_width_percentage + LIST_WIDTH_PERCENTAGE_STEP
+        } else {
+            self.list_width_percentage
+                .saturating_sub(LIST_WIDTH_PERCENTAGE_STEP)
+        };
+        let percentage = percentage.clamp(MIN_LIST_WIDTH_PERCENTAGE, MAX_LIST_WIDTH_PERCENTAGE);
+        if percentage != self.list_width_percentage {
+            self.list_width_percentage = percentage;
+            self.tx
+                .send(AppEventType::SaveListWidthPercentage(percentage));
+        }
+    }
+
+    fn open_restore_dialog(&mut self) {
+        if !self.file_detail.is_archived() {
+            let msg = "Object is not archived".to_string();
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        if self.file_detail.restore_status == Some(RestoreStatus::InProgress) {
+            let msg = "Restore is already in progress".to_string();
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        self.view_state = ViewState::RestoreDialog(RestoreDialogState::default());
+    }
+
+    fn close_restore_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn restore(&mut self, state: RestoreDialogState) {
+        let file_detail = self.file_detail.clone();
+        let version_id = self.current_selected_version_id();
+        self.tx.send(AppEventType::RestoreObject(
+            file_detail,
+            version_id,
+            state.tier(),
+            state.days(),
+        ));
+        self.close_restore_dialog();
+    }
+
+    pub fn set_restore_in_progress(&mut self) {
+        if self.file_detail.restore_status.is_none() {
+            self.file_detail.restore_status = Some(RestoreStatus::InProgress);
+            self.detail_tab_state = DetailTabState::new(&self.file_detail, self.owner.as_ref());
+        }
+    }
+
+    // the storage class is changed for the latest version, whichever version is selected
+    fn open_storage_class_dialog(&mut self) {
+        if self.file_detail.size_byte > MAX_COPY_SIZE_BYTE {
+            let msg = "Storage class of objects larger than 5 GiB cannot be changed".to_string();
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        // the copy cannot be encrypted with the customer key, which is not known
+        if self.file_detail.encryption.as_deref() == Some("SSE-C") {
+            let msg = "Storage class of objects encrypted with SSE-C cannot be changed".to_string();
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        if self.file_detail.requires_restore() {
+            let msg = ARCHIVED_OBJECT_MSG.to_string();
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        let state = StorageClassDialogState::new(self.file_detail.storage_class_name());
+        self.view_state = ViewState::StorageClassDialog(state);
+    }
+
+    fn close_storage_class_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn transition_storage_class(&mut self, state: StorageClassDialogState) {
+        if state.is_current_selected() {
+            let msg = format!("Object is already in {}", state.current());
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        let file_detail = self.file_detail.clone();
+        self.tx.send(AppEventType::TransitionStorageClass(
+            file_detail,
+            state.selected(),
+        ));
+        self.close_storage_class_dialog();
+    }
+
+    // the storage class is only known for the latest version
+    fn warn_if_requires_restore(&self) -> bool {
+        let latest_selected = match self.tab {
+            Tab::Detail => true,
+            Tab::Version => self
+                .file_versions
+                .get(self.version_tab_state.selected)
+                .is_none_or(|v| v.is_latest),
+        };
+        let requires_restore = latest_selected && self.file_detail.requires_restore();
+        if requires_restore {
+            let msg = ARCHIVED_OBJECT_MSG.to_string();
+            self.tx.send(AppEventType::NotifyWarn(msg));
+        }
+        requires_restore
+    }
+
+    fn copy_key(&self) {
+        let key = self.file_detail.key.clone();
+        self.tx
+            .send(AppEventType::CopyToClipboard("key".into(), key));
+    }
+
+    fn copy_bucket_name(&self) {
+        let bucket = self.bucket_name.clone();
+        self.tx
+            .send(AppEventType::CopyToClipboard("bucket name".into(), bucket));
+    }
+
     fn open_management_console(&self) {
         let file_name = self.file_detail.name.clone();
         self.tx
@@ -369,13 +1047,24 @@ impl ObjectDetailPage {
     }
 }
 
-fn build_list_items_from_object_items(
+pub fn split_panes(area: Rect, list_width_percentage: u16) -> [Rect; 2] {
+    let list_width = (area.width as u32 * list_width_percentage as u32 / 100) as u16;
+    // keep both panes usable if there is enough room
+    let list_width = if area.width >= MIN_PANE_WIDTH * 2 {
+        list_width.clamp(MIN_PANE_WIDTH, area.width - MIN_PANE_WIDTH)
+    } else {
+        list_width
+    };
+    Layout::horizontal([Constraint::Length(list_width), Constraint::Min(0)]).areas(area)
+}
+
+pub fn build_list_items_from_object_items(
     current_items: &[ObjectItem],
     offset: usize,
     selected: usize,
     area: Rect,
-) -> Vec<ListItem> {
-    let show_item_count = (area.height as usize) - 2 /* border */;
+) -> Vec<ListItem<'_>> {
+    let show_item_count = (area.height as usize).saturating_sub(2 /* border */);
     current_items
         .iter()
         .skip(offset)
@@ -391,7 +1080,7 @@ fn build_list_item_from_object_item(
     offset: usize,
     selected: usize,
     area: Rect,
-) -> ListItem {
+) -> ListItem<'_> {
     let content = match item {
         ObjectItem::Dir { name, .. } => {
             let content = format_dir_item(name, area.width);
@@ -416,14 +1105,18 @@ fn build_list_item_from_object_item(
 }
 
 fn format_dir_item(name: &str, width: u16) -> String {
-    let name_w: usize = (width as usize) - 2 /* spaces */ - 2 /* border */;
-    let name = format!("{}/", name);
-    format!(" {:<name_w$} ", name, name_w = name_w)
+    let name_w: usize = (width as usize).saturating_sub(2 /* spaces */ + 2 /* border */);
+    let name = format!(
+        "{}/",
+        truncate_with_ellipsis(name, name_w.saturating_sub(1))
+    );
+    format!(" {} ", pad_to_width(&name, name_w))
 }
 
 fn format_file_item(name: &str, width: u16) -> String {
-    let name_w: usize = (width as usize) - 2 /* spaces */ - 4 /* border */;
-    format!(" {:<name_w$} ", name, name_w = name_w)
+    let name_w: usize = (width as usize).saturating_sub(2 /* spaces */ + 4 /* border */);
+    let name = truncate_with_ellipsis(name, name_w);
+    format!(" {} ", pad_to_width(&name, name_w))
 }
 
 fn build_tabs(tab: Tab) -> Tabs<'static> {
@@ -438,7 +1131,21 @@ fn build_tabs(tab: Tab) -> Tabs<'static> {
         .block(Block::default().borders(Borders::BOTTOM))
 }
 
-fn build_detail_content_lines(detail: &FileDetail) -> Vec<Line<'static>> {
+fn build_detail_content_lines(
+    detail: &FileDetail,
+    owner: Option<&ObjectOwner>,
+) -> Vec<Line<'static>> {
+    let restore = match &detail.restore_status {
+        Some(RestoreStatus::InProgress) => "In progress".to_string(),
+        Some(RestoreStatus::Restored {
+            expiry_date: Some(expiry_date),
+        }) => format!("Restored (expires {})", expiry_date),
+        Some(RestoreStatus::Restored { expiry_date: None }) => "Restored".to_string(),
+        None if detail.is_archived() => "Not restored".to_string(),
+        None => String::new(),
+    };
+    // not shown unless the owner is fetched
+    let owner = owner.map(ObjectOwner::display).unwrap_or_default();
     let details = [
         ("Name:", &detail.name),
         ("Size:", &format_size_byte(detail.size_byte)),
@@ -446,6 +1153,8 @@ fn build_detail_content_lines(detail: &FileDetail) -> Vec<Line<'static>> {
         ("ETag:", &detail.e_tag),
         ("Content-Type:", &detail.content_type),
         ("Storage class:", &detail.storage_class),
+        ("Owner:", &owner),
+        ("Restore:", &restore),
     ]
     .iter()
     .filter_map(|(label, value)| {
@@ -470,8 +1179,8 @@ struct DetailTabState {
 }
 
 impl DetailTabState {
-    fn new(file_detail: &FileDetail) -> Self {
-        let scroll_lines = build_detail_content_lines(file_detail);
+    fn new(file_detail: &FileDetail, owner: Option<&ObjectOwner>) -> Self {
+        let scroll_lines = build_detail_content_lines(file_detail, owner);
         let scroll_lines_state =
             ScrollLinesState::new(scroll_lines, ScrollLinesOptions::new(false, true));
         Self { scroll_lines_state }
@@ -519,6 +1228,7 @@ fn build_help_lines(versions: &[FileVersion]) -> Vec<Vec<Line<'static>>> {
 struct VersionTabState {
     help_lines: Vec<Vec<Line<'static>>>,
     selected: usize,
+    marked: Option<usize>,
     offset: usize,
     height: usize,
 }
@@ -570,6 +1280,21 @@ impl VersionTabState {
         self.offset = 0;
     }
 
+    fn update(&mut self, versions: &[FileVersion], selected: usize, marked: Option<usize>) {
+        self.help_lines = build_help_lines(versions);
+        self.selected = selected;
+        self.offset = self.offset.min(selected);
+        self.marked = marked;
+    }
+
+    fn toggle_mark(&mut self) {
+        if self.marked == Some(self.selected) {
+            self.marked = None;
+        } else {
+            self.marked = Some(self.selected);
+        }
+    }
+
     fn select_last(&mut self) {
         self.selected = self.help_lines.len() - 1;
 
@@ -636,6 +1361,9 @@ impl StatefulWidget for VersionTab {
             if i == state.selected {
                 let bar = Bar::default().color(SELECTED_COLOR);
                 bar.render(chunks[0], buf);
+            } else if state.marked == Some(i) {
+                let bar = Bar::default().color(MARKED_COLOR);
+                bar.render(chunks[0], buf);
             }
             version_paragraph.render(chunks[1], buf);
         }
@@ -658,11 +1386,12 @@ fn flatten_with_empty_lines(line_groups: Vec<Vec<Line>>) -> Vec<Line> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{event, set_cells};
+    use crate::{event, object::StorageClass, set_cells};
 
     use super::*;
-    use chrono::{DateTime, Local, NaiveDateTime};
+    use chrono::{DateTime, Local, NaiveDateTime, SecondsFormat};
     use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+    use rstest::rstest;
 
     #[test]
     fn test_render_detail_tab() -> std::io::Result<()> {
@@ -675,8 +1404,10 @@ mod tests {
             let mut page = ObjectDetailPage::new(
                 file_detail,
                 file_versions,
+                "bucket-1".to_string(),
                 items,
                 ScrollListState::new(items_len),
+                50,
                 tx,
             );
             let area = Rect::new(0, 0, 60, 20);
@@ -739,8 +1470,10 @@ mod tests {
             let mut page = ObjectDetailPage::new(
                 file_detail,
                 file_versions,
+                "bucket-1".to_string(),
                 items,
                 ScrollListState::new(items_len),
+                50,
                 tx,
             );
             page.toggle_tab();
@@ -804,8 +1537,10 @@ mod tests {
             let mut page = ObjectDetailPage::new(
                 file_detail,
                 file_versions,
+                "bucket-1".to_string(),
                 items,
                 ScrollListState::new(items_len),
+                50,
                 tx,
             );
             page.open_save_dialog();
@@ -859,7 +1594,7 @@ mod tests {
     #[test]
     fn test_render_copy_detail_dialog_detail_tab() -> std::io::Result<()> {
         let (tx, _) = event::new();
-        let mut terminal = setup_terminal()?;
+        let mut terminal = Terminal::new(TestBackend::new(60, 28))?;
 
         terminal.draw(|f| {
             let (items, file_detail, file_versions) = fixtures();
@@ -867,59 +1602,168 @@ mod tests {
             let mut page = ObjectDetailPage::new(
                 file_detail,
                 file_versions,
+                "bucket-1".to_string(),
                 items,
                 ScrollListState::new(items_len),
+                50,
                 tx,
             );
             page.open_copy_detail_dialog();
-            let area = Rect::new(0, 0, 60, 20);
+            let area = Rect::new(0, 0, 60, 28);
             page.render(f, area);
         })?;
 
+        // the epoch and UTC values depend on the local time zone of the fixture
+        let value_line = |value: String| format!("│ │   {:<50} │ │", value);
+        let last_modified = parse_datetime("2024-01-02 13:01:02");
         #[rustfmt::skip]
         let mut expected = Buffer::with_lines([
-            "┌───────────────────── 1 / 3 ┐┌────────────────────────────┐",
-            "│  file1                     ││ Detail │ Version           │",
-            "│  file2                     ││────────────────────────────│",
-            "│  file3                     ││ Name:                      │",
-            "│ ╭Copy──────────────────────────────────────────────────╮ │",
-            "│ │ Key:                                                 │ │",
-            "│ │   file1                                              │ │",
-            "│ │ S3 URI:                                              │ │",
-            "│ │   s3://bucket-1/file1                                │ │",
-            "│ │ ARN:                                                 │ │",
-            "│ │   arn:aws:s3:::bucket-1/file1                        │ │",
-            "│ │ Object URL:                                          │ │",
-            "│ │   https://bucket-1.s3.ap-northeast-1.amazonaws.com/f │ │",
-            "│ │ ETag:                                                │ │",
-            "│ │   bef684de-a260-48a4-8178-8a535ecccadb               │ │",
-            "│ ╰──────────────────────────────────────────────────────╯ │",
-            "│                            ││ Content-Type:              │",
-            "│                            ││  text/plain                │",
-            "│                            ││                            │",
-            "└────────────────────────────┘└────────────────────────────┘",
+            "┌───────────────────── 1 / 3 ┐┌────────────────────────────┐".to_string(),
+            "│  file1                     ││ Detail │ Version           │".to_string(),
+            "│ ╭Copy──────────────────────────────────────────────────╮ │".to_string(),
+            "│ │ Key:                                                 │ │".to_string(),
+            "│ │   file1                                              │ │".to_string(),
+            "│ │ S3 URI:                                              │ │".to_string(),
+            "│ │   s3://bucket-1/file1                                │ │".to_string(),
+            "│ │ ARN:                                                 │ │".to_string(),
+            "│ │   arn:aws:s3:::bucket-1/file1                        │ │".to_string(),
+            "│ │ Object URL:                                          │ │".to_string(),
+            "│ │   https://bucket-1.s3.ap-northeast-1.amazonaws.com/f │ │".to_string(),
+            "│ │ ETag:                                                │ │".to_string(),
+            "│ │   bef684de-a260-48a4-8178-8a535ecccadb               │ │".to_string(),
+            "│ │ Last modified:                                       │ │".to_string(),
+            "│ │   2024-01-02 13:01:02                                │ │".to_string(),
+            "│ │ Last modified (RFC 3339, UTC):                       │ │".to_string(),
+            value_line(last_modified.to_utc().to_rfc3339_opts(SecondsFormat::Secs, true)),
+            "│ │ Last modified (epoch seconds):                       │ │".to_string(),
+            value_line(last_modified.timestamp().to_string()),
+            "│ │ Last modified (epoch milliseconds):                  │ │".to_string(),
+            value_line(last_modified.timestamp_millis().to_string()),
+            "│ │ Detail (JSON):                                       │ │".to_string(),
+            "│ │   All fields as JSON                                 │ │".to_string(),
+            "│ │ Detail (YAML):                                       │ │".to_string(),
+            "│ │   All fields as YAML                                 │ │".to_string(),
+            "│ ╰──────────────────────────────────────────────────────╯ │".to_string(),
+            "│                            ││                            │".to_string(),
+            "└────────────────────────────┘└────────────────────────────┘".to_string(),
         ]);
         set_cells! { expected =>
             // selected item
             (2..28, [1]) => bg: Color::DarkGray, fg: Color::Black,
             // "Detail" is selected
             (32..38, [1]) => fg: Color::Cyan, modifier: Modifier::BOLD,
-            // "Name" label
-            (32..37, [3]) => modifier: Modifier::BOLD,
-            // "Content-Type" label
-            (32..45, [16]) => modifier: Modifier::BOLD,
             // "Key" label
-            (4..8, [5]) => modifier: Modifier::BOLD,
+            (4..8, [3]) => modifier: Modifier::BOLD,
             // "S3 URI" label
-            (4..11, [7]) => modifier: Modifier::BOLD,
+            (4..11, [5]) => modifier: Modifier::BOLD,
             // "ARN" label
-            (4..8, [9]) => modifier: Modifier::BOLD,
+            (4..8, [7]) => modifier: Modifier::BOLD,
             // "Object URL" label
-            (4..15, [11]) => modifier: Modifier::BOLD,
+            (4..15, [9]) => modifier: Modifier::BOLD,
             // "ETag" label
-            (4..9, [13]) => modifier: Modifier::BOLD,
+            (4..9, [11]) => modifier: Modifier::BOLD,
+            // "Last modified" label
+            (4..18, [13]) => modifier: Modifier::BOLD,
+            // "Last modified (RFC 3339, UTC)" label
+            (4..34, [15]) => modifier: Modifier::BOLD,
+            // "Last modified (epoch seconds)" label
+            (4..34, [17]) => modifier: Modifier::BOLD,
+            // "Last modified (epoch milliseconds)" label
+            (4..39, [19]) => modifier: Modifier::BOLD,
+            // "Detail (JSON)" label
+            (4..18, [21]) => modifier: Modifier::BOLD,
+            // "Detail (YAML)" label
+            (4..18, [23]) => modifier: Modifier::BOLD,
             // "Key" is selected
-            (4..56, [5, 6]) => fg: Color::Cyan,
+            (4..56, [3, 4]) => fg: Color::Cyan,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_narrow_list() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = Terminal::new(TestBackend::new(80, 6))?;
+
+        terminal.draw(|f| {
+            let (items, file_detail, file_versions) = fixtures();
+            let items_len = items.len();
+            let mut page = ObjectDetailPage::new(
+                file_detail,
+                file_versions,
+                "bucket-1".to_string(),
+                items,
+                ScrollListState::new(items_len),
+                30,
+                tx,
+            );
+            let area = Rect::new(0, 0, 80, 6);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌─────────────── 1 / 3 ┐┌──────────────────────────────────────────────────────┐",
+            "│  file1               ││ Detail │ Version                                     │",
+            "│  file2               ││──────────────────────────────────────────────────────│",
+            "│  file3               ││ Name:                                                │",
+            "│                      ││  file1                                               │",
+            "└──────────────────────┘└──────────────────────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // selected item
+            (2..22, [1]) => bg: Color::DarkGray, fg: Color::Black,
+            // "Detail" is selected
+            (26..32, [1]) => fg: Color::Cyan, modifier: Modifier::BOLD,
+            // "Name" label
+            (26..31, [3]) => modifier: Modifier::BOLD,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_wide_list() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = Terminal::new(TestBackend::new(80, 6))?;
+
+        terminal.draw(|f| {
+            let (items, file_detail, file_versions) = fixtures();
+            let items_len = items.len();
+            let mut page = ObjectDetailPage::new(
+                file_detail,
+                file_versions,
+                "bucket-1".to_string(),
+                items,
+                ScrollListState::new(items_len),
+                70,
+                tx,
+            );
+            let area = Rect::new(0, 0, 80, 6);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌─────────────────────────────────────────────── 1 / 3 ┐┌──────────────────────┐",
+            "│  file1                                               ││ Detail │ Version     │",
+            "│  file2                                               ││──────────────────────│",
+            "│  file3                                               ││ Name:                │",
+            "│                                                      ││  file1               │",
+            "└──────────────────────────────────────────────────────┘└──────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // selected item
+            (2..54, [1]) => bg: Color::DarkGray, fg: Color::Black,
+            // "Detail" is selected
+            (58..64, [1]) => fg: Color::Cyan, modifier: Modifier::BOLD,
+            // "Name" label
+            (58..63, [3]) => modifier: Modifier::BOLD,
         }
 
         terminal.backend().assert_buffer(&expected);
@@ -927,6 +1771,151 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[case(80, 50, 40)]
+    #[case(80, 20, 20)]
+    #[case(60, 20, 20)] // clamped to the min pane width
+    #[case(60, 80, 40)] // clamped to leave the min pane width for the detail
+    #[case(30, 80, 24)] // too narrow to keep the min widths
+    fn test_split_panes(#[case] width: u16, #[case] percentage: u16, #[case] expected: u16) {
+        let [list, detail] = split_panes(Rect::new(0, 0, width, 10), percentage);
+        assert_eq!(list.width, expected);
+        assert_eq!(detail.width, width - expected);
+    }
+
+    #[test]
+    fn test_render_small_area() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = Terminal::new(TestBackend::new(3, 3))?;
+
+        // should not panic
+        terminal.draw(|f| {
+            let (items, file_detail, file_versions) = fixtures();
+            let items_len = items.len();
+            let mut page = ObjectDetailPage::new(
+                file_detail,
+                file_versions,
+                "bucket-1".to_string(),
+                items,
+                ScrollListState::new(items_len),
+                50,
+                tx,
+            );
+            let area = Rect::new(0, 0, 3, 3);
+            page.render(f, area);
+            page.toggle_tab();
+            page.render(f, area);
+        })?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_detail_keeps_selected_version() {
+        let (tx, _) = event::new();
+        let (items, file_detail, file_versions) = fixtures();
+        let items_len = items.len();
+        let mut page = ObjectDetailPage::new(
+            file_detail.clone(),
+            file_versions.clone(),
+            "bucket-1".to_string(),
+            items,
+            ScrollListState::new(items_len),
+            50,
+            tx,
+        );
+        page.toggle_tab();
+        page.version_tab_state.select_next();
+        page.version_tab_state.toggle_mark();
+
+        // a new version has been uploaded
+        let new_version = FileVersion {
+            version_id: "new-version".to_string(),
+            size_byte: 2048,
+            last_modified: parse_datetime("2024-01-03 10:00:00"),
+            is_latest: true,
+        };
+        let versions = std::iter::once(new_version)
+            .chain(file_versions.into_iter().map(|v| FileVersion {
+                is_latest: false,
+                ..v
+            }))
+            .collect();
+        let file_detail = FileDetail {
+            size_byte: 2048,
+            ..file_detail
+        };
+        page.update_detail(file_detail, versions);
+
+        assert_eq!(page.file_detail().size_byte, 2048);
+        assert_eq!(page.version_tab_state.selected, 2);
+        assert_eq!(page.version_tab_state.marked, Some(2));
+        assert_eq!(
+            page.current_selected_version_id().as_deref(),
+            Some("1c5d3bcc-2bb3-4cd5-875f-a95a6ae53f65")
+        );
+    }
+
+    #[test]
+    fn test_storage_class_dialog() {
+        let (tx, rx) = event::new_without_terminal();
+        let (items, file_detail, file_versions) = fixtures();
+        let items_len = items.len();
+        let mut page = ObjectDetailPage::new(
+            file_detail,
+            file_versions,
+            "bucket-1".to_string(),
+            items,
+            ScrollListState::new(items_len),
+            50,
+            tx,
+        );
+        page.handle_key(KeyEvent::from(KeyCode::Char('C')));
+
+        // the current storage class is refused
+        page.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(matches!(page.view_state, ViewState::StorageClassDialog(_)));
+        assert!(
+            matches!(rx.recv(), AppEventType::NotifyWarn(msg) if msg == "Object is already in STANDARD")
+        );
+
+        page.handle_key(KeyEvent::from(KeyCode::Char('j')));
+        page.handle_key(KeyEvent::from(KeyCode::Char('j')));
+        page.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(matches!(page.view_state, ViewState::Default));
+        assert!(matches!(
+            rx.recv(),
+            AppEventType::TransitionStorageClass(detail, StorageClass::StandardIa) if detail.name == "file1"
+        ));
+    }
+
+    #[test]
+    fn test_storage_class_dialog_of_sse_c_object() {
+        let (tx, rx) = event::new_without_terminal();
+        let (items, file_detail, file_versions) = fixtures();
+        let file_detail = FileDetail {
+            encryption: Some("SSE-C".to_string()),
+            ..file_detail
+        };
+        let items_len = items.len();
+        let mut page = ObjectDetailPage::new(
+            file_detail,
+            file_versions,
+            "bucket-1".to_string(),
+            items,
+            ScrollListState::new(items_len),
+            50,
+            tx,
+        );
+        page.handle_key(KeyEvent::from(KeyCode::Char('C')));
+
+        assert!(matches!(page.view_state, ViewState::Default));
+        assert!(matches!(
+            rx.recv(),
+            AppEventType::NotifyWarn(msg) if msg == "Storage class of objects encrypted with SSE-C cannot be changed"
+        ));
+    }
+
     fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
         let backend = TestBackend::new(60, 20);
         let mut terminal = Terminal::new(backend)?;
@@ -947,16 +1936,19 @@ mod tests {
                 name: "file1".to_string(),
                 size_byte: 1024 + 10,
                 last_modified: parse_datetime("2024-01-02 13:01:02"),
+                owner: None,
             },
             ObjectItem::File {
                 name: "file2".to_string(),
                 size_byte: 1024 * 999,
                 last_modified: parse_datetime("2023-12-31 09:00:00"),
+                owner: None,
             },
             ObjectItem::File {
                 name: "file3".to_string(),
                 size_byte: 1024,
                 last_modified: parse_datetime("2024-01-03 12:59:59"),
+                owner: None,
             },
         ];
         let file_detail = FileDetail {
@@ -966,10 +1958,15 @@ mod tests {
             e_tag: "bef684de-a260-48a4-8178-8a535ecccadb".to_string(),
             content_type: "text/plain".to_string(),
             storage_class: "STANDARD".to_string(),
+            encryption: None,
+            kms_key_id: None,
+            bucket_key_enabled: None,
+            content_encoding: None,
             key: "file1".to_string(),
             s3_uri: "s3://bucket-1/file1".to_string(),
             arn: "arn:aws:s3:::bucket-1/file1".to_string(),
             object_url: "https://bucket-1.s3.ap-northeast-1.amazonaws.com/file1".to_string(),
+            restore_status: None,
         };
         let file_versions = vec![
             FileVersion {
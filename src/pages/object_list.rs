@@ -1,10 +1,10 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::HashSet};
 
 use chrono::{DateTime, Local};
 use ratatui::{
     crossterm::event::{KeyCode, KeyEvent},
     layout::Rect,
-    style::{Color, Style, Stylize},
+    style::{Color, Modifier, Style, Stylize},
     text::Line,
     widgets::ListItem,
     Frame,
@@ -12,30 +12,44 @@ use ratatui::{
 
 use crate::{
     event::{AppEventType, Sender},
-    key_code, key_code_char,
-    object::ObjectItem,
-    pages::util::{build_helps, build_short_helps},
+    key_code, key_code_char, key_event,
+    object::{item_identity, ObjectItem, ObjectItemsDiff, ObjectKey},
+    pages::util::{build_commands, build_helps, build_short_helps, HelpGroup},
     ui::common::{format_datetime, format_size_byte},
-    util::split_str,
+    util::{
+        pad_to_width, substring_match_indices, truncate_start_with_ellipsis, truncate_with_ellipsis,
+    },
     widget::{
-        InputDialog, InputDialogState, ObjectListSortDialog, ObjectListSortDialogState,
-        ObjectListSortType, ScrollList, ScrollListState,
+        highlighted_spans, AncestorListDialog, AncestorListDialogState, InputDialog,
+        InputDialogState, ObjectListSortDialog, ObjectListSortDialogState, ObjectListSortType,
+        PaletteCommand, ScrollList, ScrollListState,
     },
 };
 
 const SELECTED_COLOR: Color = Color::Cyan;
 const SELECTED_ITEM_TEXT_COLOR: Color = Color::Black;
-const HIGHLIGHTED_ITEM_TEXT_COLOR: Color = Color::Red;
+const ADDED_ITEM_COLOR: Color = Color::Green;
+const REMOVED_ITEM_COLOR: Color = Color::DarkGray;
 
 #[derive(Debug)]
 pub struct ObjectListPage {
     object_items: Vec<ObjectItem>,
+    object_key: ObjectKey,
     view_indices: Vec<usize>,
+    show_full_key: bool,
+    // passed through automatically while collapsing single folders
+    collapsed: bool,
+    // the changes of the last refresh, shown until the next key input
+    refresh_marks: Option<RefreshMarks>,
 
     view_state: ViewState,
 
     list_state: ScrollListState,
     filter_input_state: InputDialogState,
+    go_to_key_input_state: InputDialogState,
+    presign_put_name_input_state: InputDialogState,
+    presign_put_content_type_input_state: InputDialogState,
+    create_folder_input_state: InputDialogState,
     sort_dialog_state: ObjectListSortDialogState,
     tx: Sender,
 }
@@ -44,25 +58,86 @@ pub struct ObjectListPage {
 enum ViewState {
     Default,
     FilterDialog,
+    GoToKeyDialog,
+    PresignPutNameDialog,
+    PresignPutContentTypeDialog,
+    CreateFolderDialog,
     SortDialog,
+    AncestorListDialog(AncestorListDialogState),
+}
+
+#[derive(Debug, Default)]
+struct RefreshMarks {
+    added: HashSet<(bool, String)>,
+    removed: HashSet<(bool, String)>,
+}
+
+impl RefreshMarks {
+    fn new(diff: &ObjectItemsDiff) -> Self {
+        let identities = |items: &[ObjectItem]| {
+            items
+                .iter()
+                .map(|item| {
+                    let (is_dir, name) = item_identity(item);
+                    (is_dir, name.to_string())
+                })
+                .collect()
+        };
+        Self {
+            added: identities(&diff.added),
+            removed: identities(&diff.removed),
+        }
+    }
+
+    fn mark(&self, item: &ObjectItem) -> ItemMark {
+        let (is_dir, name) = item_identity(item);
+        let identity = (is_dir, name.to_string());
+        if self.added.contains(&identity) {
+            ItemMark::Added
+        } else if self.removed.contains(&identity) {
+            ItemMark::Removed
+        } else {
+            ItemMark::None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ItemMark {
+    None,
+    Added,
+    Removed,
 }
 
 impl ObjectListPage {
-    pub fn new(object_items: Vec<ObjectItem>, tx: Sender) -> Self {
+    pub fn new(object_items: Vec<ObjectItem>, object_key: ObjectKey, tx: Sender) -> Self {
         let items_len = object_items.len();
         let view_indices = (0..items_len).collect();
         Self {
             object_items,
+            object_key,
             view_indices,
+            show_full_key: false,
+            collapsed: false,
+            refresh_marks: None,
             view_state: ViewState::Default,
             list_state: ScrollListState::new(items_len),
             filter_input_state: InputDialogState::default(),
+            go_to_key_input_state: InputDialogState::default(),
+            presign_put_name_input_state: InputDialogState::default(),
+            presign_put_content_type_input_state: InputDialogState::default(),
+            create_folder_input_state: InputDialogState::default(),
             sort_dialog_state: ObjectListSortDialogState::default(),
             tx,
         }
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) {
+        if self.clear_refresh_marks() {
+            // the selected item has been removed, so the key is not applied to another item
+            return;
+        }
+
         match self.view_state {
             ViewState::Default => match key {
                 key_code!(KeyCode::Esc) => {
@@ -96,23 +171,117 @@ impl ObjectListPage {
                 key_code_char!('b') if self.non_empty() => {
                     self.select_prev_page();
                 }
+                key_code_char!('Q') => {
+                    self.tx
+                        .send(AppEventType::QuitWithLocation(self.current_location()));
+                }
                 key_code_char!('~') => {
                     self.tx.send(AppEventType::BackToBucketList);
                 }
+                key_code_char!('\\') => {
+                    self.tx.send(AppEventType::ObjectListMoveUpTo(0));
+                }
+                key_code_char!('u') => {
+                    self.open_ancestor_list_dialog();
+                }
+                key_code_char!('y') if self.non_empty() => {
+                    self.copy_selected_key();
+                }
+                key_code_char!('Y') => {
+                    self.copy_bucket_name();
+                }
+                key_code_char!('P') => {
+                    self.open_presign_put_name_dialog();
+                }
+                key_code_char!('N') => {
+                    self.open_create_folder_dialog();
+                }
+                key_code_char!('K') => {
+                    self.toggle_full_key();
+                }
+                key_code_char!('C') => {
+                    self.tx.send(AppEventType::ToggleCollapseDirs);
+                }
                 key_code_char!('x') if self.non_empty() => {
                     self.tx.send(AppEventType::ObjectListOpenManagementConsole);
                 }
+                key_code_char!('s') if self.non_empty() => {
+                    self.download_selected_dir();
+                }
                 key_code_char!('/') => {
                     self.open_filter_dialog();
                 }
                 key_code_char!('o') => {
                     self.open_sort_dialog();
                 }
+                key_code_char!(':') => {
+                    self.open_go_to_key_dialog();
+                }
+                key_code_char!('r') => {
+                    self.tx.send(AppEventType::OpenRecentObjects);
+                }
                 key_code_char!('?') => {
                     self.tx.send(AppEventType::OpenHelp);
                 }
                 _ => {}
             },
+            ViewState::GoToKeyDialog => match key {
+                key_code!(KeyCode::Esc) => {
+                    self.close_go_to_key_dialog();
+                }
+                key_code!(KeyCode::Enter) => {
+                    self.apply_go_to_key();
+                }
+                key_code_char!('?') => {
+                    self.tx.send(AppEventType::OpenHelp);
+                }
+                _ => {
+                    self.go_to_key_input_state.handle_key_event(key);
+                }
+            },
+            ViewState::PresignPutNameDialog => match key {
+                key_code!(KeyCode::Esc) => {
+                    self.close_presign_put_dialog();
+                }
+                key_code!(KeyCode::Enter) => {
+                    self.open_presign_put_content_type_dialog();
+                }
+                key_code_char!('?') => {
+                    self.tx.send(AppEventType::OpenHelp);
+                }
+                _ => {
+                    self.presign_put_name_input_state.handle_key_event(key);
+                }
+            },
+            ViewState::PresignPutContentTypeDialog => match key {
+                key_code!(KeyCode::Esc) => {
+                    self.close_presign_put_dialog();
+                }
+                key_code!(KeyCode::Enter) => {
+                    self.apply_presign_put();
+                }
+                key_code_char!('?') => {
+                    self.tx.send(AppEventType::OpenHelp);
+                }
+                _ => {
+                    self.presign_put_content_type_input_state
+                        .handle_key_event(key);
+                }
+            },
+            ViewState::CreateFolderDialog => match key {
+                key_code!(KeyCode::Esc) => {
+                    self.close_create_folder_dialog();
+                }
+                key_code!(KeyCode::Enter) => {
+                    self.apply_create_folder();
+                }
+                key_code_char!('?') => {
+                    self.tx.send(AppEventType::OpenHelp);
+                }
+                _ => {
+                    self.create_folder_input_state.handle_key_event(key);
+                }
+            },
             ViewState::FilterDialog => match key {
                 key_code!(KeyCode::Esc) => {
                     self.close_filter_dialog();
@@ -146,6 +315,31 @@ impl ObjectListPage {
                 }
                 _ => {}
             },
+            ViewState::AncestorListDialog(ref mut state) => match key {
+                key_code!(KeyCode::Esc) | key_code!(KeyCode::Backspace) => {
+                    self.close_ancestor_list_dialog();
+                }
+                key_code_char!('j') => {
+                    state.select_next();
+                }
+                key_code_char!('k') => {
+                    state.select_prev();
+                }
+                key_code!(KeyCode::Enter) => {
+                    let depth = state.selected();
+                    self.move_up_to(depth);
+                }
+                key_code_char!(c) if c.is_ascii_digit() => {
+                    let n = c.to_digit(10).unwrap() as usize;
+                    if 1 <= n && n <= self.object_key.object_path.len() {
+                        self.move_up_to(n - 1);
+                    }
+                }
+                key_code_char!('?') => {
+                    self.tx.send(AppEventType::OpenHelp);
+                }
+                _ => {}
+            },
         }
     }
 
@@ -153,10 +347,13 @@ impl ObjectListPage {
         let offset = self.list_state.offset;
         let selected = self.list_state.selected;
 
+        let key_prefix = self.show_full_key.then(|| self.object_key.prefix());
         let list_items = build_list_items(
             &self.object_items,
             &self.view_indices,
+            self.refresh_marks.as_ref(),
             self.filter_input_state.input(),
+            key_prefix.as_deref(),
             offset,
             selected,
             area,
@@ -173,59 +370,384 @@ impl ObjectListPage {
             f.set_cursor(cursor_x, cursor_y);
         }
 
+        if let ViewState::GoToKeyDialog = self.view_state {
+            let go_to_key_dialog = InputDialog::default().title("Go to key").max_width(60);
+            f.render_stateful_widget(go_to_key_dialog, area, &mut self.go_to_key_input_state);
+
+            let (cursor_x, cursor_y) = self.go_to_key_input_state.cursor();
+            f.set_cursor(cursor_x, cursor_y);
+        }
+
+        if let ViewState::PresignPutNameDialog = self.view_state {
+            let dialog = InputDialog::default().title("Name to upload").max_width(60);
+            f.render_stateful_widget(dialog, area, &mut self.presign_put_name_input_state);
+
+            let (cursor_x, cursor_y) = self.presign_put_name_input_state.cursor();
+            f.set_cursor(cursor_x, cursor_y);
+        }
+
+        if let ViewState::PresignPutContentTypeDialog = self.view_state {
+            let dialog = InputDialog::default()
+                .title("Content-Type (optional)")
+                .max_width(60);
+            f.render_stateful_widget(dialog, area, &mut self.presign_put_content_type_input_state);
+
+            let (cursor_x, cursor_y) = self.presign_put_content_type_input_state.cursor();
+            f.set_cursor(cursor_x, cursor_y);
+        }
+
+        if let ViewState::CreateFolderDialog = self.view_state {
+            let dialog = InputDialog::default()
+                .title("New folder name")
+                .max_width(60);
+            f.render_stateful_widget(dialog, area, &mut self.create_folder_input_state);
+
+            let (cursor_x, cursor_y) = self.create_folder_input_state.cursor();
+            f.set_cursor(cursor_x, cursor_y);
+        }
+
         if let ViewState::SortDialog = self.view_state {
             let sort_dialog = ObjectListSortDialog::new(self.sort_dialog_state);
             f.render_widget(sort_dialog, area);
         }
+
+        if let ViewState::AncestorListDialog(state) = self.view_state {
+            let labels = self.ancestor_labels();
+            let ancestor_list_dialog = AncestorListDialog::new(state, &labels);
+            f.render_widget(ancestor_list_dialog, area);
+        }
     }
 
-    pub fn helps(&self) -> Vec<String> {
-        let helps: &[(&[&str], &str)] = match self.view_state {
+    fn help_groups(&self) -> &'static [HelpGroup<'static>] {
+        match self.view_state {
             ViewState::Default => {
                 if self.filter_input_state.input().is_empty() {
                     &[
-                        (&["Esc", "Ctrl-c"], "Quit app"),
-                        (&["j/k"], "Select item"),
-                        (&["g/G"], "Go to top/bottom"),
-                        (&["f"], "Scroll page forward"),
-                        (&["b"], "Scroll page backward"),
-                        (&["Enter"], "Open file or folder"),
-                        (&["Backspace"], "Go back to prev folder"),
-                        (&["~"], "Go back to bucket list"),
-                        (&["/"], "Filter object list"),
-                        (&["o"], "Sort object list"),
-                        (&["x"], "Open management console in browser"),
+                        (
+                            "General",
+                            &[
+                                (
+                                    &["Esc", "Ctrl-c"],
+                                    "Quit app",
+                                    Some(key_event!(KeyCode::Esc)),
+                                ),
+                                (
+                                    &["Q"],
+                                    "Quit app and print current location",
+                                    Some(key_event!(KeyCode::Char('Q'))),
+                                ),
+                            ],
+                        ),
+                        (
+                            "Navigation",
+                            &[
+                                (&["j/k"], "Select item", None),
+                                (&["g/G"], "Go to top/bottom", None),
+                                (&["f"], "Scroll page forward", None),
+                                (&["b"], "Scroll page backward", None),
+                                (
+                                    &["Enter"],
+                                    "Open file or folder",
+                                    Some(key_event!(KeyCode::Enter)),
+                                ),
+                                (
+                                    &["Backspace"],
+                                    "Go back to prev folder",
+                                    Some(key_event!(KeyCode::Backspace)),
+                                ),
+                                (
+                                    &["\\"],
+                                    "Go back to bucket root",
+                                    Some(key_event!(KeyCode::Char('\\'))),
+                                ),
+                                (
+                                    &["u"],
+                                    "Go back to parent folders",
+                                    Some(key_event!(KeyCode::Char('u'))),
+                                ),
+                                (
+                                    &["~"],
+                                    "Go back to bucket list",
+                                    Some(key_event!(KeyCode::Char('~'))),
+                                ),
+                                (
+                                    &["/"],
+                                    "Filter object list",
+                                    Some(key_event!(KeyCode::Char('/'))),
+                                ),
+                                (
+                                    &["o"],
+                                    "Sort object list",
+                                    Some(key_event!(KeyCode::Char('o'))),
+                                ),
+                                (
+                                    &["K"],
+                                    "Toggle full key display",
+                                    Some(key_event!(KeyCode::Char('K'))),
+                                ),
+                                (
+                                    &["C"],
+                                    "Toggle collapsing single folders",
+                                    Some(key_event!(KeyCode::Char('C'))),
+                                ),
+                                (&[":"], "Go to key", Some(key_event!(KeyCode::Char(':')))),
+                                (
+                                    &["r"],
+                                    "Open recent objects",
+                                    Some(key_event!(KeyCode::Char('r'))),
+                                ),
+                            ],
+                        ),
+                        (
+                            "Copy",
+                            &[
+                                (
+                                    &["y"],
+                                    "Copy key of selected item",
+                                    Some(key_event!(KeyCode::Char('y'))),
+                                ),
+                                (
+                                    &["Y"],
+                                    "Copy bucket name",
+                                    Some(key_event!(KeyCode::Char('Y'))),
+                                ),
+                                (
+                                    &["P"],
+                                    "Copy presigned URL for upload",
+                                    Some(key_event!(KeyCode::Char('P'))),
+                                ),
+                            ],
+                        ),
+                        (
+                            "Operations",
+                            &[
+                                (
+                                    &["Ctrl-r"],
+                                    "Refresh object list",
+                                    Some(key_event!(KeyCode::Char('r'), Ctrl)),
+                                ),
+                                (
+                                    &["s"],
+                                    "Download folder",
+                                    Some(key_event!(KeyCode::Char('s'))),
+                                ),
+                                (
+                                    &["N"],
+                                    "Create folder",
+                                    Some(key_event!(KeyCode::Char('N'))),
+                                ),
+                                (
+                                    &["x"],
+                                    "Open management console in browser",
+                                    Some(key_event!(KeyCode::Char('x'))),
+                                ),
+                            ],
+                        ),
                     ]
                 } else {
                     &[
-                        (&["Ctrl-c"], "Quit app"),
-                        (&["Esc"], "Clear filter"),
-                        (&["j/k"], "Select item"),
-                        (&["g/G"], "Go to top/bottom"),
-                        (&["f"], "Scroll page forward"),
-                        (&["b"], "Scroll page backward"),
-                        (&["Enter"], "Open file or folder"),
-                        (&["Backspace"], "Go back to prev folder"),
-                        (&["~"], "Go back to bucket list"),
-                        (&["/"], "Filter object list"),
-                        (&["o"], "Sort object list"),
-                        (&["x"], "Open management console in browser"),
+                        (
+                            "General",
+                            &[
+                                (&["Ctrl-c"], "Quit app", None),
+                                (
+                                    &["Q"],
+                                    "Quit app and print current location",
+                                    Some(key_event!(KeyCode::Char('Q'))),
+                                ),
+                            ],
+                        ),
+                        (
+                            "Navigation",
+                            &[
+                                (&["Esc"], "Clear filter", Some(key_event!(KeyCode::Esc))),
+                                (&["j/k"], "Select item", None),
+                                (&["g/G"], "Go to top/bottom", None),
+                                (&["f"], "Scroll page forward", None),
+                                (&["b"], "Scroll page backward", None),
+                                (
+                                    &["Enter"],
+                                    "Open file or folder",
+                                    Some(key_event!(KeyCode::Enter)),
+                                ),
+                                (
+                                    &["Backspace"],
+                                    "Go back to prev folder",
+                                    Some(key_event!(KeyCode::Backspace)),
+                                ),
+                                (
+                                    &["\\"],
+                                    "Go back to bucket root",
+                                    Some(key_event!(KeyCode::Char('\\'))),
+                                ),
+                                (
+                                    &["u"],
+                                    "Go back to parent folders",
+                                    Some(key_event!(KeyCode::Char('u'))),
+                                ),
+                                (
+                                    &["~"],
+                                    "Go back to bucket list",
+                                    Some(key_event!(KeyCode::Char('~'))),
+                                ),
+                                (
+                                    &["/"],
+                                    "Filter object list",
+                                    Some(key_event!(KeyCode::Char('/'))),
+                                ),
+                                (
+                                    &["o"],
+                                    "Sort object list",
+                                    Some(key_event!(KeyCode::Char('o'))),
+                                ),
+                                (
+                                    &["K"],
+                                    "Toggle full key display",
+                                    Some(key_event!(KeyCode::Char('K'))),
+                                ),
+                                (
+                                    &["C"],
+                                    "Toggle collapsing single folders",
+                                    Some(key_event!(KeyCode::Char('C'))),
+                                ),
+                                (&[":"], "Go to key", Some(key_event!(KeyCode::Char(':')))),
+                                (
+                                    &["r"],
+                                    "Open recent objects",
+                                    Some(key_event!(KeyCode::Char('r'))),
+                                ),
+                            ],
+                        ),
+                        (
+                            "Copy",
+                            &[
+                                (
+                                    &["y"],
+                                    "Copy key of selected item",
+                                    Some(key_event!(KeyCode::Char('y'))),
+                                ),
+                                (
+                                    &["Y"],
+                                    "Copy bucket name",
+                                    Some(key_event!(KeyCode::Char('Y'))),
+                                ),
+                                (
+                                    &["P"],
+                                    "Copy presigned URL for upload",
+                                    Some(key_event!(KeyCode::Char('P'))),
+                                ),
+                            ],
+                        ),
+                        (
+                            "Operations",
+                            &[
+                                (
+                                    &["Ctrl-r"],
+                                    "Refresh object list",
+                                    Some(key_event!(KeyCode::Char('r'), Ctrl)),
+                                ),
+                                (
+                                    &["s"],
+                                    "Download folder",
+                                    Some(key_event!(KeyCode::Char('s'))),
+                                ),
+                                (
+                                    &["N"],
+                                    "Create folder",
+                                    Some(key_event!(KeyCode::Char('N'))),
+                                ),
+                                (
+                                    &["x"],
+                                    "Open management console in browser",
+                                    Some(key_event!(KeyCode::Char('x'))),
+                                ),
+                            ],
+                        ),
                     ]
                 }
             }
             ViewState::FilterDialog => &[
-                (&["Ctrl-c"], "Quit app"),
-                (&["Esc"], "Close filter dialog"),
-                (&["Enter"], "Apply filter"),
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc"], "Close filter dialog", None),
+                        (&["Enter"], "Apply filter", None),
+                    ],
+                ),
+            ],
+            ViewState::GoToKeyDialog => &[
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc"], "Close dialog", None),
+                        (&["Enter"], "Go to key", None),
+                    ],
+                ),
+            ],
+            ViewState::PresignPutNameDialog => &[
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc"], "Close dialog", None),
+                        (&["Enter"], "Enter content type", None),
+                    ],
+                ),
+            ],
+            ViewState::PresignPutContentTypeDialog => &[
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc"], "Close dialog", None),
+                        (&["Enter"], "Copy presigned URL", None),
+                    ],
+                ),
+            ],
+            ViewState::CreateFolderDialog => &[
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc"], "Close dialog", None),
+                        (&["Enter"], "Create folder", None),
+                    ],
+                ),
             ],
             ViewState::SortDialog => &[
-                (&["Ctrl-c"], "Quit app"),
-                (&["Esc"], "Close sort dialog"),
-                (&["j/k"], "Select item"),
-                (&["Enter"], "Apply sort"),
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc"], "Close sort dialog", None),
+                        (&["j/k"], "Select item", None),
+                        (&["Enter"], "Apply sort", None),
+                    ],
+                ),
             ],
-        };
-        build_helps(helps)
+            ViewState::AncestorListDialog(_) => &[
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc", "Backspace"], "Close dialog", None),
+                        (&["j/k"], "Select folder", None),
+                        (&["1-9"], "Go to folder by number", None),
+                        (&["Enter"], "Go to selected folder", None),
+                    ],
+                ),
+            ],
+        }
+    }
+
+    pub fn helps(&self) -> Vec<(String, Vec<String>)> {
+        build_helps(self.help_groups())
+    }
+
+    pub fn commands(&self) -> Vec<PaletteCommand> {
+        build_commands(self.help_groups())
     }
 
     pub fn short_helps(&self) -> Vec<(String, usize)> {
@@ -260,12 +782,38 @@ impl ObjectListPage {
                 (&["Enter"], "Filter", 1),
                 (&["?"], "Help", 0),
             ],
+            ViewState::GoToKeyDialog => &[
+                (&["Esc"], "Close", 2),
+                (&["Enter"], "Go", 1),
+                (&["?"], "Help", 0),
+            ],
+            ViewState::PresignPutNameDialog => &[
+                (&["Esc"], "Close", 2),
+                (&["Enter"], "Next", 1),
+                (&["?"], "Help", 0),
+            ],
+            ViewState::PresignPutContentTypeDialog => &[
+                (&["Esc"], "Close", 2),
+                (&["Enter"], "Copy", 1),
+                (&["?"], "Help", 0),
+            ],
+            ViewState::CreateFolderDialog => &[
+                (&["Esc"], "Close", 2),
+                (&["Enter"], "Create", 1),
+                (&["?"], "Help", 0),
+            ],
             ViewState::SortDialog => &[
                 (&["Esc"], "Close", 2),
                 (&["j/k"], "Select", 3),
                 (&["Enter"], "Sort", 1),
                 (&["?"], "Help", 0),
             ],
+            ViewState::AncestorListDialog(_) => &[
+                (&["Esc"], "Close", 2),
+                (&["j/k"], "Select", 3),
+                (&["Enter"], "Go", 1),
+                (&["?"], "Help", 0),
+            ],
         };
         build_short_helps(helps)
     }
@@ -316,6 +864,283 @@ impl ObjectListPage {
         self.sort_view_indices();
     }
 
+    fn open_go_to_key_dialog(&mut self) {
+        self.view_state = ViewState::GoToKeyDialog;
+    }
+
+    fn close_go_to_key_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+        self.go_to_key_input_state.clear_input();
+    }
+
+    fn apply_go_to_key(&mut self) {
+        let key = self.go_to_key_input_state.input().trim().to_string();
+        self.close_go_to_key_dialog();
+        if !key.is_empty() {
+            self.tx.send(AppEventType::RevealObject(key));
+        }
+    }
+
+    fn open_create_folder_dialog(&mut self) {
+        self.view_state = ViewState::CreateFolderDialog;
+    }
+
+    fn close_create_folder_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+        self.create_folder_input_state.clear_input();
+    }
+
+    fn apply_create_folder(&mut self) {
+        let name = self.create_folder_input_state.input().trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        // keep the dialog open so that the name can be fixed
+        if name.contains('/') {
+            let msg = "Folder name must not contain '/'";
+            self.tx.send(AppEventType::NotifyWarn(msg.into()));
+            return;
+        }
+        let exists = self
+            .object_items
+            .iter()
+            .any(|item| matches!(item, ObjectItem::Dir { name: n } if *n == name));
+        if exists {
+            let msg = format!("Folder '{}/' already exists", name);
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        self.close_create_folder_dialog();
+        self.tx.send(AppEventType::CreateFolder(name));
+    }
+
+    fn open_presign_put_name_dialog(&mut self) {
+        self.view_state = ViewState::PresignPutNameDialog;
+    }
+
+    fn open_presign_put_content_type_dialog(&mut self) {
+        if self.presign_put_name_input_state.input().trim().is_empty() {
+            return;
+        }
+        self.view_state = ViewState::PresignPutContentTypeDialog;
+    }
+
+    fn close_presign_put_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+        self.presign_put_name_input_state.clear_input();
+        self.presign_put_content_type_input_state.clear_input();
+    }
+
+    fn apply_presign_put(&mut self) {
+        let name = self.presign_put_name_input_state.input().trim().to_string();
+        let content_type = self
+            .presign_put_content_type_input_state
+            .input()
+            .trim()
+            .to_string();
+        self.close_presign_put_dialog();
+        let content_type = (!content_type.is_empty()).then_some(content_type);
+        self.tx
+            .send(AppEventType::CopyPresignedPutUrl(name, content_type));
+    }
+
+    pub fn object_key(&self) -> &ObjectKey {
+        &self.object_key
+    }
+
+    /// Adds the items of a following page of the listing, keeping the current selection.
+    pub fn append_items(&mut self, items: Vec<ObjectItem>) {
+        self.update_items(|object_items| insert_items(object_items, items));
+    }
+
+    /// Replaces the items with the complete listing, keeping the current selection.
+    pub fn set_items(&mut self, items: Vec<ObjectItem>) {
+        self.refresh_marks = None;
+        self.update_items(|object_items| *object_items = items);
+    }
+
+    /// Replaces the items with the reloaded listing, marking the added items
+    /// and keeping the removed items until the next key input.
+    pub fn set_refreshed_items(&mut self, items: Vec<ObjectItem>, diff: ObjectItemsDiff) {
+        if diff.is_empty() {
+            self.set_items(items);
+            return;
+        }
+        self.refresh_marks = Some(RefreshMarks::new(&diff));
+        self.update_items(|object_items| {
+            *object_items = items;
+            insert_items(object_items, diff.removed);
+        });
+    }
+
+    /// Clears the marks and the removed items, and returns true if the selected item was removed.
+    ///
+    /// In that case, the nearest remaining item is selected instead.
+    fn clear_refresh_marks(&mut self) -> bool {
+        let Some(marks) = self.refresh_marks.take() else {
+            return false;
+        };
+        if marks.removed.is_empty() {
+            return false;
+        }
+
+        let selected = self.list_state.selected;
+        let is_removed = |i: &usize| marks.mark(&self.object_items[*i]) == ItemMark::Removed;
+        let selected_removed = self.view_indices.get(selected).is_some_and(is_removed);
+        let replacement = selected_removed
+            .then(|| {
+                let (before, after) = self.view_indices.split_at(selected);
+                after
+                    .iter()
+                    .chain(before.iter().rev())
+                    .find(|i| !is_removed(i))
+                    .map(|&i| self.object_items[i].clone())
+            })
+            .flatten();
+
+        self.update_items(|object_items| {
+            object_items.retain(|item| marks.mark(item) != ItemMark::Removed)
+        });
+        if let Some(item) = replacement {
+            let index = self
+                .view_indices
+                .iter()
+                .position(|&i| same_item(&self.object_items[i], &item))
+                .unwrap_or_default();
+            self.list_state.update_total(self.view_indices.len(), index);
+        }
+        selected_removed
+    }
+
+    fn update_items(&mut self, f: impl FnOnce(&mut Vec<ObjectItem>)) {
+        let selected = self
+            .non_empty()
+            .then(|| self.current_selected_item().clone());
+
+        f(&mut self.object_items);
+
+        let filter = self.filter_input_state.input();
+        self.view_indices = self
+            .object_items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.name().contains(filter))
+            .map(|(idx, _)| idx)
+            .collect();
+        self.sort_view_indices();
+
+        let index = selected
+            .and_then(|selected| {
+                self.view_indices
+                    .iter()
+                    .position(|&i| same_item(&self.object_items[i], &selected))
+            })
+            .unwrap_or_default();
+        self.list_state.update_total(self.view_indices.len(), index);
+    }
+
+    /// Selects the item with the given name, clearing the filter if it hides the item.
+    pub fn select_item_by_name(&mut self, name: &str, dir: bool) -> bool {
+        let matches = |item: &ObjectItem| match item {
+            ObjectItem::Dir { name: n, .. } => dir && n == name,
+            ObjectItem::File { name: n, .. } => !dir && n == name,
+        };
+        if !self.object_items.iter().any(matches) {
+            return false;
+        }
+        if !self
+            .view_indices
+            .iter()
+            .any(|&i| matches(&self.object_items[i]))
+        {
+            self.reset_filter();
+        }
+        let index = self
+            .view_indices
+            .iter()
+            .position(|&i| matches(&self.object_items[i]))
+            .unwrap();
+        self.list_state.select(index);
+        true
+    }
+
+    fn open_ancestor_list_dialog(&mut self) {
+        let ancestors_len = self.object_key.object_path.len();
+        if ancestors_len == 0 {
+            // already at the bucket root
+            return;
+        }
+        self.view_state =
+            ViewState::AncestorListDialog(AncestorListDialogState::new(ancestors_len));
+    }
+
+    fn close_ancestor_list_dialog(&mut self) {
+        self.view_state = ViewState::Default;
+    }
+
+    fn move_up_to(&mut self, depth: usize) {
+        self.close_ancestor_list_dialog();
+        self.tx.send(AppEventType::ObjectListMoveUpTo(depth));
+    }
+
+    pub fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    pub fn set_collapsed(&mut self, collapsed: bool) {
+        self.collapsed = collapsed;
+    }
+
+    /// Returns true if the folder contains only one folder and nothing else.
+    pub fn has_single_dir(&self) -> bool {
+        matches!(self.object_items.as_slice(), [ObjectItem::Dir { .. }])
+    }
+
+    fn download_selected_dir(&self) {
+        if let ObjectItem::Dir { name, .. } = self.current_selected_item() {
+            self.tx.send(AppEventType::DownloadPrefix(name.clone()));
+        }
+    }
+
+    fn toggle_full_key(&mut self) {
+        self.show_full_key = !self.show_full_key;
+    }
+
+    fn copy_selected_key(&self) {
+        let key = self.object_key.item_key(self.current_selected_item());
+        self.tx
+            .send(AppEventType::CopyToClipboard("key".into(), key));
+    }
+
+    fn copy_bucket_name(&self) {
+        let bucket = self.object_key.bucket_name.clone();
+        self.tx
+            .send(AppEventType::CopyToClipboard("bucket name".into(), bucket));
+    }
+
+    fn current_location(&self) -> String {
+        format!(
+            "s3://{}/{}",
+            self.object_key.bucket_name,
+            self.object_key.prefix()
+        )
+    }
+
+    fn ancestor_labels(&self) -> Vec<String> {
+        let bucket = &self.object_key.bucket_name;
+        let path = &self.object_key.object_path;
+        (0..path.len())
+            .map(|depth| {
+                let mut label = format!("{}/", bucket);
+                for p in path.iter().take(depth) {
+                    label.push_str(p);
+                    label.push('/');
+                }
+                label
+            })
+            .collect()
+    }
+
     fn apply_filter(&mut self) {
         self.view_state = ViewState::Default;
 
@@ -338,7 +1163,8 @@ impl ObjectListPage {
             .map(|(idx, _)| idx)
             .collect();
         // reset list state
-        self.list_state = ScrollListState::new(self.view_indices.len());
+        self.list_state =
+            ScrollListState::new(self.view_indices.len()).with_wrap(self.list_state.wrap());
 
         self.sort_view_indices();
     }
@@ -387,6 +1213,22 @@ impl ObjectListPage {
         self.view_indices.sort_by(sort_func);
     }
 
+    /// Returns the items shown in the list, except the ones kept only to be shown as removed.
+    pub fn visible_items(&self) -> Vec<&ObjectItem> {
+        let is_removed = |item: &&ObjectItem| {
+            self.refresh_marks
+                .as_ref()
+                .is_some_and(|marks| marks.mark(item) == ItemMark::Removed)
+        };
+        self.view_indices
+            .iter()
+            .skip(self.list_state.offset)
+            .take(self.list_state.height())
+            .map(|&i| &self.object_items[i])
+            .filter(|item| !is_removed(item))
+            .collect()
+    }
+
     pub fn current_selected_item(&self) -> &ObjectItem {
         let i = self
             .view_indices
@@ -407,6 +1249,10 @@ impl ObjectListPage {
         })
     }
 
+    pub fn object_list_len(&self) -> usize {
+        self.object_items.len()
+    }
+
     pub fn object_list(&self) -> Vec<ObjectItem> {
         self.view_indices
             .iter()
@@ -419,44 +1265,91 @@ impl ObjectListPage {
         self.list_state
     }
 
+    pub fn set_wrap_selection(&mut self, wrap: bool) {
+        self.list_state.set_wrap(wrap);
+    }
+
     fn non_empty(&self) -> bool {
         !self.view_indices.is_empty()
     }
 }
 
+fn same_item(a: &ObjectItem, b: &ObjectItem) -> bool {
+    match (a, b) {
+        (ObjectItem::Dir { name: a, .. }, ObjectItem::Dir { name: b, .. }) => a == b,
+        (ObjectItem::File { name: a, .. }, ObjectItem::File { name: b, .. }) => a == b,
+        _ => false,
+    }
+}
+
+fn insert_items(object_items: &mut Vec<ObjectItem>, items: Vec<ObjectItem>) {
+    // folders come first, as in the complete listing
+    let (dirs, files): (Vec<_>, Vec<_>) = items
+        .into_iter()
+        .partition(|item| matches!(item, ObjectItem::Dir { .. }));
+    let dirs_len = object_items
+        .iter()
+        .take_while(|item| matches!(item, ObjectItem::Dir { .. }))
+        .count();
+    object_items.splice(dirs_len..dirs_len, dirs);
+    object_items.extend(files);
+}
+
+#[allow(clippy::too_many_arguments)]
 fn build_list_items<'a>(
     current_items: &'a [ObjectItem],
     view_indices: &'a [usize],
+    refresh_marks: Option<&RefreshMarks>,
     filter: &'a str,
+    key_prefix: Option<&str>,
     offset: usize,
     selected: usize,
     area: Rect,
 ) -> Vec<ListItem<'a>> {
-    let show_item_count = (area.height as usize) - 2 /* border */;
+    let show_item_count = (area.height as usize).saturating_sub(2 /* border */);
     view_indices
         .iter()
         .map(|&original_idx| &current_items[original_idx])
         .skip(offset)
         .take(show_item_count)
         .enumerate()
-        .map(|(idx, item)| build_list_item(item, idx + offset == selected, filter, area))
+        .map(|(idx, item)| {
+            let mark = refresh_marks.map_or(ItemMark::None, |marks| marks.mark(item));
+            build_list_item(
+                item,
+                idx + offset == selected,
+                mark,
+                filter,
+                key_prefix,
+                area,
+            )
+        })
         .collect()
 }
 
 fn build_list_item<'a>(
     item: &'a ObjectItem,
     selected: bool,
+    mark: ItemMark,
     filter: &'a str,
+    key_prefix: Option<&str>,
     area: Rect,
 ) -> ListItem<'a> {
     let line = match item {
-        ObjectItem::Dir { name, .. } => build_object_dir_line(name, filter),
+        ObjectItem::Dir { name, .. } => build_object_dir_line(name, key_prefix, filter, area.width),
         ObjectItem::File {
             name,
             size_byte,
             last_modified,
             ..
-        } => build_object_file_line(name, *size_byte, last_modified, filter, area.width),
+        } => build_object_file_line(
+            name,
+            key_prefix,
+            *size_byte,
+            last_modified,
+            filter,
+            area.width,
+        ),
     };
 
     let style = if selected {
@@ -464,29 +1357,48 @@ fn build_list_item<'a>(
             .bg(SELECTED_COLOR)
             .fg(SELECTED_ITEM_TEXT_COLOR)
     } else {
-        Style::default()
+        match mark {
+            ItemMark::None => Style::default(),
+            ItemMark::Added => Style::default().fg(ADDED_ITEM_COLOR),
+            ItemMark::Removed => Style::default().fg(REMOVED_ITEM_COLOR),
+        }
+    };
+    let style = if mark == ItemMark::Removed {
+        style.add_modifier(Modifier::CROSSED_OUT)
+    } else {
+        style
     };
     ListItem::new(line).style(style)
 }
 
-fn build_object_dir_line<'a>(name: &'a str, filter: &'a str) -> Line<'a> {
-    if filter.is_empty() {
-        Line::from(vec![" ".into(), name.bold(), "/".bold(), " ".into()])
-    } else {
-        let (before, highlighted, after) = split_str(name, filter).unwrap();
-        Line::from(vec![
-            " ".into(),
-            before.bold(),
-            highlighted.fg(HIGHLIGHTED_ITEM_TEXT_COLOR).bold(),
-            after.bold(),
-            "/".bold(),
-            " ".into(),
-        ])
+/// Truncates the name to fit in `width`. If the key prefix is given, the full key is shown keeping its end visible.
+fn display_name(name: &str, key_prefix: Option<&str>, width: usize) -> String {
+    match key_prefix {
+        Some(prefix) => truncate_start_with_ellipsis(&format!("{}{}", prefix, name), width),
+        None => truncate_with_ellipsis(name, width),
     }
 }
 
+fn build_object_dir_line<'a>(
+    name: &'a str,
+    key_prefix: Option<&str>,
+    filter: &'a str,
+    width: u16,
+) -> Line<'a> {
+    let name_w =
+        (width as usize).saturating_sub(3 /* spaces + slash */ + 4 /* border + pad */);
+    let name = display_name(name, key_prefix, name_w);
+    // the matched part may have been truncated
+    let indices = substring_match_indices(filter, &name).unwrap_or_default();
+    let mut spans = vec![" ".into()];
+    spans.extend(highlighted_spans(&name, &indices, Style::default().bold()));
+    spans.extend(["/".bold(), " ".into()]);
+    Line::from(spans)
+}
+
 fn build_object_file_line<'a>(
     name: &'a str,
+    key_prefix: Option<&str>,
     size_byte: usize,
     last_modified: &'a DateTime<Local>,
     filter: &'a str,
@@ -496,36 +1408,26 @@ fn build_object_file_line<'a>(
     let date = format_datetime(last_modified);
     let date_w: usize = 19;
     let size_w: usize = 10;
-    let name_w: usize = (width as usize) - date_w - size_w - 10 /* spaces */ - 4 /* border + pad */;
+    let name_w: usize = (width as usize).saturating_sub(
+        date_w + size_w + 10 /* spaces */ + 4, /* border + pad */
+    );
 
-    let name = format!("{:<name_w$}", name, name_w = name_w);
+    let name = pad_to_width(&display_name(name, key_prefix, name_w), name_w);
     let date = format!("{:<date_w$}", date, date_w = date_w);
     let size = format!("{:>size_w$}", size, size_w = size_w);
 
-    if filter.is_empty() {
-        Line::from(vec![
-            " ".into(),
-            name.into(),
-            "    ".into(),
-            date.into(),
-            "    ".into(),
-            size.into(),
-            " ".into(),
-        ])
-    } else {
-        let (before, highlighted, after) = split_str(&name, filter).unwrap();
-        Line::from(vec![
-            " ".into(),
-            before.into(),
-            highlighted.fg(HIGHLIGHTED_ITEM_TEXT_COLOR),
-            after.into(),
-            "    ".into(),
-            date.into(),
-            "    ".into(),
-            size.into(),
-            " ".into(),
-        ])
-    }
+    // the matched part may have been truncated
+    let indices = substring_match_indices(filter, &name).unwrap_or_default();
+    let mut spans = vec![" ".into()];
+    spans.extend(highlighted_spans(&name, &indices, Style::default()));
+    spans.extend([
+        "    ".into(),
+        date.into(),
+        "    ".into(),
+        size.into(),
+        " ".into(),
+    ]);
+    Line::from(spans)
 }
 
 #[cfg(test)]
@@ -553,14 +1455,16 @@ mod tests {
                     name: "file1".to_string(),
                     size_byte: 1024 + 10,
                     last_modified: parse_datetime("2024-01-02 13:01:02"),
+                    owner: None,
                 },
                 ObjectItem::File {
                     name: "file2".to_string(),
                     size_byte: 1024 * 999,
                     last_modified: parse_datetime("2023-12-31 09:00:00"),
+                    owner: None,
                 },
             ];
-            let mut page = ObjectListPage::new(items, tx);
+            let mut page = ObjectListPage::new(items, object_key(), tx);
             let area = Rect::new(0, 0, 60, 10);
             page.render(f, area);
         })?;
@@ -590,6 +1494,70 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_render_full_key() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = Terminal::new(TestBackend::new(60, 4))?;
+
+        let items = vec![
+            ObjectItem::Dir {
+                name: "dir1".to_string(),
+            },
+            ObjectItem::File {
+                name: "file1.txt".to_string(),
+                size_byte: 1024,
+                last_modified: parse_datetime("2024-01-02 13:01:02"),
+                owner: None,
+            },
+        ];
+        let object_key = ObjectKey {
+            bucket_name: "bucket-1".to_string(),
+            object_path: vec!["a-long-folder".into(), "sub".into(), "deeper".into()],
+        };
+        let mut page = ObjectListPage::new(items, object_key, tx);
+        let area = Rect::new(0, 0, 60, 4);
+
+        terminal.draw(|f| page.render(f, area))?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌─────────────────────────────────────────────────── 1 / 2 ┐",
+            "│  dir1/                                                   │",
+            "│  file1.txt            2024-01-02 13:01:02         1 KiB  │",
+            "└──────────────────────────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // dir items
+            (3..8, [1]) => modifier: Modifier::BOLD,
+            // selected item
+            (2..58, [1]) => bg: Color::Cyan, fg: Color::Black,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        page.handle_key(KeyEvent::from(KeyCode::Char('K')));
+        terminal.draw(|f| page.render(f, area))?;
+
+        // the end of the long key is kept visible
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌─────────────────────────────────────────────────── 1 / 2 ┐",
+            "│  a-long-folder/sub/deeper/dir1/                          │",
+            "│  …deeper/file1.txt    2024-01-02 13:01:02         1 KiB  │",
+            "└──────────────────────────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // dir items
+            (3..33, [1]) => modifier: Modifier::BOLD,
+            // selected item
+            (2..58, [1]) => bg: Color::Cyan, fg: Color::Black,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
     #[test]
     fn test_render_with_scroll() -> std::io::Result<()> {
         let (tx, _) = event::new();
@@ -601,9 +1569,10 @@ mod tests {
                     name: format!("file{}", i + 1),
                     size_byte: 1024,
                     last_modified: parse_datetime("2024-01-02 13:01:02"),
+                    owner: None,
                 })
                 .collect();
-            let mut page = ObjectListPage::new(items, tx);
+            let mut page = ObjectListPage::new(items, object_key(), tx);
             let area = Rect::new(0, 0, 60, 10);
             page.render(f, area);
         })?;
@@ -631,6 +1600,413 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_render_ancestor_list_dialog() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = setup_terminal()?;
+
+        terminal.draw(|f| {
+            let items = vec![ObjectItem::File {
+                name: "file1".to_string(),
+                size_byte: 1024,
+                last_modified: parse_datetime("2024-01-02 13:01:02"),
+                owner: None,
+            }];
+            let mut page = ObjectListPage::new(items, object_key(), tx);
+            page.handle_key(KeyEvent::from(KeyCode::Char('u')));
+            let area = Rect::new(0, 0, 60, 10);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌─────────────────────────────────────────────────── 1 / 1 ┐",
+            "│  file1                2024-01-02 13:01:02         1 KiB  │",
+            "│                                                          │",
+            "│ ╭Go to─────────────────────────────────────────────────╮ │",
+            "│ │ 1: bucket-1/                                         │ │",
+            "│ │ 2: bucket-1/dir1/                                    │ │",
+            "│ ╰──────────────────────────────────────────────────────╯ │",
+            "│                                                          │",
+            "│                                                          │",
+            "└──────────────────────────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // selected item
+            (2..58, [1]) => bg: Color::Cyan, fg: Color::Black,
+            // selected ancestor
+            (4..56, [5]) => fg: Color::Cyan,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ancestor_list_dialog_at_bucket_root() {
+        let (tx, _) = event::new();
+        let object_key = ObjectKey {
+            bucket_name: "bucket-1".to_string(),
+            object_path: vec![],
+        };
+        let mut page = ObjectListPage::new(vec![], object_key, tx);
+
+        page.handle_key(KeyEvent::from(KeyCode::Char('u')));
+
+        assert!(matches!(page.view_state, ViewState::Default));
+    }
+
+    #[test]
+    fn test_visible_items() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = setup_terminal()?;
+
+        let items = (0..32)
+            .map(|i| ObjectItem::File {
+                name: format!("file{}", i + 1),
+                size_byte: 1024,
+                last_modified: parse_datetime("2024-01-02 13:01:02"),
+                owner: None,
+            })
+            .collect();
+        let mut page = ObjectListPage::new(items, object_key(), tx);
+        let area = Rect::new(0, 0, 60, 10);
+
+        // nothing is visible before rendering
+        assert!(page.visible_items().is_empty());
+
+        terminal.draw(|f| page.render(f, area))?;
+        let names: Vec<&str> = page.visible_items().iter().map(|i| i.name()).collect();
+        assert_eq!(
+            names,
+            (1..=8).map(|i| format!("file{}", i)).collect::<Vec<_>>()
+        );
+
+        for _ in 0..10 {
+            page.handle_key(KeyEvent::from(KeyCode::Char('j')));
+        }
+        terminal.draw(|f| page.render(f, area))?;
+        let names: Vec<&str> = page.visible_items().iter().map(|i| i.name()).collect();
+        assert_eq!(
+            names,
+            (4..=11).map(|i| format!("file{}", i)).collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_visible_items_without_removed_items() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = setup_terminal()?;
+        let old_items = vec![file("file1"), file("file2"), file("file3")];
+        let new_items = vec![file("file1"), file("file3"), file("file4")];
+        let diff = ObjectItemsDiff::new(&old_items, &new_items);
+        let mut page = ObjectListPage::new(old_items, object_key(), tx);
+        page.set_refreshed_items(new_items, diff);
+        let area = Rect::new(0, 0, 60, 10);
+
+        // the removed item is still shown, but not prefetched
+        terminal.draw(|f| page.render(f, area))?;
+        let names: Vec<&str> = page.visible_items().iter().map(|i| i.name()).collect();
+        assert_eq!(names, ["file1", "file3", "file4"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_items_keeps_selection() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = setup_terminal()?;
+
+        let items = vec![dir("dir1"), file("file1"), file("file2"), file("file3")];
+        let mut page = ObjectListPage::new(items, object_key(), tx);
+        let area = Rect::new(0, 0, 60, 10);
+        terminal.draw(|f| page.render(f, area))?;
+
+        page.handle_key(KeyEvent::from(KeyCode::Char('j')));
+        page.handle_key(KeyEvent::from(KeyCode::Char('j')));
+        assert_eq!(page.current_selected_item().name(), "file2");
+
+        // folders of the next page are inserted before the files
+        page.append_items(vec![dir("dir2"), file("file4")]);
+        let names: Vec<String> = page
+            .object_list()
+            .iter()
+            .map(|i| i.name().to_string())
+            .collect();
+        assert_eq!(names, ["dir1", "dir2", "file1", "file2", "file3", "file4"]);
+        assert_eq!(page.current_selected_item().name(), "file2");
+        assert_eq!(page.list_state().selected, 3);
+
+        // the filter is kept
+        page.handle_key(KeyEvent::from(KeyCode::Char('/')));
+        page.handle_key(KeyEvent::from(KeyCode::Char('f')));
+        page.handle_key(KeyEvent::from(KeyCode::Enter));
+        page.handle_key(KeyEvent::from(KeyCode::Char('G')));
+        assert_eq!(page.current_selected_item().name(), "file4");
+        page.append_items(vec![dir("dir3"), file("file5")]);
+        let names: Vec<String> = page
+            .object_list()
+            .iter()
+            .map(|i| i.name().to_string())
+            .collect();
+        assert_eq!(names, ["file1", "file2", "file3", "file4", "file5"]);
+        assert_eq!(page.current_selected_item().name(), "file4");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_refreshed_items() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = setup_terminal()?;
+
+        let old_items = vec![dir("dir1"), file("file1"), file("file2")];
+        let new_items = vec![dir("dir1"), dir("dir2"), file("file1"), file("file3")];
+        let diff = ObjectItemsDiff::new(&old_items, &new_items);
+        let mut page = ObjectListPage::new(old_items, object_key(), tx);
+        page.set_refreshed_items(new_items, diff);
+        let area = Rect::new(0, 0, 60, 10);
+        terminal.draw(|f| page.render(f, area))?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌─────────────────────────────────────────────────── 1 / 5 ┐",
+            "│  dir1/                                                   │",
+            "│  dir2/                                                   │",
+            "│  file1                2024-01-02 13:01:02         1 KiB  │",
+            "│  file3                2024-01-02 13:01:02         1 KiB  │",
+            "│  file2                2024-01-02 13:01:02         1 KiB  │",
+            "│                                                          │",
+            "│                                                          │",
+            "│                                                          │",
+            "└──────────────────────────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // dir items
+            (3..8, [1, 2]) => modifier: Modifier::BOLD,
+            // selected item
+            (2..58, [1]) => bg: Color::Cyan, fg: Color::Black,
+            // added items
+            (2..58, [2, 4]) => fg: Color::Green,
+            // removed item
+            (2..58, [5]) => fg: Color::DarkGray, modifier: Modifier::CROSSED_OUT,
+        }
+        terminal.backend().assert_buffer(&expected);
+
+        // the marks and the removed items are cleared by the next key input
+        page.handle_key(KeyEvent::from(KeyCode::Char('j')));
+        terminal.draw(|f| page.render(f, area))?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌─────────────────────────────────────────────────── 2 / 4 ┐",
+            "│  dir1/                                                   │",
+            "│  dir2/                                                   │",
+            "│  file1                2024-01-02 13:01:02         1 KiB  │",
+            "│  file3                2024-01-02 13:01:02         1 KiB  │",
+            "│                                                          │",
+            "│                                                          │",
+            "│                                                          │",
+            "│                                                          │",
+            "└──────────────────────────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            // dir items
+            (3..8, [1, 2]) => modifier: Modifier::BOLD,
+            // selected item
+            (2..58, [2]) => bg: Color::Cyan, fg: Color::Black,
+        }
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_on_removed_item_after_refresh() {
+        let (tx, rx) = event::new_without_terminal();
+        let old_items = vec![file("file1"), file("file2"), file("file3")];
+        let new_items = vec![file("file1"), file("file3")];
+        let diff = ObjectItemsDiff::new(&old_items, &new_items);
+        let mut page = ObjectListPage::new(old_items, object_key(), tx.clone());
+        page.set_refreshed_items(new_items, diff);
+        // the removed item is shown at the end
+        page.list_state.select(2);
+        assert_eq!(page.current_selected_item().name(), "file2");
+
+        // the key is not applied to the item replacing the removed one
+        page.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(page.current_selected_item().name(), "file3");
+        tx.send(AppEventType::NotifyInfo("end".into()));
+        assert!(matches!(rx.recv(), AppEventType::NotifyInfo(_)));
+
+        page.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(matches!(rx.recv(), AppEventType::ObjectListMoveDown));
+    }
+
+    #[test]
+    fn test_select_item_by_name() {
+        let (tx, _) = event::new();
+        let items = vec![
+            ObjectItem::Dir { name: "dir".into() },
+            ObjectItem::File {
+                name: "dir".into(),
+                size_byte: 1024,
+                last_modified: parse_datetime("2024-01-02 13:01:02"),
+                owner: None,
+            },
+            ObjectItem::File {
+                name: "file".into(),
+                size_byte: 1024,
+                last_modified: parse_datetime("2024-01-02 13:01:02"),
+                owner: None,
+            },
+        ];
+        let mut page = ObjectListPage::new(items, object_key(), tx);
+
+        assert!(page.select_item_by_name("dir", false));
+        assert_eq!(page.current_selected_item().name(), "dir");
+        assert!(matches!(
+            page.current_selected_item(),
+            ObjectItem::File { .. }
+        ));
+
+        assert!(page.select_item_by_name("dir", true));
+        assert!(matches!(
+            page.current_selected_item(),
+            ObjectItem::Dir { .. }
+        ));
+
+        assert!(!page.select_item_by_name("file", true));
+        assert!(!page.select_item_by_name("nothing", false));
+
+        // the filter is cleared if it hides the item
+        page.handle_key(KeyEvent::from(KeyCode::Char('/')));
+        page.handle_key(KeyEvent::from(KeyCode::Char('d')));
+        page.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(page.select_item_by_name("file", false));
+        assert_eq!(page.current_selected_item().name(), "file");
+        assert_eq!(page.filter_input_state.input(), "");
+    }
+
+    #[test]
+    fn test_presign_put_dialog() {
+        let (tx, rx) = event::new_without_terminal();
+        let mut page = ObjectListPage::new(vec![], object_key(), tx);
+
+        let type_chars = |page: &mut ObjectListPage, s: &str| {
+            for c in s.chars() {
+                page.handle_key(KeyEvent::from(KeyCode::Char(c)));
+            }
+        };
+        page.handle_key(KeyEvent::from(KeyCode::Char('P')));
+        type_chars(&mut page, "new.csv");
+        page.handle_key(KeyEvent::from(KeyCode::Enter));
+        type_chars(&mut page, "text/csv");
+        page.handle_key(KeyEvent::from(KeyCode::Enter));
+
+        assert!(matches!(page.view_state, ViewState::Default));
+        assert!(matches!(
+            rx.recv(),
+            AppEventType::CopyPresignedPutUrl(name, Some(content_type))
+                if name == "new.csv" && content_type == "text/csv"
+        ));
+    }
+
+    #[test]
+    fn test_create_folder_dialog() {
+        let (tx, rx) = event::new_without_terminal();
+        let items = vec![ObjectItem::Dir {
+            name: "dir1".into(),
+        }];
+        let mut page = ObjectListPage::new(items, object_key(), tx);
+
+        let type_chars = |page: &mut ObjectListPage, s: &str| {
+            for c in s.chars() {
+                page.handle_key(KeyEvent::from(KeyCode::Char(c)));
+            }
+        };
+        let clear_input = |page: &mut ObjectListPage| {
+            while !page.create_folder_input_state.input().is_empty() {
+                page.handle_key(KeyEvent::from(KeyCode::Backspace));
+            }
+        };
+        page.handle_key(KeyEvent::from(KeyCode::Char('N')));
+
+        type_chars(&mut page, "a/b");
+        page.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(matches!(page.view_state, ViewState::CreateFolderDialog));
+        assert!(matches!(rx.recv(), AppEventType::NotifyWarn(_)));
+
+        clear_input(&mut page);
+        type_chars(&mut page, "dir1");
+        page.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(matches!(page.view_state, ViewState::CreateFolderDialog));
+        assert!(matches!(rx.recv(), AppEventType::NotifyWarn(_)));
+
+        clear_input(&mut page);
+        type_chars(&mut page, "dir2");
+        page.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(matches!(page.view_state, ViewState::Default));
+        assert!(matches!(rx.recv(), AppEventType::CreateFolder(name) if name == "dir2"));
+    }
+
+    #[test]
+    fn test_has_single_dir() {
+        let (tx, _) = event::new();
+
+        let page = ObjectListPage::new(vec![dir("a")], object_key(), tx.clone());
+        assert!(page.has_single_dir());
+
+        let page = ObjectListPage::new(vec![dir("a"), dir("b")], object_key(), tx.clone());
+        assert!(!page.has_single_dir());
+
+        let page = ObjectListPage::new(vec![dir("a"), file("file")], object_key(), tx.clone());
+        assert!(!page.has_single_dir());
+
+        let page = ObjectListPage::new(vec![], object_key(), tx);
+        assert!(!page.has_single_dir());
+    }
+
+    #[test]
+    fn test_object_key_with_selected_folder() {
+        let (tx, _) = event::new();
+        let items = vec![
+            ObjectItem::Dir {
+                name: "dir3".into(),
+            },
+            ObjectItem::File {
+                name: "file1".into(),
+                size_byte: 1024,
+                last_modified: parse_datetime("2024-01-02 13:01:02"),
+                owner: None,
+            },
+        ];
+        let mut page = ObjectListPage::new(items, object_key(), tx);
+        assert!(page.select_item_by_name("dir3", true));
+
+        // the requests from the page are made in its location, not in the selected folder
+        assert_eq!(page.object_key().prefix(), "dir1/dir2/");
+        let file = &page.object_items[1];
+        assert_eq!(page.object_key().item_key(file), "dir1/dir2/file1");
+    }
+
+    #[test]
+    fn test_current_location() {
+        let (tx, _) = event::new();
+        let page = ObjectListPage::new(vec![], object_key(), tx.clone());
+        assert_eq!(page.current_location(), "s3://bucket-1/dir1/dir2/");
+
+        let object_key = ObjectKey {
+            bucket_name: "bucket-1".to_string(),
+            object_path: vec![],
+        };
+        let page = ObjectListPage::new(vec![], object_key, tx);
+        assert_eq!(page.current_location(), "s3://bucket-1/");
+    }
+
     #[test]
     fn test_sort_items() {
         let (tx, _) = event::new();
@@ -640,20 +2016,23 @@ mod tests {
                 name: "file".into(),
                 size_byte: 1024,
                 last_modified: parse_datetime("2024-01-02 13:01:02"),
+                owner: None,
             },
             ObjectItem::Dir { name: "dir".into() },
             ObjectItem::File {
                 name: "xyz".into(),
                 size_byte: 1024 * 1024,
                 last_modified: parse_datetime("2023-12-31 23:59:59"),
+                owner: None,
             },
             ObjectItem::File {
                 name: "abc".into(),
                 size_byte: 0,
                 last_modified: parse_datetime("-2000-01-01 00:00:00"),
+                owner: None,
             },
         ];
-        let mut page = ObjectListPage::new(items, tx);
+        let mut page = ObjectListPage::new(items, object_key(), tx);
 
         page.handle_key(KeyEvent::from(KeyCode::Char('o')));
         page.handle_key(KeyEvent::from(KeyCode::Char('j'))); // select NameAsc
@@ -681,6 +2060,54 @@ mod tests {
         assert_eq!(page.view_indices, vec![3, 1, 4, 0, 2]);
     }
 
+    fn object_key() -> ObjectKey {
+        ObjectKey {
+            bucket_name: "bucket-1".to_string(),
+            object_path: vec!["dir1".to_string(), "dir2".to_string()],
+        }
+    }
+
+    fn dir(name: &str) -> ObjectItem {
+        ObjectItem::Dir {
+            name: name.to_string(),
+        }
+    }
+
+    fn file(name: &str) -> ObjectItem {
+        ObjectItem::File {
+            name: name.to_string(),
+            size_byte: 1024,
+            last_modified: parse_datetime("2024-01-02 13:01:02"),
+            owner: None,
+        }
+    }
+
+    #[test]
+    fn test_render_small_area() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = Terminal::new(TestBackend::new(3, 3))?;
+
+        // should not panic
+        terminal.draw(|f| {
+            let items = vec![
+                ObjectItem::Dir {
+                    name: "dir1".to_string(),
+                },
+                ObjectItem::File {
+                    name: "file1".to_string(),
+                    size_byte: 1024,
+                    last_modified: parse_datetime("2024-01-02 13:01:02"),
+                    owner: None,
+                },
+            ];
+            let mut page = ObjectListPage::new(items, object_key(), tx);
+            let area = Rect::new(0, 0, 3, 3);
+            page.render(f, area);
+        })?;
+
+        Ok(())
+    }
+
     fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
         let backend = TestBackend::new(60, 10);
         let mut terminal = Terminal::new(backend)?;
@@ -1,26 +1,44 @@
 use ratatui::{
     crossterm::event::{KeyCode, KeyEvent},
     layout::Rect,
+    widgets::Block,
     Frame,
 };
 
+use std::borrow::Cow;
+
 use crate::{
     config::PreviewConfig,
+    csv::{is_csv, Csv},
+    encoding::{ContentEncoding, MAX_DECODED_SIZE_BYTES},
     event::{AppEventType, Sender},
-    key_code, key_code_char,
+    key_code, key_code_char, key_event,
     object::{FileDetail, RawObject},
-    pages::util::{build_helps, build_short_helps},
-    widget::{InputDialog, InputDialogState, TextPreview, TextPreviewState},
+    pages::util::{build_commands, build_helps, build_short_helps, HelpGroup},
+    ui::common::format_size_byte,
+    widget::{
+        CsvTable, CsvTableState, Hyperlink, InputDialog, InputDialogState, PaletteCommand,
+        SaveConfirmAction, SaveConfirmDialog, SaveConfirmDialogState, ScrollLinesOptions,
+        TextPreview, TextPreviewState,
+    },
 };
 
+const MAX_COPY_CONTENTS_BYTES: usize = 1024 * 1024;
+const MAX_TABLE_ROWS: usize = 10_000;
+
 #[derive(Debug)]
 pub struct ObjectPreviewPage {
     state: TextPreviewState,
+    table_state: Option<CsvTableState>,
+    table_view: bool,
 
     file_detail: FileDetail,
     file_version_id: Option<String>,
     object: RawObject,
     path: String,
+    preview_config: PreviewConfig,
+    follow: bool,
+    hyperlink: bool,
 
     view_state: ViewState,
 
@@ -32,6 +50,7 @@ enum ViewState {
     #[default]
     Default,
     SaveDialog(InputDialogState),
+    SaveConfirmDialog(SaveConfirmDialogState),
 }
 
 impl ObjectPreviewPage {
@@ -41,25 +60,51 @@ impl ObjectPreviewPage {
         object: RawObject,
         path: String,
         preview_config: PreviewConfig,
+        offset: Option<(usize, usize)>,
         tx: Sender,
     ) -> Self {
-        let (state, msg) = TextPreviewState::new(&file_detail, &object, preview_config.highlight);
+        let (mut state, msg) = TextPreviewState::new(
+            &file_detail,
+            &object,
+            preview_config.highlight,
+            preview_config.hyperlink,
+            ScrollLinesOptions::new(preview_config.number, preview_config.wrap)
+                .wrap_width(preview_config.wrap_width)
+                .min_content_width(preview_config.min_content_width),
+            offset.unwrap_or_default(),
+        );
         if let Some(msg) = msg {
             tx.send(AppEventType::NotifyWarn(msg));
         }
+        let open_at_end = preview_config.open_at_end || is_log_file(&file_detail.name);
+        if offset.is_none() && open_at_end {
+            state.scroll_lines_state.scroll_to_end();
+        }
+
+        let table_state = build_table_state(&file_detail, &object, &tx);
+        let table_view = table_state.is_some();
 
+        let hyperlink = preview_config.hyperlink;
         Self {
             state,
+            table_state,
+            table_view,
             object,
             file_detail,
             file_version_id,
             path,
+            preview_config,
+            follow: false,
+            hyperlink,
             view_state: ViewState::Default,
             tx,
         }
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) {
+        if matches!(self.view_state, ViewState::Default) && self.handle_table_key(key) {
+            return;
+        }
         match self.view_state {
             ViewState::Default => match key {
                 key_code!(KeyCode::Esc) => {
@@ -68,35 +113,70 @@ impl ObjectPreviewPage {
                 key_code!(KeyCode::Backspace) => {
                     self.tx.send(AppEventType::CloseCurrentPage);
                 }
-                key_code_char!('j') => {
+                key_code_char!('j') | key_code!(KeyCode::Down) => {
                     self.state.scroll_lines_state.scroll_forward();
                 }
-                key_code_char!('k') => {
+                key_code_char!('k') | key_code!(KeyCode::Up) => {
                     self.state.scroll_lines_state.scroll_backward();
                 }
-                key_code_char!('f') => {
+                key_code_char!('f') | key_code!(KeyCode::PageDown) => {
                     self.state.scroll_lines_state.scroll_page_forward();
                 }
-                key_code_char!('b') => {
+                key_code_char!('b') | key_code!(KeyCode::PageUp) => {
                     self.state.scroll_lines_state.scroll_page_backward();
                 }
-                key_code_char!('g') => {
+                key_code_char!('g') | key_code!(KeyCode::Home) => {
                     self.state.scroll_lines_state.scroll_to_top();
                 }
-                key_code_char!('G') => {
+                key_code_char!('G') | key_code!(KeyCode::End) => {
                     self.state.scroll_lines_state.scroll_to_end();
                 }
-                key_code_char!('h') => {
+                key_code_char!('h') | key_code!(KeyCode::Left) => {
                     self.state.scroll_lines_state.scroll_left();
                 }
-                key_code_char!('l') => {
+                key_code_char!('l') | key_code!(KeyCode::Right) => {
                     self.state.scroll_lines_state.scroll_right();
                 }
+                key_code_char!('0') => {
+                    self.state.scroll_lines_state.scroll_to_line_start();
+                }
+                key_code_char!('$') => {
+                    self.state.scroll_lines_state.scroll_to_line_end();
+                }
+                key_code_char!('W') => {
+                    self.state.scroll_lines_state.scroll_word_right();
+                }
+                key_code_char!('B') => {
+                    self.state.scroll_lines_state.scroll_word_left();
+                }
+                key_code_char!('%') => {
+                    self.jump_to_matching_bracket();
+                }
                 key_code_char!('w') => {
                     self.state.scroll_lines_state.toggle_wrap();
+                    self.keep_options();
                 }
                 key_code_char!('n') => {
                     self.state.scroll_lines_state.toggle_number();
+                    self.keep_options();
+                }
+                key_code_char!('F') => {
+                    self.toggle_follow();
+                }
+                key_code_char!('u') => {
+                    self.toggle_hyperlink();
+                }
+                key_code_char!('x') => {
+                    self.toggle_raw_encoded();
+                }
+                key_code_char!('t') => {
+                    self.toggle_table_view();
+                }
+                key_code_char!('c') => {
+                    self.copy_contents();
+                }
+                key_code_char!('R') => {
+                    self.refresh();
                 }
                 key_code_char!('s') => {
                     self.download();
@@ -124,15 +204,49 @@ impl ObjectPreviewPage {
                     state.handle_key_event(key);
                 }
             },
+            ViewState::SaveConfirmDialog(ref mut state) => match key {
+                key_code!(KeyCode::Esc) | key_code!(KeyCode::Backspace) => {
+                    self.close_save_dialog();
+                }
+                key_code!(KeyCode::Enter) => {
+                    let state = state.clone();
+                    self.apply_save_confirm(state);
+                }
+                key_code_char!('j') => {
+                    state.select_next();
+                }
+                key_code_char!('k') => {
+                    state.select_prev();
+                }
+                key_code_char!('?') => {
+                    self.tx.send(AppEventType::OpenHelp);
+                }
+                _ => {}
+            },
         }
     }
 
     pub fn render(&mut self, f: &mut Frame, area: Rect) {
-        let preview = TextPreview::new(
-            self.file_detail.name.as_str(),
-            self.file_version_id.as_deref(),
-        );
-        f.render_stateful_widget(preview, area, &mut self.state);
+        match &mut self.table_state {
+            Some(table_state) if self.table_view => {
+                let title = match &self.file_version_id {
+                    Some(version_id) => format!(
+                        "Preview [{} (Version ID: {})] (table)",
+                        self.file_detail.name, version_id
+                    ),
+                    None => format!("Preview [{}] (table)", self.file_detail.name),
+                };
+                let table = CsvTable::default().block(Block::bordered().title(title));
+                f.render_stateful_widget(table, area, table_state);
+            }
+            _ => {
+                let preview = TextPreview::new(
+                    self.file_detail.name.as_str(),
+                    self.file_version_id.as_deref(),
+                );
+                f.render_stateful_widget(preview, area, &mut self.state);
+            }
+        }
 
         if let ViewState::SaveDialog(state) = &mut self.view_state {
             let save_dialog = InputDialog::default().title("Save As").max_width(40);
@@ -141,30 +255,125 @@ impl ObjectPreviewPage {
             let (cursor_x, cursor_y) = state.cursor();
             f.set_cursor(cursor_x, cursor_y);
         }
+
+        if let ViewState::SaveConfirmDialog(state) = &self.view_state {
+            let save_confirm_dialog = SaveConfirmDialog::new(state);
+            f.render_widget(save_confirm_dialog, area);
+        }
     }
 
-    pub fn helps(&self) -> Vec<String> {
-        let helps: &[(&[&str], &str)] = match self.view_state {
+    fn help_groups(&self) -> &'static [HelpGroup<'static>] {
+        match self.view_state {
             ViewState::Default => &[
-                (&["Esc", "Ctrl-c"], "Quit app"),
-                (&["j/k"], "Scroll forward/backward"),
-                (&["f/b"], "Scroll page forward/backward"),
-                (&["g/G"], "Scroll to top/end"),
-                (&["h/l"], "Scroll left/right"),
-                (&["w"], "Toggle wrap"),
-                (&["n"], "Toggle number"),
-                (&["Backspace"], "Close preview"),
-                (&["s"], "Download object"),
-                (&["S"], "Download object as"),
+                (
+                    "General",
+                    &[(
+                        &["Esc", "Ctrl-c"],
+                        "Quit app",
+                        Some(key_event!(KeyCode::Esc)),
+                    )],
+                ),
+                (
+                    "Preview",
+                    &[
+                        (&["j/k", "Down/Up"], "Scroll forward/backward", None),
+                        (&["f/b", "PgDn/PgUp"], "Scroll page forward/backward", None),
+                        (&["g/G", "Home/End"], "Scroll to top/end", None),
+                        (&["h/l", "Left/Right"], "Scroll left/right", None),
+                        (&["0/$"], "Scroll to line start/end", None),
+                        (&["B/W"], "Scroll word left/right", None),
+                        (
+                            &["%"],
+                            "Jump to matching bracket of top line",
+                            Some(key_event!(KeyCode::Char('%'))),
+                        ),
+                        (&["w"], "Toggle wrap", Some(key_event!(KeyCode::Char('w')))),
+                        (
+                            &["n"],
+                            "Toggle number",
+                            Some(key_event!(KeyCode::Char('n'))),
+                        ),
+                        (
+                            &["R", "Ctrl-r"],
+                            "Reload object",
+                            Some(key_event!(KeyCode::Char('R'))),
+                        ),
+                        (
+                            &["F"],
+                            "Toggle follow mode",
+                            Some(key_event!(KeyCode::Char('F'))),
+                        ),
+                        (
+                            &["u"],
+                            "Toggle hyperlinks",
+                            Some(key_event!(KeyCode::Char('u'))),
+                        ),
+                        (
+                            &["x"],
+                            "Toggle raw encoded content",
+                            Some(key_event!(KeyCode::Char('x'))),
+                        ),
+                        (
+                            &["t"],
+                            "Toggle table view",
+                            Some(key_event!(KeyCode::Char('t'))),
+                        ),
+                        (
+                            &["Backspace"],
+                            "Close preview",
+                            Some(key_event!(KeyCode::Backspace)),
+                        ),
+                    ],
+                ),
+                (
+                    "Copy",
+                    &[(
+                        &["c"],
+                        "Copy contents to clipboard",
+                        Some(key_event!(KeyCode::Char('c'))),
+                    )],
+                ),
+                (
+                    "Operations",
+                    &[
+                        (
+                            &["s"],
+                            "Download object",
+                            Some(key_event!(KeyCode::Char('s'))),
+                        ),
+                        (
+                            &["S"],
+                            "Download object as",
+                            Some(key_event!(KeyCode::Char('S'))),
+                        ),
+                    ],
+                ),
             ],
             ViewState::SaveDialog(_) => &[
-                (&["Ctrl-c"], "Quit app"),
-                (&["Esc"], "Close save dialog"),
-                (&["Enter"], "Download object"),
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                ("Navigation", &[(&["Esc"], "Close save dialog", None)]),
+                ("Operations", &[(&["Enter"], "Download object", None)]),
             ],
-        };
+            ViewState::SaveConfirmDialog(_) => &[
+                ("General", &[(&["Ctrl-c"], "Quit app", None)]),
+                (
+                    "Navigation",
+                    &[
+                        (&["Esc", "Backspace"], "Close dialog", None),
+                        (&["j/k"], "Select item", None),
+                    ],
+                ),
+                ("Operations", &[(&["Enter"], "Apply selected action", None)]),
+            ],
+        }
+    }
+
+    pub fn helps(&self) -> Vec<(String, Vec<String>)> {
+        build_helps(self.help_groups())
+    }
 
-        build_helps(helps)
+    pub fn commands(&self) -> Vec<PaletteCommand> {
+        build_commands(self.help_groups())
     }
 
     pub fn short_helps(&self) -> Vec<(String, usize)> {
@@ -182,6 +391,12 @@ impl ObjectPreviewPage {
                 (&["Enter"], "Download", 1),
                 (&["?"], "Help", 0),
             ],
+            ViewState::SaveConfirmDialog(_) => &[
+                (&["Esc"], "Close", 2),
+                (&["j/k"], "Select", 3),
+                (&["Enter"], "Apply", 1),
+                (&["?"], "Help", 0),
+            ],
         };
 
         build_short_helps(helps)
@@ -189,6 +404,193 @@ impl ObjectPreviewPage {
 }
 
 impl ObjectPreviewPage {
+    pub fn file_detail(&self) -> &FileDetail {
+        &self.file_detail
+    }
+
+    pub fn file_version_id(&self) -> Option<&str> {
+        self.file_version_id.as_deref()
+    }
+
+    pub fn offset(&self) -> (usize, usize) {
+        self.state.scroll_lines_state.offset()
+    }
+
+    pub fn hyperlinks(&self) -> &[Hyperlink] {
+        match self.view_state {
+            ViewState::Default => self.state.hyperlinks(),
+            // the dialog may be rendered over the links
+            _ => &[],
+        }
+    }
+
+    pub fn update_object(&mut self, object: RawObject) {
+        if object.bytes == self.object.bytes {
+            let msg = "No new content".to_string();
+            self.tx.send(AppEventType::NotifyInfo(msg));
+            return;
+        }
+        let (old_len, new_len) = (self.object.bytes.len(), object.bytes.len());
+
+        let highlight = self.preview_config.highlight;
+        let hyperlink = self.hyperlink;
+        if let Some(msg) = self
+            .state
+            .update(&self.file_detail, &object, highlight, hyperlink)
+        {
+            self.tx.send(AppEventType::NotifyWarn(msg));
+        }
+        self.file_detail.size_byte = object.bytes.len();
+        if self.table_state.is_some() {
+            self.table_state = build_table_state(&self.file_detail, &object, &self.tx);
+        }
+        self.object = object;
+
+        if self.follow {
+            self.state.scroll_lines_state.scroll_to_end();
+        }
+
+        // the object may have been truncated or rewritten, e.g. a rotated log
+        let msg = if new_len > old_len {
+            format!("Loaded {} new bytes", new_len - old_len)
+        } else if new_len < old_len {
+            format!("Object shrank by {} bytes, reloaded", old_len - new_len)
+        } else {
+            "Object changed, reloaded".to_string()
+        };
+        self.tx.send(AppEventType::NotifyInfo(msg));
+    }
+
+    /// Keeps the toggled options for the subsequent previews in the session.
+    fn keep_options(&self) {
+        let options = self.state.scroll_lines_state.options();
+        self.tx.send(AppEventType::SetPreviewOptions(
+            options.number,
+            options.wrap,
+        ));
+    }
+
+    fn jump_to_matching_bracket(&mut self) {
+        if let Err(msg) = self.state.jump_to_matching_bracket() {
+            self.tx.send(AppEventType::NotifyWarn(msg));
+        }
+    }
+
+    fn toggle_follow(&mut self) {
+        self.follow = !self.follow;
+        if self.follow {
+            self.state.scroll_lines_state.scroll_to_end();
+        }
+        let msg = if self.follow {
+            "Follow mode enabled"
+        } else {
+            "Follow mode disabled"
+        };
+        self.tx.send(AppEventType::NotifyInfo(msg.into()));
+    }
+
+    fn toggle_hyperlink(&mut self) {
+        self.hyperlink = !self.hyperlink;
+        let highlight = self.preview_config.highlight;
+        // the warning about highlighting has been already notified when opened
+        let _ = self
+            .state
+            .update(&self.file_detail, &self.object, highlight, self.hyperlink);
+        let msg = if self.hyperlink {
+            "Hyperlinks enabled"
+        } else {
+            "Hyperlinks disabled"
+        };
+        self.tx.send(AppEventType::NotifyInfo(msg.into()));
+    }
+
+    // the keys to scroll are applied to the table instead of the lines while it is shown
+    fn handle_table_key(&mut self, key: KeyEvent) -> bool {
+        let Some(table_state) = self.table_state.as_mut().filter(|_| self.table_view) else {
+            return false;
+        };
+        match key {
+            key_code_char!('j') | key_code!(KeyCode::Down) => table_state.scroll_forward(),
+            key_code_char!('k') | key_code!(KeyCode::Up) => table_state.scroll_backward(),
+            key_code_char!('f') | key_code!(KeyCode::PageDown) => table_state.scroll_page_forward(),
+            key_code_char!('b') | key_code!(KeyCode::PageUp) => table_state.scroll_page_backward(),
+            key_code_char!('g') | key_code!(KeyCode::Home) => table_state.scroll_to_top(),
+            key_code_char!('G') | key_code!(KeyCode::End) => table_state.scroll_to_end(),
+            key_code_char!('h') | key_code!(KeyCode::Left) => table_state.scroll_left(),
+            key_code_char!('l') | key_code!(KeyCode::Right) => table_state.scroll_right(),
+            key_code_char!('0') => table_state.scroll_to_first_column(),
+            key_code_char!('$') => table_state.scroll_to_last_column(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn toggle_table_view(&mut self) {
+        if self.table_state.is_none() {
+            let msg = "Table view is available only for CSV objects".to_string();
+            self.tx.send(AppEventType::NotifyInfo(msg));
+            return;
+        }
+        self.table_view = !self.table_view;
+    }
+
+    fn toggle_raw_encoded(&mut self) {
+        if self.file_detail.content_encoding.is_none() {
+            let msg = "Object has no content encoding".to_string();
+            self.tx.send(AppEventType::NotifyInfo(msg));
+            return;
+        }
+        let raw_encoded = !self.state.raw_encoded();
+        self.state.set_raw_encoded(raw_encoded);
+        let highlight = self.preview_config.highlight;
+        if let Some(msg) =
+            self.state
+                .update(&self.file_detail, &self.object, highlight, self.hyperlink)
+        {
+            self.tx.send(AppEventType::NotifyWarn(msg));
+        }
+    }
+
+    fn copy_contents(&self) {
+        let Some(value) = self.state.text() else {
+            let msg = "Only text contents can be copied to clipboard".to_string();
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        };
+        let size = value.len();
+        if size > MAX_COPY_CONTENTS_BYTES {
+            let msg = format!(
+                "Object is too large to copy to clipboard (max {})",
+                format_size_byte(MAX_COPY_CONTENTS_BYTES)
+            );
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        let name = if self.is_partial() || self.state.truncated() {
+            // only the loaded (or decoded) part is copied
+            format!("first {} bytes of a partial preview", size)
+        } else {
+            format!("contents ({} bytes)", size)
+        };
+        self.tx.send(AppEventType::CopyToClipboard(name, value));
+    }
+
+    pub fn refresh(&self) {
+        if self.file_version_id.is_some() {
+            let msg = "Cannot reload a specific version".to_string();
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        if self.is_partial() {
+            let msg = "Cannot reload a partially loaded object".to_string();
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        let file_detail = self.file_detail.clone();
+        self.tx
+            .send(AppEventType::PreviewRefreshObject(file_detail));
+    }
+
     fn open_save_dialog(&mut self) {
         self.view_state = ViewState::SaveDialog(InputDialogState::default());
     }
@@ -197,7 +599,34 @@ impl ObjectPreviewPage {
         self.view_state = ViewState::Default;
     }
 
+    pub fn open_save_confirm_dialog(&mut self, input: String, renamed_input: String) {
+        self.view_state =
+            ViewState::SaveConfirmDialog(SaveConfirmDialogState::new(input, renamed_input));
+    }
+
+    fn apply_save_confirm(&mut self, state: SaveConfirmDialogState) {
+        match state.selected() {
+            SaveConfirmAction::Overwrite => {
+                self.send_download_as(state.input().into(), true);
+            }
+            SaveConfirmAction::Rename => {
+                self.send_download_as(state.renamed_input().into(), false);
+            }
+            SaveConfirmAction::Cancel => {
+                self.close_save_dialog();
+            }
+        }
+    }
+
     fn download(&self) {
+        if self.is_partial() {
+            // only the first part has been downloaded, so download the whole object again
+            let file_detail = self.file_detail.clone();
+            let version_id = self.file_version_id.clone();
+            self.tx
+                .send(AppEventType::DetailDownloadObject(file_detail, version_id));
+            return;
+        }
         // object has been already downloaded, so send completion event to save file
         let obj = self.object.clone();
         let path = self.path.clone();
@@ -210,14 +639,61 @@ impl ObjectPreviewPage {
             return;
         }
 
+        self.send_download_as(input, false);
+    }
+
+    fn send_download_as(&self, input: String, overwrite: bool) {
         let file_detail = self.file_detail.clone();
         let version_id = self.file_version_id.clone();
         self.tx.send(AppEventType::PreviewDownloadObjectAs(
             file_detail,
             input,
             version_id,
+            overwrite,
         ));
     }
+
+    fn is_partial(&self) -> bool {
+        self.object.bytes.len() < self.file_detail.size_byte
+    }
+}
+
+fn build_table_state(
+    file_detail: &FileDetail,
+    object: &RawObject,
+    tx: &Sender,
+) -> Option<CsvTableState> {
+    if !is_csv(&file_detail.content_type, &file_detail.name) {
+        return None;
+    }
+    let bytes = match file_detail
+        .content_encoding
+        .as_deref()
+        .and_then(ContentEncoding::parse)
+    {
+        None => Cow::Borrowed(&object.bytes),
+        // the failure and the truncation are notified by the text preview
+        Some(Ok(encoding)) => {
+            let decoded = encoding
+                .decode(&object.bytes, MAX_DECODED_SIZE_BYTES)
+                .ok()?;
+            Cow::Owned(decoded.bytes)
+        }
+        Some(Err(_)) => return None,
+    };
+    let csv = Csv::parse(&String::from_utf8_lossy(&bytes), MAX_TABLE_ROWS)?;
+    if csv.truncated {
+        let msg = format!(
+            "Only the first {} rows are shown in the table",
+            MAX_TABLE_ROWS
+        );
+        tx.send(AppEventType::NotifyInfo(msg));
+    }
+    Some(CsvTableState::new(csv))
+}
+
+fn is_log_file(name: &str) -> bool {
+    name.ends_with(".log")
 }
 
 #[cfg(test)]
@@ -226,8 +702,15 @@ mod tests {
 
     use super::*;
     use chrono::{DateTime, Local, NaiveDateTime};
+    use flate2::{write::GzEncoder, Compression};
     use itertools::Itertools;
-    use ratatui::{backend::TestBackend, buffer::Buffer, style::Color, Terminal};
+    use ratatui::{
+        backend::TestBackend,
+        buffer::Buffer,
+        style::{Color, Modifier},
+        Terminal,
+    };
+    use std::io::Write;
 
     fn object(ss: &[&str]) -> RawObject {
         RawObject {
@@ -251,8 +734,15 @@ mod tests {
             let object = object(&preview);
             let file_path = "file.txt".to_string();
             let preview_config = PreviewConfig::default();
-            let mut page =
-                ObjectPreviewPage::new(file_detail, None, object, file_path, preview_config, tx);
+            let mut page = ObjectPreviewPage::new(
+                file_detail,
+                None,
+                object,
+                file_path,
+                preview_config,
+                None,
+                tx,
+            );
             let area = Rect::new(0, 0, 30, 10);
             page.render(f, area);
         })?;
@@ -279,6 +769,128 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_render_empty_content() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = setup_terminal()?;
+
+        terminal.draw(|f| {
+            let file_detail = FileDetail {
+                size_byte: 0,
+                ..file_detail()
+            };
+            let object = RawObject { bytes: Vec::new() };
+            let file_path = "file.txt".to_string();
+            let preview_config = PreviewConfig::default();
+            let mut page = ObjectPreviewPage::new(
+                file_detail,
+                None,
+                object,
+                file_path,
+                preview_config,
+                None,
+                tx,
+            );
+            let area = Rect::new(0, 0, 30, 10);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌Preview [file.txt]──────────┐",
+            "│ (empty file — 0 bytes)     │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "└────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            (2..24, [1]) => fg: Color::DarkGray,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_with_hyperlinks() -> std::io::Result<()> {
+        let (tx, _rx) = event::new();
+        let mut terminal = setup_terminal()?;
+
+        let file_detail = file_detail();
+        let preview = [
+            "Visit https://example.com/ now",
+            "Docs: https://example.com/docs/stu/index.html.",
+        ];
+        let object = object(&preview);
+        let file_path = "file.txt".to_string();
+        let preview_config = PreviewConfig::default();
+        let mut page = ObjectPreviewPage::new(
+            file_detail,
+            None,
+            object,
+            file_path,
+            preview_config,
+            None,
+            tx,
+        );
+        terminal.draw(|f| {
+            let area = Rect::new(0, 0, 30, 10);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌Preview [file.txt]──────────┐",
+            "│ 1 Visit                    │",
+            "│   https://example.com/ now │",
+            "│ 2 Docs:                    │",
+            "│   https://example.com/docs │",
+            "│   /stu/index.html.         │",
+            "│                            │",
+            "│                            │",
+            "│                            │",
+            "└────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            ([2], [1, 3]) => fg: Color::DarkGray,
+            (4..24, [2]) => fg: Color::Blue, modifier: Modifier::UNDERLINED,
+            (4..28, [4]) => fg: Color::Blue, modifier: Modifier::UNDERLINED,
+            (4..19, [5]) => fg: Color::Blue, modifier: Modifier::UNDERLINED,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        let url1 = "https://example.com/";
+        let url2 = "https://example.com/docs/stu/index.html";
+        let link = |x, y, width, url: &str| Hyperlink {
+            x,
+            y,
+            width,
+            url: url.into(),
+        };
+        let expected = vec![
+            link(4, 2, 20, url1),
+            link(4, 4, 24, url2),
+            link(4, 5, 15, url2),
+        ];
+        assert_eq!(page.hyperlinks(), expected);
+
+        page.toggle_hyperlink();
+        terminal.draw(|f| {
+            let area = Rect::new(0, 0, 30, 10);
+            page.render(f, area);
+        })?;
+        assert!(page.hyperlinks().is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn test_render_with_scroll() -> std::io::Result<()> {
         let (tx, _) = event::new();
@@ -290,8 +902,15 @@ mod tests {
             let object = object(&preview);
             let file_path = "file.txt".to_string();
             let preview_config = PreviewConfig::default();
-            let mut page =
-                ObjectPreviewPage::new(file_detail, None, object, file_path, preview_config, tx);
+            let mut page = ObjectPreviewPage::new(
+                file_detail,
+                None,
+                object,
+                file_path,
+                preview_config,
+                None,
+                tx,
+            );
             let area = Rect::new(0, 0, 30, 10);
             page.render(f, area);
         })?;
@@ -334,8 +953,15 @@ mod tests {
             let object = object(&preview);
             let file_path = "file.txt".to_string();
             let preview_config = PreviewConfig::default();
-            let mut page =
-                ObjectPreviewPage::new(file_detail, None, object, file_path, preview_config, tx);
+            let mut page = ObjectPreviewPage::new(
+                file_detail,
+                None,
+                object,
+                file_path,
+                preview_config,
+                None,
+                tx,
+            );
             page.open_save_dialog();
             let area = Rect::new(0, 0, 30, 10);
             page.render(f, area);
@@ -370,6 +996,375 @@ mod tests {
             .unwrap()
     }
 
+    #[test]
+    fn test_render_small_area() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = Terminal::new(TestBackend::new(3, 3))?;
+
+        // should not panic
+        terminal.draw(|f| {
+            let file_detail = file_detail();
+            let object = object(&["Hello, world!", "This is a test file."]);
+            let file_path = "file.txt".to_string();
+            let preview_config = PreviewConfig::default();
+            let mut page = ObjectPreviewPage::new(
+                file_detail,
+                None,
+                object,
+                file_path,
+                preview_config,
+                None,
+                tx,
+            );
+            let area = Rect::new(0, 0, 3, 3);
+            page.render(f, area);
+        })?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_jump_to_matching_bracket() {
+        let (tx, rx) = event::new_without_terminal();
+        let new_page = |ss: &[&str]| {
+            ObjectPreviewPage::new(
+                file_detail(),
+                None,
+                object(ss),
+                "file.txt".to_string(),
+                PreviewConfig::default(),
+                None,
+                tx.clone(),
+            )
+        };
+
+        let mut page = new_page(&["{", r#"  "a": ["#, "    1", "  ]", "}"]);
+        page.handle_key(KeyEvent::from(KeyCode::Char('%')));
+        assert_eq!(page.offset(), (4, 0));
+        page.handle_key(KeyEvent::from(KeyCode::Char('%')));
+        assert_eq!(page.offset(), (0, 0));
+
+        // unmatched brackets do not scroll to the end
+        let mut page = new_page(&["(", "a", "b"]);
+        page.handle_key(KeyEvent::from(KeyCode::Char('%')));
+        assert_eq!(page.offset(), (0, 0));
+        assert!(
+            matches!(rx.recv(), AppEventType::NotifyWarn(msg) if msg == "No matching bracket for `(`")
+        );
+    }
+
+    #[test]
+    fn test_toggled_options_are_kept_for_next_preview() {
+        let (tx, rx) = event::new_without_terminal();
+        let new_page = |preview_config: PreviewConfig| {
+            ObjectPreviewPage::new(
+                file_detail(),
+                None,
+                object(&["Hello, world!"]),
+                "file.txt".to_string(),
+                preview_config,
+                None,
+                tx.clone(),
+            )
+        };
+
+        let mut page = new_page(PreviewConfig::default());
+        assert!(page.state.scroll_lines_state.options().wrap);
+        page.handle_key(KeyEvent::from(KeyCode::Char('w')));
+
+        let AppEventType::SetPreviewOptions(number, wrap) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        let preview_config = PreviewConfig {
+            number,
+            wrap,
+            ..PreviewConfig::default()
+        };
+        let page = new_page(preview_config);
+        let options = page.state.scroll_lines_state.options();
+        assert!(options.number);
+        assert!(!options.wrap);
+    }
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_render_gzip_content() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = Terminal::new(TestBackend::new(40, 4))?;
+
+        terminal.draw(|f| {
+            let file_detail = FileDetail {
+                content_encoding: Some("gzip".to_string()),
+                ..file_detail()
+            };
+            let object = RawObject {
+                bytes: gzip(b"Hello, world!"),
+            };
+            let file_path = "file.txt".to_string();
+            let preview_config = PreviewConfig::default();
+            let mut page = ObjectPreviewPage::new(
+                file_detail,
+                None,
+                object,
+                file_path,
+                preview_config,
+                None,
+                tx,
+            );
+            let area = Rect::new(0, 0, 40, 4);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌Preview [file.txt] (gzip decoded)─────┐",
+            "│ 1 Hello, world!                      │",
+            "│                                      │",
+            "└──────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            ([2], [1]) => fg: Color::DarkGray,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_toggle_raw_encoded_content() {
+        let (tx, rx) = event::new_without_terminal();
+        let file_detail = FileDetail {
+            content_encoding: Some("gzip".to_string()),
+            ..file_detail()
+        };
+        let first_line =
+            |page: &ObjectPreviewPage| page.state.scroll_lines_state.lines()[0].to_string();
+
+        let object = RawObject {
+            bytes: gzip(b"Hello, world!"),
+        };
+        let mut page = ObjectPreviewPage::new(
+            file_detail.clone(),
+            None,
+            object,
+            "file.txt".to_string(),
+            PreviewConfig::default(),
+            None,
+            tx.clone(),
+        );
+        assert_eq!(first_line(&page), "Hello, world!");
+
+        page.handle_key(KeyEvent::from(KeyCode::Char('x')));
+        assert!(first_line(&page).starts_with("00000000: 1f8b"));
+
+        page.handle_key(KeyEvent::from(KeyCode::Char('x')));
+        assert_eq!(first_line(&page), "Hello, world!");
+
+        // the truncated stream falls back to hex
+        let mut bytes = gzip(b"Hello, world!");
+        bytes.truncate(bytes.len() / 2);
+        let page = ObjectPreviewPage::new(
+            file_detail,
+            None,
+            RawObject { bytes },
+            "file.txt".to_string(),
+            PreviewConfig::default(),
+            None,
+            tx,
+        );
+        assert!(first_line(&page).starts_with("00000000: 1f8b"));
+        let AppEventType::NotifyWarn(msg) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        assert_eq!(
+            msg,
+            "Failed to decode gzip content (truncated or corrupt), showing as hex"
+        );
+    }
+
+    #[test]
+    fn test_copy_decoded_contents() {
+        let (tx, rx) = event::new_without_terminal();
+        let bytes = gzip(b"Hello,\nworld!");
+        let file_detail = FileDetail {
+            size_byte: bytes.len(),
+            content_encoding: Some("gzip".to_string()),
+            ..file_detail()
+        };
+        let object = RawObject { bytes };
+        let mut page = ObjectPreviewPage::new(
+            file_detail,
+            None,
+            object,
+            "file.txt".to_string(),
+            PreviewConfig::default(),
+            None,
+            tx,
+        );
+
+        page.handle_key(KeyEvent::from(KeyCode::Char('c')));
+        let AppEventType::CopyToClipboard(name, value) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        assert_eq!(name, "contents (13 bytes)");
+        assert_eq!(value, "Hello,\nworld!");
+
+        // the raw content is shown as hex
+        page.handle_key(KeyEvent::from(KeyCode::Char('x')));
+        page.handle_key(KeyEvent::from(KeyCode::Char('c')));
+        let AppEventType::NotifyWarn(msg) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        assert_eq!(msg, "Only text contents can be copied to clipboard");
+    }
+
+    #[test]
+    fn test_copy_contents_of_partial_preview() {
+        let (tx, rx) = event::new_without_terminal();
+        let file_detail = FileDetail {
+            size_byte: 1024,
+            ..file_detail()
+        };
+        let mut page = ObjectPreviewPage::new(
+            file_detail,
+            None,
+            object(&["Hello,", "world!"]),
+            "file.txt".to_string(),
+            PreviewConfig::default(),
+            None,
+            tx,
+        );
+
+        page.handle_key(KeyEvent::from(KeyCode::Char('c')));
+        let AppEventType::CopyToClipboard(name, value) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        assert_eq!(name, "first 13 bytes of a partial preview");
+        assert_eq!(value, "Hello,\nworld!");
+    }
+
+    #[test]
+    fn test_update_object() {
+        let (tx, rx) = event::new_without_terminal();
+        let file_detail = FileDetail {
+            size_byte: 13,
+            ..file_detail()
+        };
+        let mut page = ObjectPreviewPage::new(
+            file_detail,
+            None,
+            object(&["Hello,", "world!"]),
+            "file.txt".to_string(),
+            PreviewConfig::default(),
+            None,
+            tx,
+        );
+        let recv_info = || {
+            let AppEventType::NotifyInfo(msg) = rx.recv() else {
+                panic!("unexpected event");
+            };
+            msg
+        };
+
+        page.update_object(object(&["Hello,", "world!"]));
+        assert_eq!(recv_info(), "No new content");
+
+        page.update_object(object(&["Hello,", "world!", "foo"]));
+        assert_eq!(recv_info(), "Loaded 4 new bytes");
+        assert_eq!(page.state.text().unwrap(), "Hello,\nworld!\nfoo");
+
+        // the stale lines are not kept when the object is truncated
+        page.update_object(object(&["bar"]));
+        assert_eq!(recv_info(), "Object shrank by 14 bytes, reloaded");
+        assert_eq!(page.state.text().unwrap(), "bar");
+        assert_eq!(page.file_detail().size_byte, 3);
+
+        page.update_object(object(&["baz"]));
+        assert_eq!(recv_info(), "Object changed, reloaded");
+        assert_eq!(page.state.text().unwrap(), "baz");
+    }
+
+    #[test]
+    fn test_toggle_table_view() {
+        let (tx, rx) = event::new_without_terminal();
+        let new_page = |name: &str| {
+            let file_detail = FileDetail {
+                name: name.to_string(),
+                ..file_detail()
+            };
+            ObjectPreviewPage::new(
+                file_detail,
+                None,
+                object(&["id,name", "1,foo", "2,bar"]),
+                name.to_string(),
+                PreviewConfig::default(),
+                None,
+                tx.clone(),
+            )
+        };
+
+        let mut page = new_page("data.csv");
+        assert!(page.table_view);
+        page.handle_key(KeyEvent::from(KeyCode::Char('t')));
+        assert!(!page.table_view);
+        page.handle_key(KeyEvent::from(KeyCode::Char('t')));
+        assert!(page.table_view);
+
+        let mut page = new_page("file.txt");
+        assert!(!page.table_view);
+        page.handle_key(KeyEvent::from(KeyCode::Char('t')));
+        assert!(!page.table_view);
+        let AppEventType::NotifyInfo(msg) = rx.recv() else {
+            panic!("unexpected event");
+        };
+        assert_eq!(msg, "Table view is available only for CSV objects");
+    }
+
+    #[test]
+    fn test_arrow_keys_scroll_table_view() {
+        let (tx, _) = event::new_without_terminal();
+        let rows: Vec<String> = (0..50).map(|i| format!("{},foo", i)).collect();
+        let lines: Vec<&str> = ["id,name"]
+            .into_iter()
+            .chain(rows.iter().map(String::as_str))
+            .collect();
+        let file_detail = FileDetail {
+            name: "data.csv".to_string(),
+            ..file_detail()
+        };
+        let mut page = ObjectPreviewPage::new(
+            file_detail,
+            None,
+            object(&lines),
+            "data.csv".to_string(),
+            PreviewConfig::default(),
+            None,
+            tx,
+        );
+        assert!(page.table_view);
+        let mut terminal = setup_terminal().unwrap();
+        let mut render = |page: &mut ObjectPreviewPage| {
+            terminal.draw(|f| page.render(f, f.size())).unwrap();
+        };
+
+        // the hidden text is not scrolled
+        page.handle_key(KeyEvent::from(KeyCode::Down));
+        page.handle_key(KeyEvent::from(KeyCode::Right));
+        page.handle_key(KeyEvent::from(KeyCode::Char('t')));
+        render(&mut page);
+        assert_eq!(page.state.scroll_lines_state.offset(), (0, 0));
+
+        page.handle_key(KeyEvent::from(KeyCode::Down));
+        render(&mut page);
+        assert_eq!(page.state.scroll_lines_state.offset(), (1, 0));
+    }
+
     fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
         let backend = TestBackend::new(30, 10);
         let mut terminal = Terminal::new(backend)?;
@@ -385,10 +1380,15 @@ mod tests {
             e_tag: "bef684de-a260-48a4-8178-8a535ecccadb".to_string(),
             content_type: "text/plain".to_string(),
             storage_class: "STANDARD".to_string(),
+            encryption: None,
+            kms_key_id: None,
+            bucket_key_enabled: None,
+            content_encoding: None,
             key: "file.txt".to_string(),
             s3_uri: "s3://bucket-1/file.txt".to_string(),
             arn: "arn:aws:s3:::bucket-1/file.txt".to_string(),
             object_url: "https://bucket-1.s3.ap-northeast-1.amazonaws.com/file.txt".to_string(),
+            restore_status: None,
         }
     }
 }
@@ -1,13 +1,16 @@
 use crate::{
     config::PreviewConfig,
+    error::AppError,
     event::Sender,
-    object::{BucketItem, FileDetail, FileVersion, ObjectItem, RawObject},
+    object::{BucketItem, FileDetail, FileVersion, ObjectItem, ObjectKey, RawObject},
     pages::{
         bucket_list::BucketListPage, help::HelpPage, initializing::InitializingPage,
-        object_detail::ObjectDetailPage, object_list::ObjectListPage,
-        object_preview::ObjectPreviewPage,
+        object_denied::ObjectDeniedPage, object_detail::ObjectDetailPage,
+        object_list::ObjectListPage, object_preview::ObjectPreviewPage,
+        recent_objects::RecentObjectsPage,
     },
-    widget::ScrollListState,
+    recent::RecentObject,
+    widget::{PaletteCommand, ScrollListState},
 };
 
 #[derive(Debug)]
@@ -16,7 +19,9 @@ pub enum Page {
     BucketList(Box<BucketListPage>),
     ObjectList(Box<ObjectListPage>),
     ObjectDetail(Box<ObjectDetailPage>),
+    ObjectDenied(Box<ObjectDeniedPage>),
     ObjectPreview(Box<ObjectPreviewPage>),
+    RecentObjects(Box<RecentObjectsPage>),
     Help(Box<HelpPage>),
 }
 
@@ -29,22 +34,48 @@ impl Page {
         Self::BucketList(Box::new(BucketListPage::new(bucket_items, tx)))
     }
 
-    pub fn of_object_list(object_items: Vec<ObjectItem>, tx: Sender) -> Self {
-        Self::ObjectList(Box::new(ObjectListPage::new(object_items, tx)))
+    pub fn of_object_list(
+        object_items: Vec<ObjectItem>,
+        object_key: ObjectKey,
+        tx: Sender,
+    ) -> Self {
+        Self::ObjectList(Box::new(ObjectListPage::new(object_items, object_key, tx)))
     }
 
     pub fn of_object_detail(
         file_detail: FileDetail,
         file_versions: Vec<FileVersion>,
+        bucket_name: String,
         object_items: Vec<ObjectItem>,
         list_state: ScrollListState,
+        list_width_percentage: u16,
         tx: Sender,
     ) -> Self {
         Self::ObjectDetail(Box::new(ObjectDetailPage::new(
             file_detail,
             file_versions,
+            bucket_name,
+            object_items,
+            list_state,
+            list_width_percentage,
+            tx,
+        )))
+    }
+
+    pub fn of_object_denied(
+        key: String,
+        error: AppError,
+        object_items: Vec<ObjectItem>,
+        list_state: ScrollListState,
+        list_width_percentage: u16,
+        tx: Sender,
+    ) -> Self {
+        Self::ObjectDenied(Box::new(ObjectDeniedPage::new(
+            key,
+            error,
             object_items,
             list_state,
+            list_width_percentage,
             tx,
         )))
     }
@@ -55,6 +86,7 @@ impl Page {
         object: RawObject,
         path: String,
         preview_config: PreviewConfig,
+        offset: Option<(usize, usize)>,
         tx: Sender,
     ) -> Self {
         Self::ObjectPreview(Box::new(ObjectPreviewPage::new(
@@ -63,14 +95,45 @@ impl Page {
             object,
             path,
             preview_config,
+            offset,
             tx,
         )))
     }
 
-    pub fn of_help(helps: Vec<String>, tx: Sender) -> Self {
+    pub fn of_recent_objects(items: Vec<RecentObject>, tx: Sender) -> Self {
+        Self::RecentObjects(Box::new(RecentObjectsPage::new(items, tx)))
+    }
+
+    pub fn of_help(helps: Vec<(String, Vec<String>)>, tx: Sender) -> Self {
         Self::Help(Box::new(HelpPage::new(helps, tx)))
     }
 
+    pub fn commands(&self) -> Vec<PaletteCommand> {
+        match self {
+            Self::Initializing(_) | Self::Help(_) => Vec::new(),
+            Self::BucketList(page) => page.commands(),
+            Self::ObjectList(page) => page.commands(),
+            Self::ObjectDetail(page) => page.commands(),
+            Self::ObjectDenied(page) => page.commands(),
+            Self::ObjectPreview(page) => page.commands(),
+            Self::RecentObjects(page) => page.commands(),
+        }
+    }
+
+    /// Applies to the pages with a list, the others are left as is.
+    pub fn set_wrap_selection(&mut self, wrap: bool) {
+        match self {
+            Self::BucketList(page) => page.set_wrap_selection(wrap),
+            Self::ObjectList(page) => page.set_wrap_selection(wrap),
+            Self::RecentObjects(page) => page.set_wrap_selection(wrap),
+            Self::Initializing(_)
+            | Self::ObjectDetail(_)
+            | Self::ObjectDenied(_)
+            | Self::ObjectPreview(_)
+            | Self::Help(_) => {}
+        }
+    }
+
     pub fn as_bucket_list(&self) -> &BucketListPage {
         match self {
             Self::BucketList(page) => page,
@@ -78,6 +141,13 @@ impl Page {
         }
     }
 
+    pub fn as_mut_bucket_list(&mut self) -> &mut BucketListPage {
+        match self {
+            Self::BucketList(page) => &mut *page,
+            page => panic!("Page is not BucketList: {:?}", page),
+        }
+    }
+
     pub fn as_object_list(&self) -> &ObjectListPage {
         match self {
             Self::ObjectList(page) => page,
@@ -85,6 +155,20 @@ impl Page {
         }
     }
 
+    pub fn as_mut_object_list(&mut self) -> &mut ObjectListPage {
+        match self {
+            Self::ObjectList(page) => &mut *page,
+            page => panic!("Page is not ObjectList: {:?}", page),
+        }
+    }
+
+    pub fn as_object_detail(&self) -> &ObjectDetailPage {
+        match self {
+            Self::ObjectDetail(page) => page,
+            page => panic!("Page is not ObjectDetail: {:?}", page),
+        }
+    }
+
     pub fn as_mut_object_detail(&mut self) -> &mut ObjectDetailPage {
         match self {
             Self::ObjectDetail(page) => &mut *page,
@@ -103,12 +187,14 @@ impl Page {
 #[derive(Debug)]
 pub struct PageStack {
     stack: Vec<Page>,
+    wrap_selection: bool,
 }
 
 impl PageStack {
-    pub fn new(tx: Sender) -> PageStack {
+    pub fn new(tx: Sender, wrap_selection: bool) -> PageStack {
         PageStack {
             stack: vec![Page::of_initializing(tx)],
+            wrap_selection,
         }
     }
 
@@ -116,7 +202,8 @@ impl PageStack {
         self.stack.len()
     }
 
-    pub fn push(&mut self, page: Page) {
+    pub fn push(&mut self, mut page: Page) {
+        page.set_wrap_selection(self.wrap_selection);
         self.stack.push(page);
     }
 
@@ -140,7 +227,14 @@ impl PageStack {
         self.stack.first().unwrap()
     }
 
-    pub fn iter(&self) -> std::slice::Iter<Page> {
+    pub fn iter(&self) -> std::slice::Iter<'_, Page> {
         self.stack.iter()
     }
+
+    pub fn find_object_list_mut(&mut self, object_key: &ObjectKey) -> Option<&mut ObjectListPage> {
+        self.stack.iter_mut().find_map(|page| match page {
+            Page::ObjectList(page) if page.object_key() == object_key => Some(&mut **page),
+            _ => None,
+        })
+    }
 }
@@ -0,0 +1,307 @@
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::ListItem,
+    Frame,
+};
+
+use crate::{
+    event::{AppEventType, Sender},
+    key_code, key_code_char, key_event,
+    pages::util::{build_commands, build_helps, build_short_helps, HelpGroup},
+    recent::RecentObject,
+    ui::common::format_datetime,
+    util::{pad_to_width, truncate_start_with_ellipsis},
+    widget::{PaletteCommand, ScrollList, ScrollListState},
+};
+
+const SELECTED_COLOR: Color = Color::Cyan;
+const SELECTED_ITEM_TEXT_COLOR: Color = Color::Black;
+const STALE_ITEM_TEXT_COLOR: Color = Color::DarkGray;
+
+const STALE_MARK: &str = " (deleted)";
+
+#[derive(Debug)]
+pub struct RecentObjectsPage {
+    items: Vec<RecentObject>,
+
+    list_state: ScrollListState,
+    tx: Sender,
+}
+
+impl RecentObjectsPage {
+    pub fn new(items: Vec<RecentObject>, tx: Sender) -> Self {
+        let items_len = items.len();
+        Self {
+            items,
+            list_state: ScrollListState::new(items_len),
+            tx,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        match key {
+            key_code!(KeyCode::Esc) => {
+                self.tx.send(AppEventType::Quit);
+            }
+            key_code!(KeyCode::Backspace) => {
+                self.tx.send(AppEventType::CloseCurrentPage);
+            }
+            key_code!(KeyCode::Enter) if self.non_empty() => {
+                self.open_selected();
+            }
+            key_code_char!('j') if self.non_empty() => {
+                self.list_state.select_next();
+            }
+            key_code_char!('k') if self.non_empty() => {
+                self.list_state.select_prev();
+            }
+            key_code_char!('g') if self.non_empty() => {
+                self.list_state.select_first();
+            }
+            key_code_char!('G') if self.non_empty() => {
+                self.list_state.select_last();
+            }
+            key_code_char!('f') if self.non_empty() => {
+                self.list_state.select_next_page();
+            }
+            key_code_char!('b') if self.non_empty() => {
+                self.list_state.select_prev_page();
+            }
+            key_code_char!('D') if self.non_empty() => {
+                self.remove_selected();
+            }
+            key_code_char!('?') => {
+                self.tx.send(AppEventType::OpenHelp);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let offset = self.list_state.offset;
+        let selected = self.list_state.selected;
+
+        let list_items = build_list_items(&self.items, offset, selected, area);
+
+        let list = ScrollList::new(list_items);
+        f.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn help_groups(&self) -> &'static [HelpGroup<'static>] {
+        &[
+            (
+                "General",
+                &[(
+                    &["Esc", "Ctrl-c"],
+                    "Quit app",
+                    Some(key_event!(KeyCode::Esc)),
+                )],
+            ),
+            (
+                "Navigation",
+                &[
+                    (&["j/k"], "Select item", None),
+                    (&["g/G"], "Go to top/bottom", None),
+                    (&["f"], "Scroll page forward", None),
+                    (&["b"], "Scroll page backward", None),
+                    (&["Enter"], "Open object", Some(key_event!(KeyCode::Enter))),
+                    (
+                        &["Backspace"],
+                        "Close recent objects",
+                        Some(key_event!(KeyCode::Backspace)),
+                    ),
+                ],
+            ),
+            (
+                "Operations",
+                &[(
+                    &["D"],
+                    "Remove from recent objects",
+                    Some(key_event!(KeyCode::Char('D'))),
+                )],
+            ),
+        ]
+    }
+
+    pub fn helps(&self) -> Vec<(String, Vec<String>)> {
+        build_helps(self.help_groups())
+    }
+
+    pub fn commands(&self) -> Vec<PaletteCommand> {
+        build_commands(self.help_groups())
+    }
+
+    pub fn short_helps(&self) -> Vec<(String, usize)> {
+        let helps: &[(&[&str], &str, usize)] = &[
+            (&["Esc"], "Quit", 0),
+            (&["j/k"], "Select", 1),
+            (&["Enter"], "Open", 2),
+            (&["D"], "Remove", 3),
+            (&["Backspace"], "Close", 4),
+            (&["?"], "Help", 0),
+        ];
+        build_short_helps(helps)
+    }
+}
+
+impl RecentObjectsPage {
+    fn open_selected(&self) {
+        let item = self.current_selected_item();
+        if item.stale {
+            let msg = "Object no longer exists, press 'D' to remove it".to_string();
+            self.tx.send(AppEventType::NotifyWarn(msg));
+            return;
+        }
+        self.tx.send(AppEventType::OpenRecentObject(
+            item.bucket.clone(),
+            item.key.clone(),
+        ));
+    }
+
+    fn remove_selected(&mut self) {
+        let item = self.items.remove(self.list_state.selected);
+        let index = self
+            .list_state
+            .selected
+            .min(self.items.len().saturating_sub(1));
+        self.list_state.update_total(self.items.len(), index);
+        self.tx
+            .send(AppEventType::RemoveRecentObject(item.bucket, item.key));
+    }
+
+    pub fn set_wrap_selection(&mut self, wrap: bool) {
+        self.list_state.set_wrap(wrap);
+    }
+
+    fn current_selected_item(&self) -> &RecentObject {
+        &self.items[self.list_state.selected]
+    }
+
+    fn non_empty(&self) -> bool {
+        !self.items.is_empty()
+    }
+}
+
+fn build_list_items(
+    items: &[RecentObject],
+    offset: usize,
+    selected: usize,
+    area: Rect,
+) -> Vec<ListItem<'static>> {
+    let show_item_count = (area.height as usize).saturating_sub(2 /* border */);
+    items
+        .iter()
+        .skip(offset)
+        .take(show_item_count)
+        .enumerate()
+        .map(|(idx, item)| {
+            let selected = idx + offset == selected;
+            build_list_item(item, selected, area.width)
+        })
+        .collect()
+}
+
+fn build_list_item(item: &RecentObject, selected: bool, width: u16) -> ListItem<'static> {
+    let date = format_datetime(&item.accessed_at);
+    let date_w: usize = 19;
+    let mark = if item.stale { STALE_MARK } else { "" };
+    let name_w: usize =
+        (width as usize).saturating_sub(date_w + 6 /* spaces */ + 4 /* border + pad */);
+
+    // the end of the key is more distinguishable
+    let name = format!("{}/{}", item.bucket, item.key);
+    let name = truncate_start_with_ellipsis(&name, name_w.saturating_sub(mark.len()));
+    let name = pad_to_width(&format!("{}{}", name, mark), name_w);
+
+    let line = Line::from(vec![
+        " ".into(),
+        name.into(),
+        "    ".into(),
+        format!("{:<date_w$}", date, date_w = date_w).into(),
+        " ".into(),
+    ]);
+    let line = if item.stale {
+        line.fg(STALE_ITEM_TEXT_COLOR)
+    } else {
+        line
+    };
+
+    let style = if selected {
+        Style::default()
+            .bg(SELECTED_COLOR)
+            .fg(SELECTED_ITEM_TEXT_COLOR)
+    } else {
+        Style::default()
+    };
+    ListItem::new(line).style(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{event, set_cells};
+
+    use super::*;
+    use chrono::{DateTime, Local, NaiveDateTime};
+    use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    #[test]
+    fn test_render() -> std::io::Result<()> {
+        let (tx, _) = event::new();
+        let mut terminal = setup_terminal()?;
+
+        terminal.draw(|f| {
+            let items = vec![
+                recent_object("bucket1", "file1.txt", false),
+                recent_object("bucket2", "dir/file2.txt", true),
+                recent_object("bucket1", "very/long/path/to/the/file3.txt", false),
+            ];
+            let mut page = RecentObjectsPage::new(items, tx);
+            let area = Rect::new(0, 0, 60, 6);
+            page.render(f, area);
+        })?;
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌─────────────────────────────────────────────────── 1 / 3 ┐",
+            "│  bucket1/file1.txt                  2024-01-02 13:01:02  │",
+            "│  bucket2/dir/file2.txt (deleted)    2024-01-02 13:01:02  │",
+            "│  …ery/long/path/to/the/file3.txt    2024-01-02 13:01:02  │",
+            "│                                                          │",
+            "└──────────────────────────────────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            (2..58, [1]) => bg: Color::Cyan, fg: Color::Black,
+            (2..58, [2]) => fg: Color::DarkGray,
+        }
+
+        terminal.backend().assert_buffer(&expected);
+
+        Ok(())
+    }
+
+    fn setup_terminal() -> std::io::Result<Terminal<TestBackend>> {
+        let backend = TestBackend::new(60, 6);
+        let mut terminal = Terminal::new(backend)?;
+        terminal.clear()?;
+        Ok(terminal)
+    }
+
+    fn recent_object(bucket: &str, key: &str, stale: bool) -> RecentObject {
+        RecentObject {
+            bucket: bucket.to_string(),
+            key: key.to_string(),
+            accessed_at: parse_datetime("2024-01-02 13:01:02"),
+            stale,
+        }
+    }
+
+    fn parse_datetime(s: &str) -> DateTime<Local> {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+    }
+}
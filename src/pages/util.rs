@@ -1,14 +1,29 @@
-pub fn build_helps(helps: &[(&[&str], &str)]) -> Vec<String> {
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use crate::widget::PaletteCommand;
+
+/// Keys, description, and the key sent when it is run from the command palette, if it can be
+pub type Help<'a> = (&'a [&'a str], &'a str, Option<KeyEvent>);
+
+/// Group name and its helps
+pub type HelpGroup<'a> = (&'a str, &'a [Help<'a>]);
+
+pub fn build_helps(helps: &[HelpGroup]) -> Vec<(String, Vec<String>)> {
     helps
         .iter()
-        .map(|(keys, desc)| {
-            let key_maps = keys
+        .map(|(group, helps)| {
+            let helps = helps
                 .iter()
-                .map(|key| format!("<{}>", key))
-                .collect::<Vec<String>>()
-                .join(" ");
-            let help = format!("{}: {}", key_maps, desc);
-            help
+                .map(|(keys, desc, _)| {
+                    let key_maps = keys
+                        .iter()
+                        .map(|key| format!("<{}>", key))
+                        .collect::<Vec<String>>()
+                        .join(" ");
+                    format!("{}: {}", key_maps, desc)
+                })
+                .collect();
+            (group.to_string(), helps)
         })
         .collect()
 }
@@ -27,3 +42,95 @@ pub fn build_short_helps(helps: &[(&[&str], &str, usize)]) -> Vec<(String, usize
         })
         .collect()
 }
+
+/// Builds the commands of the palette from the helps that can be run from it.
+///
+/// Showing help is added to the commands, unless there are none (e.g. in a dialog).
+pub fn build_commands(helps: &[HelpGroup]) -> Vec<PaletteCommand> {
+    let mut commands: Vec<PaletteCommand> = helps
+        .iter()
+        .flat_map(|(_, helps)| helps.iter())
+        .filter_map(|(keys, desc, key)| {
+            key.map(|key| PaletteCommand {
+                keys: keys[0].to_string(),
+                desc: desc.to_string(),
+                key,
+            })
+        })
+        .collect();
+    if !commands.is_empty() {
+        commands.push(PaletteCommand {
+            keys: "?".to_string(),
+            desc: "Show help".to_string(),
+            key: KeyEvent::from(KeyCode::Char('?')),
+        });
+    }
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use crate::key_event;
+
+    use super::*;
+
+    #[test]
+    fn test_build_commands() {
+        let helps: &[HelpGroup] = &[
+            (
+                "General",
+                &[(
+                    &["Esc", "Ctrl-c"],
+                    "Quit app",
+                    Some(key_event!(KeyCode::Esc)),
+                )],
+            ),
+            (
+                "Navigation",
+                &[
+                    (&["j/k"], "Select item", None),
+                    (&["Enter"], "Open", Some(key_event!(KeyCode::Enter))),
+                    (
+                        &["Ctrl-r"],
+                        "Refresh",
+                        Some(key_event!(KeyCode::Char('r'), Ctrl)),
+                    ),
+                ],
+            ),
+        ];
+        let commands: Vec<(String, String, KeyEvent)> = build_commands(helps)
+            .into_iter()
+            .map(|c| (c.keys, c.desc, c.key))
+            .collect();
+        assert_eq!(
+            commands,
+            [
+                (
+                    "Esc".to_string(),
+                    "Quit app".to_string(),
+                    KeyEvent::from(KeyCode::Esc)
+                ),
+                (
+                    "Enter".to_string(),
+                    "Open".to_string(),
+                    KeyEvent::from(KeyCode::Enter)
+                ),
+                (
+                    "Ctrl-r".to_string(),
+                    "Refresh".to_string(),
+                    KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL)
+                ),
+                (
+                    "?".to_string(),
+                    "Show help".to_string(),
+                    KeyEvent::from(KeyCode::Char('?'))
+                ),
+            ]
+        );
+
+        let helps: &[HelpGroup] = &[("General", &[(&["Ctrl-c"], "Quit app", None)])];
+        assert!(build_commands(helps).is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+use std::fs;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{AppError, Result},
+    file::save_binary,
+};
+
+const MAX_RECENT_OBJECTS: usize = 50;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RecentObject {
+    pub bucket: String,
+    pub key: String,
+    pub accessed_at: DateTime<Local>,
+    // the object was not found when it was opened last time
+    #[serde(default)]
+    pub stale: bool,
+}
+
+/// The most recently opened objects, newest first.
+#[derive(Debug, Default)]
+pub struct RecentObjects {
+    items: Vec<RecentObject>,
+    file_path: Option<String>,
+}
+
+impl RecentObjects {
+    /// Loads the list from the file. If the file does not exist or is broken, the list starts empty.
+    pub fn load(file_path: String) -> RecentObjects {
+        let items = match fs::read(&file_path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                tracing::warn!("failed to parse recent objects file: {}", e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        RecentObjects {
+            items,
+            file_path: Some(file_path),
+        }
+    }
+
+    pub fn items(&self) -> &[RecentObject] {
+        &self.items
+    }
+
+    pub fn add(&mut self, bucket: String, key: String, accessed_at: DateTime<Local>) {
+        self.remove(&bucket, &key);
+        let item = RecentObject {
+            bucket,
+            key,
+            accessed_at,
+            stale: false,
+        };
+        self.items.insert(0, item);
+        self.items.truncate(MAX_RECENT_OBJECTS);
+    }
+
+    pub fn mark_stale(&mut self, bucket: &str, key: &str) {
+        if let Some(item) = self
+            .items
+            .iter_mut()
+            .find(|item| item.bucket == bucket && item.key == key)
+        {
+            item.stale = true;
+        }
+    }
+
+    pub fn remove(&mut self, bucket: &str, key: &str) {
+        self.items
+            .retain(|item| item.bucket != bucket || item.key != key);
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.file_path else {
+            return Ok(());
+        };
+        let bytes = serde_json::to_vec_pretty(&self.items)
+            .map_err(|e| AppError::new("Failed to serialize recent objects", e))?;
+        save_binary(path, &bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn time(sec: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 1, 2, 13, 4, sec).unwrap()
+    }
+
+    fn keys(recent: &RecentObjects) -> Vec<(&str, &str)> {
+        recent
+            .items()
+            .iter()
+            .map(|item| (item.bucket.as_str(), item.key.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_add_dedupes_and_moves_to_front() {
+        let mut recent = RecentObjects::default();
+        recent.add("b1".into(), "a.txt".into(), time(1));
+        recent.add("b1".into(), "dir/b.txt".into(), time(2));
+        recent.add("b2".into(), "a.txt".into(), time(3));
+        recent.add("b1".into(), "a.txt".into(), time(4));
+
+        assert_eq!(
+            keys(&recent),
+            vec![("b1", "a.txt"), ("b2", "a.txt"), ("b1", "dir/b.txt")]
+        );
+        assert_eq!(recent.items()[0].accessed_at, time(4));
+    }
+
+    #[test]
+    fn test_add_caps_length() {
+        let mut recent = RecentObjects::default();
+        for i in 0..(MAX_RECENT_OBJECTS + 5) {
+            recent.add("b".into(), format!("{}.txt", i), time(0));
+        }
+
+        assert_eq!(recent.items().len(), MAX_RECENT_OBJECTS);
+        assert_eq!(
+            recent.items()[0].key,
+            format!("{}.txt", MAX_RECENT_OBJECTS + 4)
+        );
+    }
+
+    #[test]
+    fn test_mark_stale_and_remove() {
+        let mut recent = RecentObjects::default();
+        recent.add("b".into(), "a.txt".into(), time(1));
+        recent.add("b".into(), "b.txt".into(), time(2));
+
+        recent.mark_stale("b", "a.txt");
+        assert!(!recent.items()[0].stale);
+        assert!(recent.items()[1].stale);
+
+        // opening it again clears the mark
+        recent.add("b".into(), "a.txt".into(), time(3));
+        assert!(!recent.items()[0].stale);
+
+        recent.remove("b", "a.txt");
+        assert_eq!(keys(&recent), vec![("b", "b.txt")]);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!(
+            "stu-test-{}-save-and-load-recent-objects.json",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let path = path.to_string_lossy().to_string();
+
+        let mut recent = RecentObjects::load(path.clone());
+        assert!(recent.items().is_empty());
+        recent.add("b".into(), "a.txt".into(), time(1));
+        recent.mark_stale("b", "a.txt");
+        recent.save().unwrap();
+
+        let loaded = RecentObjects::load(path.clone());
+        fs::remove_file(path).unwrap();
+        assert_eq!(loaded.items(), recent.items());
+    }
+}
@@ -1,33 +1,45 @@
-use ratatui::{backend::Backend, crossterm::event::KeyCode, Terminal};
-use std::io::Result;
+use ratatui::{
+    backend::Backend,
+    crossterm::event::{KeyCode, KeyEvent},
+    Terminal,
+};
+use std::io::{Result, Write};
 
 use crate::{
     app::{App, Notification},
     event::{AppEventType, Receiver},
     key_code, key_code_char,
     pages::page::Page,
-    ui,
+    ui::{self, HyperlinkWriter},
 };
 
-pub async fn run<B: Backend>(
+pub async fn run<B: Backend + Write>(
     app: &mut App,
     terminal: &mut Terminal<B>,
     rx: Receiver,
-) -> Result<()> {
+) -> Result<Option<String>> {
+    let mut hyperlink_writer = HyperlinkWriter::default();
     loop {
-        terminal.draw(|f| ui::render(f, app))?;
+        let frame = terminal.draw(|f| ui::render(f, app))?;
+        let outputs = hyperlink_writer.prepare(app.hyperlinks(), frame.buffer);
+        hyperlink_writer.write(terminal.backend_mut(), outputs)?;
+        // the visible range is determined by rendering
+        app.prefetch_object_details();
 
         let event = rx.recv();
         tracing::debug!("event received: {:?}", event);
 
         match event {
             AppEventType::Quit => {
-                return Ok(());
+                return Ok(None);
+            }
+            AppEventType::QuitWithLocation(location) => {
+                return Ok(Some(location));
             }
             AppEventType::Key(key) => {
                 if matches!(key, key_code_char!('c', Ctrl)) {
                     // Exit regardless of status
-                    return Ok(());
+                    return Ok(None);
                 }
 
                 if app.app_view_state.is_loading {
@@ -35,9 +47,18 @@ pub async fn run<B: Backend>(
                     continue;
                 }
 
+                let dialog_opened = app.app_view_state.command_palette.is_some()
+                    || app.app_view_state.region_picker.is_some();
+                if matches!(key, key_code_char!('g', Ctrl)) && !dialog_opened {
+                    // before quitting on the error, as the bucket may be in another region
+                    app.clear_notification();
+                    app.open_region_picker();
+                    continue;
+                }
+
                 if matches!(app.app_view_state.notification, Notification::Error(_)) {
                     if matches!(app.page_stack.current_page(), Page::Initializing(_)) {
-                        return Ok(());
+                        return Ok(None);
                     }
                     // Clear message and cancel key input
                     app.clear_notification();
@@ -57,14 +78,65 @@ pub async fn run<B: Backend>(
                     continue;
                 }
 
-                match app.page_stack.current_page_mut() {
-                    Page::Initializing(page) => page.handle_key(key),
-                    Page::BucketList(page) => page.handle_key(key),
-                    Page::ObjectList(page) => page.handle_key(key),
-                    Page::ObjectDetail(page) => page.handle_key(key),
-                    Page::ObjectPreview(page) => page.handle_key(key),
-                    Page::Help(page) => page.handle_key(key),
+                if let Some(state) = &mut app.app_view_state.command_palette {
+                    match key {
+                        key_code!(KeyCode::Esc) => {
+                            app.close_command_palette();
+                        }
+                        key_code!(KeyCode::Enter) => {
+                            if let Some(command_key) = app.run_selected_command() {
+                                handle_page_key(app, command_key);
+                            }
+                        }
+                        key_code!(KeyCode::Down) | key_code_char!('n', Ctrl) => {
+                            state.select_next();
+                        }
+                        key_code!(KeyCode::Up) | key_code_char!('p', Ctrl) => {
+                            state.select_prev();
+                        }
+                        _ => {
+                            state.handle_key_event(key);
+                        }
+                    }
+                    continue;
+                }
+
+                if let Some(state) = &mut app.app_view_state.region_picker {
+                    match key {
+                        key_code!(KeyCode::Esc) => {
+                            app.close_region_picker();
+                        }
+                        key_code!(KeyCode::Enter) => {
+                            let region = state.selected_region();
+                            app.close_region_picker();
+                            if let Some(region) = region {
+                                app.set_region(region);
+                            }
+                        }
+                        key_code!(KeyCode::Down) | key_code_char!('n', Ctrl) => {
+                            state.select_next();
+                        }
+                        key_code!(KeyCode::Up) | key_code_char!('p', Ctrl) => {
+                            state.select_prev();
+                        }
+                        _ => {
+                            state.handle_key_event(key);
+                        }
+                    }
+                    continue;
+                }
+
+                if matches!(key, key_code_char!('p', Ctrl)) {
+                    app.open_command_palette();
+                    continue;
                 }
+
+                if matches!(key, key_code_char!('r', Ctrl)) {
+                    app.refresh();
+                    continue;
+                }
+
+                handle_page_key(app, key);
             }
             AppEventType::Resize(width, height) => {
                 app.resize(width, height);
@@ -75,11 +147,23 @@ pub async fn run<B: Backend>(
             AppEventType::CompleteInitialize(result) => {
                 app.complete_initialize(result);
             }
+            AppEventType::CompleteSetRegion(client) => {
+                app.complete_set_region(client);
+            }
+            AppEventType::CompleteRefreshObjects(object_key, result) => {
+                app.complete_refresh_objects(object_key, result);
+            }
+            AppEventType::CompleteRefreshObject(result) => {
+                app.complete_refresh_object(result);
+            }
             AppEventType::LoadObjects => {
                 app.load_objects();
             }
-            AppEventType::CompleteLoadObjects(result) => {
-                app.complete_load_objects(result);
+            AppEventType::LoadObjectsPage(object_key, load_id, items) => {
+                app.load_objects_page(object_key, load_id, items);
+            }
+            AppEventType::CompleteLoadObjects(object_key, load_id, result) => {
+                app.complete_load_objects(object_key, load_id, result);
             }
             AppEventType::LoadObject => {
                 app.load_object();
@@ -87,6 +171,9 @@ pub async fn run<B: Backend>(
             AppEventType::CompleteLoadObject(result) => {
                 app.complete_load_object(result);
             }
+            AppEventType::CompletePrefetchObjectDetail(map_key, result) => {
+                app.complete_prefetch_object_detail(map_key, result);
+            }
             AppEventType::DownloadObject(file_detail, version_id) => {
                 app.download_object(file_detail, version_id);
             }
@@ -96,38 +183,95 @@ pub async fn run<B: Backend>(
             AppEventType::CompleteDownloadObject(result) => {
                 app.complete_download_object(result);
             }
-            AppEventType::PreviewObject(file_detail, version_id) => {
-                app.preview_object(file_detail, version_id);
+            AppEventType::DownloadPrefix(name) => {
+                app.download_prefix(name);
+            }
+            AppEventType::CompleteDownloadPrefix(result) => {
+                app.complete_download_prefix(result);
+            }
+            AppEventType::LoadVersionDiff(file_detail, old_version, new_version) => {
+                app.load_version_diff(file_detail, old_version, new_version);
+            }
+            AppEventType::CompleteLoadVersionDiff(result) => {
+                app.complete_load_version_diff(result);
+            }
+            AppEventType::RestoreObject(file_detail, version_id, tier, days) => {
+                app.restore_object(file_detail, version_id, tier, days);
+            }
+            AppEventType::CompleteRestoreObject(result) => {
+                app.complete_restore_object(result);
+            }
+            AppEventType::TransitionStorageClass(file_detail, storage_class) => {
+                app.transition_storage_class(file_detail, storage_class);
+            }
+            AppEventType::CompleteTransitionStorageClass(storage_class, result) => {
+                app.complete_transition_storage_class(storage_class, result);
+            }
+            AppEventType::PreviewObject(file_detail, version_id, limit) => {
+                app.preview_object(file_detail, version_id, limit);
             }
             AppEventType::CompletePreviewObject(result) => {
                 app.complete_preview_object(result);
             }
+            AppEventType::PreviewRefreshObject(file_detail) => {
+                app.preview_refresh_object(file_detail);
+            }
+            AppEventType::CompletePreviewRefreshObject(result) => {
+                app.complete_preview_refresh_object(result);
+            }
             AppEventType::BucketListMoveDown => {
                 app.bucket_list_move_down();
             }
+            AppEventType::LoadBucketAccess(bucket) => {
+                app.load_bucket_access(bucket);
+            }
+            AppEventType::CompleteLoadBucketAccess(result) => {
+                app.complete_load_bucket_access(result);
+            }
             AppEventType::ObjectListMoveDown => {
                 app.object_list_move_down();
             }
             AppEventType::ObjectListMoveUp => {
                 app.object_list_move_up();
             }
+            AppEventType::RevealObject(key) => {
+                app.reveal_object(key);
+            }
+            AppEventType::OpenRecentObjects => {
+                app.open_recent_objects();
+            }
+            AppEventType::ToggleCollapseDirs => {
+                app.toggle_collapse_dirs();
+            }
+            AppEventType::OpenRecentObject(bucket, key) => {
+                app.open_recent_object(bucket, key);
+            }
+            AppEventType::RemoveRecentObject(bucket, key) => {
+                app.remove_recent_object(bucket, key);
+            }
+            AppEventType::ObjectListMoveUpTo(depth) => {
+                app.object_list_move_up_to(depth);
+            }
             AppEventType::BackToBucketList => {
                 app.back_to_bucket_list();
             }
             AppEventType::OpenPreview(file_detail, version_id) => {
                 app.open_preview(file_detail, version_id);
             }
+            AppEventType::OpenPartialPreview(file_detail, version_id) => {
+                app.open_partial_preview(file_detail, version_id);
+            }
             AppEventType::DetailDownloadObject(file_detail, version_id) => {
                 app.detail_download_object(file_detail, version_id);
             }
-            AppEventType::DetailDownloadObjectAs(file_detail, input, version_id) => {
-                app.detail_download_object_as(file_detail, input, version_id);
+            AppEventType::DetailDownloadObjectAs(file_detail, input, version_id, overwrite) => {
+                app.detail_download_object_as(file_detail, input, version_id, overwrite);
             }
             AppEventType::PreviewDownloadObject(obj, path) => {
                 app.preview_download_object(obj, path);
             }
-            AppEventType::PreviewDownloadObjectAs(file_detail, input, version_id) => {
-                app.preview_download_object_as(file_detail, input, version_id);
+            AppEventType::PreviewDownloadObjectAs(file_detail, input, version_id, overwrite) => {
+                app.preview_download_object_as(file_detail, input, version_id, overwrite);
             }
             AppEventType::BucketListOpenManagementConsole => {
                 app.bucket_list_open_management_console();
@@ -144,9 +288,27 @@ pub async fn run<B: Backend>(
             AppEventType::OpenHelp => {
                 app.open_help();
             }
+            AppEventType::SaveListWidthPercentage(percentage) => {
+                app.save_list_width_percentage(percentage);
+            }
+            AppEventType::SetPreviewOptions(number, wrap) => {
+                app.set_preview_options(number, wrap);
+            }
             AppEventType::CopyToClipboard(name, value) => {
                 app.copy_to_clipboard(name, value);
             }
+            AppEventType::CopyPresignedPutUrl(name, content_type) => {
+                app.copy_presigned_put_url(name, content_type);
+            }
+            AppEventType::CompletePresignPut(s3_uri, result) => {
+                app.complete_presign_put(s3_uri, result);
+            }
+            AppEventType::CreateFolder(name) => {
+                app.create_folder(name);
+            }
+            AppEventType::CompleteCreateFolder(object_key, name, result) => {
+                app.complete_create_folder(object_key, name, result);
+            }
             AppEventType::NotifyInfo(msg) => {
                 app.info_notification(msg);
             }
@@ -162,3 +324,16 @@ pub async fn run<B: Backend>(
         }
     }
 }
+
+fn handle_page_key(app: &mut App, key: KeyEvent) {
+    match app.page_stack.current_page_mut() {
+        Page::Initializing(page) => page.handle_key(key),
+        Page::BucketList(page) => page.handle_key(key),
+        Page::ObjectList(page) => page.handle_key(key),
+        Page::ObjectDetail(page) => page.handle_key(key),
+        Page::ObjectPreview(page) => page.handle_key(key),
+        Page::ObjectDenied(page) => page.handle_key(key),
+        Page::RecentObjects(page) => page.handle_key(key),
+        Page::Help(page) => page.handle_key(key),
+    }
+}
@@ -0,0 +1,103 @@
+use std::{
+    io::{stderr, stdout, IsTerminal, Write},
+    panic,
+};
+
+use ratatui::{
+    backend::CrosstermBackend,
+    crossterm::{
+        cursor::Show,
+        execute,
+        terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    },
+    Terminal,
+};
+
+pub type TuiTerminal = Terminal<CrosstermBackend<Box<dyn Write>>>;
+
+pub fn setup() -> anyhow::Result<TuiTerminal> {
+    enable_raw_mode()?;
+    let mut output = tui_output();
+    execute!(output, EnterAlternateScreen)?;
+
+    let backend = CrosstermBackend::new(output);
+    let terminal = Terminal::new(backend)?;
+
+    Ok(terminal)
+}
+
+fn tui_output() -> Box<dyn Write> {
+    // if stdout is captured (e.g. `$(stu)`), draw the UI to stderr instead
+    if stdout().is_terminal() {
+        Box::new(stdout())
+    } else {
+        Box::new(stderr())
+    }
+}
+
+/// Leaves the alternate screen, shows the cursor and disables raw mode.
+///
+/// Errors are ignored, since this is called while shutting down (possibly by a panic) and every step should be tried.
+pub fn restore() {
+    let _ = execute!(tui_output(), LeaveAlternateScreen, Show);
+    let _ = disable_raw_mode();
+}
+
+/// Restores the terminal when dropped, so that it is restored on every return path, including unwinding from a panic.
+pub struct TerminalGuard {
+    restore: fn(),
+}
+
+impl TerminalGuard {
+    pub fn new() -> TerminalGuard {
+        TerminalGuard { restore }
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        (self.restore)();
+    }
+}
+
+/// Restores the terminal before the panic message is printed, otherwise it is not visible in the alternate screen.
+pub fn initialize_panic_handler() {
+    let original_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |panic_info| {
+        restore();
+        original_hook(panic_info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    static RESTORE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    fn count_restore() {
+        RESTORE_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_terminal_guard_restores_on_drop() {
+        let guard = TerminalGuard {
+            restore: count_restore,
+        };
+        assert_eq!(RESTORE_COUNT.load(Ordering::SeqCst), 0);
+
+        drop(guard);
+        assert_eq!(RESTORE_COUNT.load(Ordering::SeqCst), 1);
+
+        let result = std::panic::catch_unwind(|| {
+            let _guard = TerminalGuard {
+                restore: count_restore,
+            };
+            panic!("panic while the guard is alive");
+        });
+        assert!(result.is_err());
+        assert_eq!(RESTORE_COUNT.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,4 +1,6 @@
 pub mod common;
+mod hyperlink;
 mod render;
 
+pub use hyperlink::HyperlinkWriter;
 pub use render::render;
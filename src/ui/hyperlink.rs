@@ -0,0 +1,194 @@
+use std::io::{Result, Write};
+
+use ratatui::{
+    buffer::{Buffer, Cell},
+    crossterm::{
+        cursor::MoveTo,
+        queue,
+        style::{
+            Attribute, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
+        },
+    },
+    style::Modifier,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::widget::{is_link_cell, Hyperlink};
+
+const MODIFIER_ATTRIBUTES: [(Modifier, Attribute); 9] = [
+    (Modifier::BOLD, Attribute::Bold),
+    (Modifier::DIM, Attribute::Dim),
+    (Modifier::ITALIC, Attribute::Italic),
+    (Modifier::UNDERLINED, Attribute::Underlined),
+    (Modifier::SLOW_BLINK, Attribute::SlowBlink),
+    (Modifier::RAPID_BLINK, Attribute::RapidBlink),
+    (Modifier::REVERSED, Attribute::Reverse),
+    (Modifier::HIDDEN, Attribute::Hidden),
+    (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+];
+
+/// Writes the hyperlinks over the drawn frame.
+///
+/// The escape sequences cannot be set to the cells of the buffer, since ratatui calculates the width of the cell including them.
+/// So the link cells are written again directly to the terminal after the frame is drawn.
+#[derive(Debug, Default)]
+pub struct HyperlinkWriter {
+    written: Vec<Hyperlink>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct HyperlinkOutput {
+    x: u16,
+    y: u16,
+    cells: Vec<Cell>,
+    url: Option<String>,
+}
+
+impl HyperlinkWriter {
+    pub fn prepare(&mut self, hyperlinks: &[Hyperlink], buf: &Buffer) -> Vec<HyperlinkOutput> {
+        // the links may be hidden by other widgets rendered over them
+        let hyperlinks: Vec<Hyperlink> = hyperlinks
+            .iter()
+            .filter(|link| {
+                (link.x..link.x + link.width).all(|x| {
+                    buf.area.contains((x, link.y).into()) && is_link_cell(buf.get(x, link.y))
+                })
+            })
+            .cloned()
+            .collect();
+
+        // cells that are not updated by ratatui keep the previous link, so write them again without it
+        let stale = self
+            .written
+            .iter()
+            .filter(|link| !hyperlinks.contains(link))
+            .filter(|link| link.y < buf.area.bottom())
+            .map(|link| HyperlinkOutput {
+                x: link.x,
+                y: link.y,
+                cells: row_cells(buf, link.x, link.y, link.width),
+                url: None,
+            });
+        let current = hyperlinks.iter().map(|link| HyperlinkOutput {
+            x: link.x,
+            y: link.y,
+            cells: row_cells(buf, link.x, link.y, link.width),
+            url: Some(link.url.clone()),
+        });
+        let outputs = stale.chain(current).collect();
+
+        self.written = hyperlinks;
+        outputs
+    }
+
+    pub fn write<W: Write>(&self, w: &mut W, outputs: Vec<HyperlinkOutput>) -> Result<()> {
+        if outputs.is_empty() {
+            return Ok(());
+        }
+        for output in outputs {
+            queue!(w, MoveTo(output.x, output.y))?;
+            if let Some(url) = &output.url {
+                queue!(w, Print(format!("\x1B]8;;{}\x1B\\", url)))?;
+            }
+            let mut to_skip = 0;
+            for cell in &output.cells {
+                if to_skip > 0 {
+                    to_skip -= 1;
+                    continue;
+                }
+                write_cell(w, cell)?;
+                to_skip = cell.symbol().width().saturating_sub(1);
+            }
+            if output.url.is_some() {
+                queue!(w, Print("\x1B]8;;\x1B\\"))?;
+            }
+            queue!(w, SetAttribute(Attribute::Reset), ResetColor)?;
+        }
+        w.flush()
+    }
+}
+
+fn row_cells(buf: &Buffer, x: u16, y: u16, width: u16) -> Vec<Cell> {
+    (x..(x + width).min(buf.area.right()))
+        .map(|x| buf.get(x, y).clone())
+        .collect()
+}
+
+fn write_cell<W: Write>(w: &mut W, cell: &Cell) -> Result<()> {
+    queue!(
+        w,
+        SetAttribute(Attribute::Reset),
+        SetForegroundColor(cell.fg.into()),
+        SetBackgroundColor(cell.bg.into()),
+    )?;
+    for (modifier, attribute) in MODIFIER_ATTRIBUTES {
+        if cell.modifier.contains(modifier) {
+            queue!(w, SetAttribute(attribute))?;
+        }
+    }
+    queue!(w, Print(cell.symbol()))
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{layout::Rect, style::Stylize, text::Line, widgets::Widget};
+
+    use super::*;
+
+    fn link(x: u16, y: u16, width: u16, url: &str) -> Hyperlink {
+        Hyperlink {
+            x,
+            y,
+            width,
+            url: url.into(),
+        }
+    }
+
+    #[test]
+    fn test_prepare() {
+        let mut writer = HyperlinkWriter::default();
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 2));
+        Line::from(vec!["a ".into(), "http://a".blue().underlined()]).render(buf.area, &mut buf);
+
+        let outputs = writer.prepare(&[link(2, 0, 8, "http://a")], &buf);
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].url.as_deref(), Some("http://a"));
+        assert_eq!(outputs[0].cells.len(), 8);
+
+        // same links are written again, because the cells may have been updated
+        let outputs = writer.prepare(&[link(2, 0, 8, "http://a")], &buf);
+        assert_eq!(outputs.len(), 1);
+
+        // the link is no longer rendered
+        let buf = Buffer::empty(Rect::new(0, 0, 10, 2));
+        let outputs = writer.prepare(&[link(2, 0, 8, "http://a")], &buf);
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].url, None);
+        assert_eq!(outputs[0].cells, vec![Cell::default(); 8]);
+
+        let outputs = writer.prepare(&[], &buf);
+        assert!(outputs.is_empty());
+    }
+
+    #[test]
+    fn test_write() {
+        let writer = HyperlinkWriter::default();
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 1));
+        Line::from("http://a".blue().underlined()).render(buf.area, &mut buf);
+        let outputs = vec![HyperlinkOutput {
+            x: 0,
+            y: 0,
+            cells: row_cells(&buf, 0, 0, 8),
+            url: Some("http://a".into()),
+        }];
+
+        let mut out = Vec::new();
+        writer.write(&mut out, outputs).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.starts_with("\x1B[1;1H\x1B]8;;http://a\x1B\\"));
+        assert!(out.contains("\x1B]8;;\x1B\\"));
+    }
+}
@@ -2,7 +2,7 @@ use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Modifier, Stylize},
     text::Line,
-    widgets::{Block, BorderType, Padding, Paragraph},
+    widgets::{Block, BorderType, Padding, Paragraph, Wrap},
     Frame,
 };
 
@@ -11,9 +11,12 @@ use crate::{
     pages::page::Page,
     ui::common::calc_centered_dialog_rect,
     util,
-    widget::{Dialog, Header},
+    widget::{CommandPalette, Dialog, Header, RegionPicker},
 };
 
+const MIN_WIDTH: u16 = 30;
+const MIN_HEIGHT: u16 = 10;
+
 const SHORT_HELP_COLOR: Color = Color::DarkGray;
 const INFO_STATUS_COLOR: Color = Color::Blue;
 const SUCCESS_STATUS_COLOR: Color = Color::Green;
@@ -21,6 +24,11 @@ const WARN_STATUS_COLOR: Color = Color::Yellow;
 const ERROR_STATUS_COLOR: Color = Color::Red;
 
 pub fn render(f: &mut Frame, app: &mut App) {
+    if f.size().width < MIN_WIDTH || f.size().height < MIN_HEIGHT {
+        render_too_small_message(f);
+        return;
+    }
+
     let chunks = Layout::vertical([
         Constraint::Length(header_height(app)),
         Constraint::Min(0),
@@ -31,9 +39,18 @@ pub fn render(f: &mut Frame, app: &mut App) {
     render_header(f, chunks[0], app);
     render_content(f, chunks[1], app);
     render_footer(f, chunks[2], app);
+    render_command_palette(f, chunks[1], app);
+    render_region_picker(f, chunks[1], app);
     render_loading_dialog(f, app);
 }
 
+fn render_too_small_message(f: &mut Frame) {
+    let msg = Paragraph::new("Terminal too small")
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(msg, f.size());
+}
+
 fn header_height(app: &App) -> u16 {
     match app.page_stack.current_page() {
         Page::Help(_) => 0, // Hide header
@@ -56,6 +73,8 @@ fn render_content(f: &mut Frame, area: Rect, app: &mut App) {
         Page::ObjectList(page) => page.render(f, area),
         Page::ObjectDetail(page) => page.render(f, area),
         Page::ObjectPreview(page) => page.render(f, area),
+        Page::ObjectDenied(page) => page.render(f, area),
+        Page::RecentObjects(page) => page.render(f, area),
         Page::Help(page) => page.render(f, area),
     }
 }
@@ -85,6 +104,24 @@ fn render_footer(f: &mut Frame, area: Rect, app: &App) {
     }
 }
 
+fn render_command_palette(f: &mut Frame, area: Rect, app: &mut App) {
+    if let Some(state) = &mut app.app_view_state.command_palette {
+        f.render_stateful_widget(CommandPalette::default(), area, state);
+
+        let (cursor_x, cursor_y) = state.cursor();
+        f.set_cursor(cursor_x, cursor_y);
+    }
+}
+
+fn render_region_picker(f: &mut Frame, area: Rect, app: &mut App) {
+    if let Some(state) = &mut app.app_view_state.region_picker {
+        f.render_stateful_widget(RegionPicker::default(), area, state);
+
+        let (cursor_x, cursor_y) = state.cursor();
+        f.set_cursor(cursor_x, cursor_y);
+    }
+}
+
 fn render_loading_dialog(f: &mut Frame, app: &App) {
     if app.app_view_state.is_loading {
         let loading = build_loading_dialog("Loading...");
@@ -102,28 +139,40 @@ fn build_header(app: &App) -> Header {
         .collect();
     target_pages.pop(); // Remove the last item (current page)
 
-    let breadcrumb: Vec<String> = target_pages
-        .iter()
-        .map(|page| match page {
+    let mut breadcrumb: Vec<String> = Vec::new();
+    let mut prev_collapsed = false;
+    for page in target_pages {
+        let name = match page {
             Page::BucketList(page) => page.current_selected_item().name.clone(),
             Page::ObjectList(page) => page.current_selected_item().name().into(),
             _ => unreachable!(),
-        })
-        .collect();
+        };
+        // the folders passed through by collapsing are shown as one segment (e.g. `a/b/c`)
+        match breadcrumb.last_mut() {
+            Some(last) if prev_collapsed => *last = format!("{}/{}", last, name),
+            _ => breadcrumb.push(name),
+        }
+        prev_collapsed = matches!(page, Page::ObjectList(page) if page.is_collapsed());
+    }
     Header::new(breadcrumb)
+        .anonymous(app.is_anonymous())
+        .requester_pays(app.is_requester_pays())
+        .region(app.region())
 }
 
-fn build_short_help(app: &App, width: u16) -> Paragraph {
+fn build_short_help(app: &App, width: u16) -> Paragraph<'_> {
     let helps = match app.page_stack.current_page() {
         Page::Initializing(page) => page.short_helps(),
         Page::BucketList(page) => page.short_helps(),
         Page::ObjectList(page) => page.short_helps(),
         Page::ObjectDetail(page) => page.short_helps(),
         Page::ObjectPreview(page) => page.short_helps(),
+        Page::ObjectDenied(page) => page.short_helps(),
+        Page::RecentObjects(page) => page.short_helps(),
         Page::Help(page) => page.short_helps(),
     };
     let pad = Padding::horizontal(2);
-    let max_width = (width - pad.left - pad.right) as usize;
+    let max_width = width.saturating_sub(pad.left + pad.right) as usize;
     let help = build_short_help_string(&helps, max_width);
     Paragraph::new(help.fg(SHORT_HELP_COLOR)).block(Block::default().padding(pad))
 }
@@ -134,28 +183,28 @@ fn build_short_help_string(helps: &[(String, usize)], max_width: usize) -> Strin
     ss.join(delimiter)
 }
 
-fn build_info_status(msg: &str) -> Paragraph {
+fn build_info_status(msg: &str) -> Paragraph<'_> {
     Paragraph::new(msg.fg(INFO_STATUS_COLOR))
         .block(Block::default().padding(Padding::horizontal(2)))
 }
 
-fn build_success_status(msg: &str) -> Paragraph {
+fn build_success_status(msg: &str) -> Paragraph<'_> {
     Paragraph::new(msg.add_modifier(Modifier::BOLD).fg(SUCCESS_STATUS_COLOR))
         .block(Block::default().padding(Padding::horizontal(2)))
 }
 
-fn build_warn_status(msg: &str) -> Paragraph {
+fn build_warn_status(msg: &str) -> Paragraph<'_> {
     Paragraph::new(msg.add_modifier(Modifier::BOLD).fg(WARN_STATUS_COLOR))
         .block(Block::default().padding(Padding::horizontal(2)))
 }
 
-fn build_error_status(err: &str) -> Paragraph {
+fn build_error_status(err: &str) -> Paragraph<'_> {
     let err = format!("ERROR: {}", err);
     Paragraph::new(err.add_modifier(Modifier::BOLD).fg(ERROR_STATUS_COLOR))
         .block(Block::default().padding(Padding::horizontal(2)))
 }
 
-fn build_loading_dialog(msg: &str) -> Paragraph {
+fn build_loading_dialog(msg: &str) -> Paragraph<'_> {
     let text = Line::from(msg.add_modifier(Modifier::BOLD));
     Paragraph::new(text).alignment(Alignment::Center).block(
         Block::bordered()
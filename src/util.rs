@@ -1,3 +1,11 @@
+use std::{
+    collections::VecDeque,
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
 pub fn prune_strings_to_fit_width(
     words_with_priority: &[(String, usize)],
     max_width: usize,
@@ -5,9 +13,9 @@ pub fn prune_strings_to_fit_width(
 ) -> Vec<String> {
     let words_total_length = words_with_priority
         .iter()
-        .map(|(s, _)| s.len())
+        .map(|(s, _)| s.width())
         .sum::<usize>();
-    let delimiter_total_length = words_with_priority.len().saturating_sub(1) * delimiter.len();
+    let delimiter_total_length = words_with_priority.len().saturating_sub(1) * delimiter.width();
     let mut total_length = words_total_length + delimiter_total_length;
 
     let mut words_with_priority_with_index: Vec<(usize, &(String, usize))> =
@@ -21,8 +29,7 @@ pub fn prune_strings_to_fit_width(
             break;
         }
         prune.push(*i);
-        total_length -= s.len();
-        total_length -= delimiter.len();
+        total_length = total_length.saturating_sub(s.width() + delimiter.width());
     }
 
     words_with_priority
@@ -72,19 +79,440 @@ pub fn digits(n: usize) -> usize {
 pub fn extension_from_file_name(filename: &str) -> String {
     filename
         .split('.')
-        .last()
+        .next_back()
         .map(|s| s.to_string())
         .unwrap_or_default()
 }
 
-pub fn split_str(s: &str, sp: &str) -> Option<(String, String, String)> {
-    s.find(sp).map(|start| {
-        let mut chars = s.chars();
-        let before = chars.by_ref().take(start).collect::<String>();
-        let matched = chars.by_ref().take(sp.chars().count()).collect::<String>();
-        let after = chars.collect::<String>();
-        (before, matched, after)
-    })
+const HEX_DUMP_WIDTH: usize = 16;
+
+/// Formats `bytes` like `xxd`, 16 bytes per line with the offset and the printable ASCII characters.
+pub fn hex_dump_lines(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(HEX_DUMP_WIDTH)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex = chunk
+                .chunks(2)
+                .map(|pair| {
+                    pair.iter()
+                        .map(|b| format!("{:02x}", b))
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            let hex_w = HEX_DUMP_WIDTH * 2 + HEX_DUMP_WIDTH / 2 - 1;
+            format!("{:08x}: {:<hex_w$}  {}", i * HEX_DUMP_WIDTH, hex, ascii)
+        })
+        .collect()
+}
+
+/// Returns the char indices of the first occurrence of `pattern` in `text`.
+pub fn substring_match_indices(pattern: &str, text: &str) -> Option<Vec<usize>> {
+    let start = text.find(pattern)?;
+    let start = text[..start].chars().count();
+    Some((start..start + pattern.chars().count()).collect())
+}
+
+const ELLIPSIS: char = '…';
+
+/// Truncates `s` so that its display width (full-width characters count as two columns) fits in `max_cols`,
+/// replacing the truncated part with an ellipsis.
+pub fn truncate_with_ellipsis(s: &str, max_cols: usize) -> String {
+    if s.width() <= max_cols {
+        return s.to_string();
+    }
+    if max_cols == 0 {
+        return String::new();
+    }
+
+    let limit = max_cols - 1 /* ellipsis */;
+    let mut width = 0;
+    let mut truncated = String::new();
+    for c in s.chars() {
+        let w = c.width().unwrap_or_default();
+        if width + w > limit {
+            break;
+        }
+        width += w;
+        truncated.push(c);
+    }
+    truncated.push(ELLIPSIS);
+    truncated
+}
+
+/// Same as [`truncate_with_ellipsis`], but truncates the beginning of `s` to keep its end visible.
+pub fn truncate_start_with_ellipsis(s: &str, max_cols: usize) -> String {
+    if s.width() <= max_cols {
+        return s.to_string();
+    }
+    if max_cols == 0 {
+        return String::new();
+    }
+
+    let limit = max_cols - 1 /* ellipsis */;
+    let mut width = 0;
+    let mut chars = Vec::new();
+    for c in s.chars().rev() {
+        let w = c.width().unwrap_or_default();
+        if width + w > limit {
+            break;
+        }
+        width += w;
+        chars.push(c);
+    }
+    std::iter::once(ELLIPSIS)
+        .chain(chars.into_iter().rev())
+        .collect()
+}
+
+/// Pads `s` with spaces on the right up to the display width `cols`.
+pub fn pad_to_width(s: &str, cols: usize) -> String {
+    let pad = cols.saturating_sub(s.width());
+    format!("{}{}", s, " ".repeat(pad))
+}
+
+/// Returns the char indices of `text` matched by `pattern` as a subsequence (case-insensitive).
+pub fn fuzzy_match_indices(pattern: &str, text: &str) -> Option<Vec<usize>> {
+    let mut indices = Vec::new();
+    let mut pattern_chars = pattern.chars().flat_map(char::to_lowercase).peekable();
+    for (i, c) in text.chars().enumerate() {
+        match pattern_chars.peek() {
+            Some(p) if c.to_lowercase().eq(std::iter::once(*p)) => {
+                indices.push(i);
+                pattern_chars.next();
+            }
+            Some(_) => {}
+            None => break,
+        }
+    }
+    pattern_chars.peek().is_none().then_some(indices)
+}
+
+/// Returns the byte ranges of the http/https URLs in `s`.
+///
+/// Trailing punctuation and unbalanced closing brackets are not included, so that URLs in prose are detected as expected.
+pub fn find_urls(s: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut pos = 0;
+    while let Some(i) = s[pos..].find("http").map(|i| i + pos) {
+        let preceded_by_word = s[..i]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric());
+        let rest = &s[i..];
+        let scheme_len = ["https://", "http://"]
+            .iter()
+            .find(|scheme| rest.starts_with(*scheme))
+            .map(|scheme| scheme.len());
+        let (Some(scheme_len), false) = (scheme_len, preceded_by_word) else {
+            pos = i + "http".len();
+            continue;
+        };
+
+        let len = rest
+            .find(|c: char| c.is_whitespace() || c.is_control() || "<>\"'`".contains(c))
+            .unwrap_or(rest.len());
+        let end = i + trim_url_end(&rest[..len]);
+        if end - i > scheme_len {
+            ranges.push(i..end);
+        }
+        pos = end.max(i + scheme_len);
+    }
+    ranges
+}
+
+fn trim_url_end(url: &str) -> usize {
+    let mut url = url;
+    loop {
+        let Some(last) = url.chars().next_back() else {
+            return 0;
+        };
+        let open = match last {
+            ')' => '(',
+            ']' => '[',
+            '}' => '{',
+            '.' | ',' | ';' | ':' | '!' | '?' => {
+                url = &url[..url.len() - 1];
+                continue;
+            }
+            _ => return url.len(),
+        };
+        if url.matches(open).count() >= url.matches(last).count() {
+            return url.len();
+        }
+        url = &url[..url.len() - 1];
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BracketMatch {
+    Line(usize),
+    NoBracket,
+    Unmatched(char),
+}
+
+/// Finds the line of the bracket matching the first bracket on the line `from` that is not closed (or opened) on the same line.
+///
+/// The brackets in double-quoted strings are ignored, assuming that strings do not span lines (as in JSON).
+pub fn find_matching_bracket_line<S: AsRef<str>>(lines: &[S], from: usize) -> BracketMatch {
+    let Some(line) = lines.get(from) else {
+        return BracketMatch::NoBracket;
+    };
+    let brackets = brackets_outside_strings(line.as_ref());
+
+    let mut opens = Vec::new();
+    let mut first_close = None;
+    for (i, c) in brackets.iter().enumerate() {
+        if is_open_bracket(*c) {
+            opens.push(i);
+        } else if opens
+            .last()
+            .is_some_and(|j| pair_bracket(brackets[*j]) == *c)
+        {
+            opens.pop();
+        } else {
+            first_close = Some(i);
+            break;
+        }
+    }
+    let Some(i) = [opens.first().copied(), first_close]
+        .into_iter()
+        .flatten()
+        .min()
+    else {
+        return BracketMatch::NoBracket;
+    };
+
+    let bracket = brackets[i];
+    let found = if is_open_bracket(bracket) {
+        let rest = brackets[i + 1..].iter().map(|c| (from, *c));
+        let following = (from + 1..lines.len()).flat_map(|n| {
+            brackets_outside_strings(lines[n].as_ref())
+                .into_iter()
+                .map(move |c| (n, c))
+        });
+        scan_to_matching_bracket(bracket, rest.chain(following), true)
+    } else {
+        let rest = brackets[..i].iter().rev().map(|c| (from, *c));
+        let preceding = (0..from).rev().flat_map(|n| {
+            brackets_outside_strings(lines[n].as_ref())
+                .into_iter()
+                .rev()
+                .map(move |c| (n, c))
+        });
+        scan_to_matching_bracket(bracket, rest.chain(preceding), false)
+    };
+    match found {
+        Some(n) => BracketMatch::Line(n),
+        None => BracketMatch::Unmatched(bracket),
+    }
+}
+
+// stops at a mismatched bracket, since the brackets after it cannot be matched reliably
+fn scan_to_matching_bracket(
+    bracket: char,
+    brackets: impl Iterator<Item = (usize, char)>,
+    forward: bool,
+) -> Option<usize> {
+    let mut stack = vec![bracket];
+    for (n, c) in brackets {
+        if is_open_bracket(c) == forward {
+            stack.push(c);
+        } else if stack.last().is_some_and(|b| pair_bracket(*b) == c) {
+            stack.pop();
+            if stack.is_empty() {
+                return Some(n);
+            }
+        } else {
+            return None;
+        }
+    }
+    None
+}
+
+fn brackets_outside_strings(s: &str) -> Vec<char> {
+    let mut brackets = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in s.chars() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' | ')' | '[' | ']' | '{' | '}' => brackets.push(c),
+            _ => {}
+        }
+    }
+    brackets
+}
+
+fn is_open_bracket(c: char) -> bool {
+    matches!(c, '(' | '[' | '{')
+}
+
+fn pair_bracket(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        _ => c,
+    }
+}
+
+/// Measures the moving-average throughput from the cumulative byte counts.
+///
+/// Only the samples in the latest `window` are used, so that bursty chunk arrivals are smoothed out.
+#[derive(Debug)]
+pub struct ThroughputMeter {
+    window: Duration,
+    samples: VecDeque<(Instant, usize)>,
+}
+
+impl ThroughputMeter {
+    pub fn new(window: Duration, start: Instant) -> ThroughputMeter {
+        ThroughputMeter {
+            window,
+            samples: VecDeque::from([(start, 0)]),
+        }
+    }
+
+    pub fn record(&mut self, at: Instant, total_bytes: usize) {
+        self.samples.push_back((at, total_bytes));
+        // keep the last sample before the window as the baseline
+        while self.samples.len() > 2 && at.duration_since(self.samples[1].0) >= self.window {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn bytes_per_sec(&self) -> Option<f64> {
+        let (first_at, first_bytes) = self.samples.front()?;
+        let (last_at, last_bytes) = self.samples.back()?;
+        let secs = last_at.duration_since(*first_at).as_secs_f64();
+        if secs <= 0.0 {
+            return None;
+        }
+        Some(last_bytes.saturating_sub(*first_bytes) as f64 / secs)
+    }
+
+    pub fn remaining(&self, total_bytes: usize) -> Option<Duration> {
+        let bytes_per_sec = self.bytes_per_sec().filter(|b| *b > 0.0)?;
+        let (_, current_bytes) = self.samples.back()?;
+        let remaining_bytes = total_bytes.saturating_sub(*current_bytes);
+        Some(Duration::from_secs_f64(
+            remaining_bytes as f64 / bytes_per_sec,
+        ))
+    }
+}
+
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs >= 3600 {
+        format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60)
+    } else if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Converts the JSON value to a YAML document in block style, keeping the order of the keys.
+pub fn json_to_yaml_string(value: &serde_json::Value) -> String {
+    match yaml_inline(value) {
+        Some(s) => s,
+        None => {
+            let mut lines = Vec::new();
+            write_yaml_block(value, 0, &mut lines);
+            lines.join("\n")
+        }
+    }
+}
+
+/// Returns the value written on the same line as its key, or `None` for a non-empty collection.
+fn yaml_inline(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => Some("~".into()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::String(s) => Some(yaml_string(s)),
+        serde_json::Value::Array(vs) if vs.is_empty() => Some("[]".into()),
+        serde_json::Value::Object(m) if m.is_empty() => Some("{}".into()),
+        _ => None,
+    }
+}
+
+fn write_yaml_block(value: &serde_json::Value, indent: usize, lines: &mut Vec<String>) {
+    let pad = " ".repeat(indent);
+    match value {
+        serde_json::Value::Array(vs) => {
+            for v in vs {
+                match yaml_inline(v) {
+                    Some(s) => lines.push(format!("{}- {}", pad, s)),
+                    None => {
+                        // the first line of the nested block follows the dash
+                        let mut nested = Vec::new();
+                        write_yaml_block(v, indent + 2, &mut nested);
+                        let first = nested.remove(0);
+                        lines.push(format!("{}- {}", pad, &first[indent + 2..]));
+                        lines.extend(nested);
+                    }
+                }
+            }
+        }
+        serde_json::Value::Object(m) => {
+            for (k, v) in m {
+                let k = yaml_string(k);
+                match yaml_inline(v) {
+                    Some(s) => lines.push(format!("{}{}: {}", pad, k, s)),
+                    None => {
+                        lines.push(format!("{}{}:", pad, k));
+                        write_yaml_block(v, indent + 2, lines);
+                    }
+                }
+            }
+        }
+        _ => lines.extend(yaml_inline(value)),
+    }
+}
+
+/// Quotes the string unless it is certainly read back as the same string.
+fn yaml_string(s: &str) -> String {
+    let plain = !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.' | '/'))
+        && !s.starts_with([' ', '-', '.'])
+        && !s.ends_with(' ')
+        && s.parse::<f64>().is_err()
+        && !matches!(
+            s.to_ascii_lowercase().as_str(),
+            "y" | "n" | "yes" | "no" | "on" | "off" | "true" | "false" | "null"
+        );
+    if plain {
+        s.to_string()
+    } else {
+        // JSON strings are valid double-quoted YAML scalars
+        serde_json::to_string(s).unwrap()
+    }
 }
 
 #[cfg(test)]
@@ -167,20 +595,227 @@ mod tests {
         assert_eq!(extension_from_file_name("a.gif.txt"), "txt");
     }
 
+    #[rstest]
+    #[case("abcde", 10, "abcde")]
+    #[case("abcde", 5, "abcde")]
+    #[case("abcde", 4, "abc…")]
+    #[case("abcde", 1, "…")]
+    #[case("abcde", 0, "")]
+    #[case("", 0, "")]
+    #[case("あいうえお", 10, "あいうえお")]
+    #[case("あいうえお", 9, "あいうえ…")]
+    #[case("あいうえお", 8, "あいう…")]
+    #[case("あいうえお", 2, "…")]
+    #[case("aあいう", 5, "aあ…")]
+    #[case("aあいう", 4, "aあ…")]
+    #[case("éèêë", 3, "éè…")]
+    #[trace]
+    fn test_truncate_with_ellipsis(
+        #[case] s: &str,
+        #[case] max_cols: usize,
+        #[case] expected: &str,
+    ) {
+        let actual = truncate_with_ellipsis(s, max_cols);
+        assert_eq!(actual, expected);
+        assert!(actual.width() <= max_cols);
+    }
+
+    #[rstest]
+    #[case("dir1/dir2/file", 14, "dir1/dir2/file")]
+    #[case("dir1/dir2/file", 13, "…r1/dir2/file")]
+    #[case("dir1/dir2/file", 5, "…file")]
+    #[case("dir1/dir2/file", 0, "")]
+    #[case("あいうえお", 9, "…いうえお")]
+    #[case("あいうえお", 8, "…うえお")]
+    #[trace]
+    fn test_truncate_start_with_ellipsis(
+        #[case] s: &str,
+        #[case] max_cols: usize,
+        #[case] expected: &str,
+    ) {
+        let actual = truncate_start_with_ellipsis(s, max_cols);
+        assert_eq!(actual, expected);
+        assert!(actual.width() <= max_cols);
+    }
+
+    #[rstest]
+    #[case("", "abc", Some(vec![]))]
+    #[case("abc", "abc", Some(vec![0, 1, 2]))]
+    #[case("ac", "abc", Some(vec![0, 2]))]
+    #[case("AC", "abc", Some(vec![0, 2]))]
+    #[case("dlo", "Download object", Some(vec![0, 4, 5]))]
+    #[case("ca", "abc", None)]
+    #[case("abcd", "abc", None)]
+    #[trace]
+    fn test_fuzzy_match_indices(
+        #[case] pattern: &str,
+        #[case] text: &str,
+        #[case] expected: Option<Vec<usize>>,
+    ) {
+        assert_eq!(fuzzy_match_indices(pattern, text), expected);
+    }
+
+    #[rstest]
+    #[case("", &[])]
+    #[case("no links here", &[])]
+    #[case("https://example.com", &["https://example.com"])]
+    #[case("see http://example.com/a?b=c#d.", &["http://example.com/a?b=c#d"])]
+    #[case("(https://example.com/a_(b)), and https://x.y", &["https://example.com/a_(b)", "https://x.y"])]
+    #[case("<https://example.com>", &["https://example.com"])]
+    #[case("\"https://example.com/日本\"", &["https://example.com/日本"])]
+    #[case("https:// http:/example.com ftp://example.com", &[])]
+    #[case("xhttps://example.com", &[])]
+    #[trace]
+    fn test_find_urls(#[case] s: &str, #[case] expected: &[&str]) {
+        let actual: Vec<&str> = find_urls(s).into_iter().map(|r| &s[r]).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(0, BracketMatch::Line(8))]
+    #[case(8, BracketMatch::Line(0))]
+    #[case(2, BracketMatch::Line(7))]
+    #[case(7, BracketMatch::Line(2))]
+    #[case(4, BracketMatch::Line(6))]
+    #[case(6, BracketMatch::Line(4))]
+    #[case(1, BracketMatch::NoBracket)] // matched on the same line
+    #[case(3, BracketMatch::NoBracket)] // in the string
+    #[case(5, BracketMatch::NoBracket)]
+    #[case(9, BracketMatch::NoBracket)]
+    fn test_find_matching_bracket_line_json(#[case] from: usize, #[case] expected: BracketMatch) {
+        let lines = [
+            "{",
+            r#"  "a": [1, 2],"#,
+            r#"  "b": {"#,
+            r#"    "c": "} [\" ","#,
+            r#"    "d": ["#,
+            r#"      {"e": 1}"#,
+            "    ]",
+            "  }",
+            "}",
+        ];
+        assert_eq!(find_matching_bracket_line(&lines, from), expected);
+    }
+
+    #[rstest]
+    #[case(&["if (a) {", "} else {", "}"], 0, BracketMatch::Line(1))]
+    #[case(&["if (a) {", "} else {", "}"], 1, BracketMatch::Line(0))]
+    #[case(&["{", "  [", "}"], 0, BracketMatch::Unmatched('{'))]
+    #[case(&["{", "  ]", "}"], 2, BracketMatch::Unmatched('}'))]
+    #[case(&["(", "a"], 0, BracketMatch::Unmatched('('))]
+    #[case(&["a", ")"], 1, BracketMatch::Unmatched(')'))]
+    fn test_find_matching_bracket_line(
+        #[case] lines: &[&str],
+        #[case] from: usize,
+        #[case] expected: BracketMatch,
+    ) {
+        assert_eq!(find_matching_bracket_line(lines, from), expected);
+    }
+
+    #[test]
+    fn test_throughput_meter() {
+        const MIB: usize = 1024 * 1024;
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        let mut meter = ThroughputMeter::new(Duration::from_secs(2), start);
+        assert_eq!(meter.bytes_per_sec(), None);
+        assert_eq!(meter.remaining(10 * MIB), None);
+
+        meter.record(at(500), MIB);
+        assert_eq!(meter.bytes_per_sec(), Some(2.0 * MIB as f64));
+        assert_eq!(meter.remaining(10 * MIB), Some(Duration::from_millis(4500)));
+
+        // a burst of chunks is averaged over the window
+        meter.record(at(600), 3 * MIB);
+        meter.record(at(1000), 3 * MIB);
+        assert_eq!(meter.bytes_per_sec(), Some(3.0 * MIB as f64));
+
+        // old samples are dropped
+        meter.record(at(3000), 4 * MIB);
+        meter.record(at(4000), 6 * MIB);
+        assert_eq!(meter.bytes_per_sec(), Some(MIB as f64));
+        assert_eq!(meter.remaining(10 * MIB), Some(Duration::from_secs(4)));
+    }
+
+    #[rstest]
+    #[case(0, "0s")]
+    #[case(59, "59s")]
+    #[case(60, "1m 00s")]
+    #[case(65, "1m 05s")]
+    #[case(3599, "59m 59s")]
+    #[case(3600, "1h 00m")]
+    #[case(7380, "2h 03m")]
+    #[trace]
+    fn test_format_duration(#[case] secs: u64, #[case] expected: &str) {
+        assert_eq!(format_duration(Duration::from_secs(secs)), expected);
+    }
+
+    #[test]
+    fn test_hex_dump_lines() {
+        let bytes = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x02";
+        let expected = [
+            "00000000: 8950 4e47 0d0a 1a0a 0000 000d 4948 4452  .PNG........IHDR",
+            "00000010: 0000 02                                  ...",
+        ];
+        assert_eq!(hex_dump_lines(bytes), expected);
+        assert!(hex_dump_lines(b"").is_empty());
+    }
+
+    #[rstest]
+    #[case("b", "abc", Some(vec![1]))]
+    #[case("c", "abc", Some(vec![2]))]
+    #[case("a", "abc", Some(vec![0]))]
+    #[case("d", "abc", None)]
+    #[case("abc", "abc", Some(vec![0, 1, 2]))]
+    #[case("cd", "abcdefg", Some(vec![2, 3]))]
+    #[case("", "abc", Some(vec![]))]
+    #[case("うえ", "あいうえお", Some(vec![2, 3]))]
+    fn test_substring_match_indices(
+        #[case] pattern: &str,
+        #[case] text: &str,
+        #[case] expected: Option<Vec<usize>>,
+    ) {
+        assert_eq!(substring_match_indices(pattern, text), expected);
+    }
+
     #[test]
-    fn test_split_str() {
-        fn assert(s: &str, sp: &str, expected: Option<(&str, &str, &str)>) {
-            let actual = split_str(s, sp);
-            assert_eq!(
-                actual,
-                expected.map(|(a, b, c)| (a.into(), b.into(), c.into()))
-            );
-        }
-        assert("abc", "b", Some(("a", "b", "c")));
-        assert("abc", "c", Some(("ab", "c", "")));
-        assert("abc", "a", Some(("", "a", "bc")));
-        assert("abc", "d", None);
-        assert("abc", "abc", Some(("", "abc", "")));
-        assert("abcdefg", "cd", Some(("ab", "cd", "efg")));
+    fn test_json_to_yaml_string() {
+        let value = serde_json::json!({
+            "name": "file.txt",
+            "size": 10,
+            "tags": ["a", {"k": "v", "n": null}],
+            "empty": [],
+            "nested": {"flag": true, "none": {}},
+        });
+        let expected = r#"name: file.txt
+size: 10
+tags:
+  - a
+  - k: v
+    "n": ~
+empty: []
+nested:
+  flag: true
+  none: {}"#;
+        assert_eq!(json_to_yaml_string(&value), expected);
+    }
+
+    #[rstest]
+    #[case("text/plain", "text/plain")]
+    #[case("dir 1/file_1.txt", "dir 1/file_1.txt")]
+    #[case("", r#""""#)]
+    #[case("1024", r#""1024""#)]
+    #[case("1e3", r#""1e3""#)]
+    #[case("yes", r#""yes""#)]
+    #[case("NULL", r#""NULL""#)]
+    #[case("-a", r#""-a""#)]
+    #[case(" a", r#"" a""#)]
+    #[case("a: b", r#""a: b""#)]
+    #[case("a #b", r#""a #b""#)]
+    #[case("\"etag\"", r#""\"etag\"""#)]
+    #[case("a\nb", r#""a\nb""#)]
+    fn test_yaml_string(#[case] s: &str, #[case] expected: &str) {
+        assert_eq!(yaml_string(s), expected);
     }
 }
@@ -1,21 +1,41 @@
+mod ancestor_list_dialog;
 mod bar;
+mod bucket_access_dialog;
+mod command_palette;
 mod copy_detail_dialog;
+mod csv_table;
 mod dialog;
 mod divider;
 mod header;
+mod highlight;
 mod input_dialog;
+mod large_object_dialog;
+mod region_picker;
+mod restore_dialog;
+mod save_confirm_dialog;
 mod scroll;
 mod scroll_lines;
 mod scroll_list;
 mod sort_list_dialog;
+mod storage_class_dialog;
 mod text_preview;
+mod version_diff_dialog;
 
+pub use ancestor_list_dialog::{AncestorListDialog, AncestorListDialogState};
 pub use bar::Bar;
+pub use bucket_access_dialog::{BucketAccessDialog, BucketAccessDialogState};
+pub use command_palette::{CommandPalette, CommandPaletteState, PaletteCommand};
 pub use copy_detail_dialog::{CopyDetailDialog, CopyDetailDialogState};
+pub use csv_table::{CsvTable, CsvTableState};
 pub use dialog::Dialog;
 pub use divider::Divider;
 pub use header::Header;
+pub use highlight::{highlighted_line, highlighted_spans};
 pub use input_dialog::{InputDialog, InputDialogState};
+pub use large_object_dialog::{LargeObjectDialog, LargeObjectDialogState};
+pub use region_picker::{RegionPicker, RegionPickerState};
+pub use restore_dialog::{RestoreDialog, RestoreDialogState};
+pub use save_confirm_dialog::{SaveConfirmAction, SaveConfirmDialog, SaveConfirmDialogState};
 pub use scroll::ScrollBar;
 pub use scroll_lines::{ScrollLines, ScrollLinesOptions, ScrollLinesState};
 pub use scroll_list::{ScrollList, ScrollListState};
@@ -23,4 +43,6 @@ pub use sort_list_dialog::{
     BucketListSortDialog, BucketListSortDialogState, BucketListSortType, ObjectListSortDialog,
     ObjectListSortDialogState, ObjectListSortType,
 };
-pub use text_preview::{TextPreview, TextPreviewState};
+pub use storage_class_dialog::{StorageClassDialog, StorageClassDialogState};
+pub use text_preview::{is_link_cell, Hyperlink, TextPreview, TextPreviewState};
+pub use version_diff_dialog::{VersionDiffDialog, VersionDiffDialogState};
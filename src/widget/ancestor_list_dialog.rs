@@ -0,0 +1,137 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{block::Title, Block, BorderType, List, ListItem, Padding, Widget, WidgetRef},
+};
+
+use crate::{ui::common::calc_centered_dialog_rect, widget::Dialog};
+
+const SELECTED_COLOR: Color = Color::Cyan;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AncestorListDialogState {
+    selected: usize,
+    total: usize,
+}
+
+impl AncestorListDialogState {
+    pub fn new(total: usize) -> Self {
+        // select the parent (the last ancestor) by default
+        Self {
+            selected: total.saturating_sub(1),
+            total,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.total == 0 {
+            return;
+        }
+        self.selected = (self.selected + 1) % self.total;
+    }
+
+    pub fn select_prev(&mut self) {
+        if self.total == 0 {
+            return;
+        }
+        self.selected = (self.selected + self.total - 1) % self.total;
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+}
+
+pub struct AncestorListDialog<'a> {
+    state: AncestorListDialogState,
+    labels: &'a [String],
+}
+
+impl<'a> AncestorListDialog<'a> {
+    pub fn new(state: AncestorListDialogState, labels: &'a [String]) -> Self {
+        Self { state, labels }
+    }
+}
+
+impl Widget for AncestorListDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let list_items: Vec<ListItem> = self
+            .labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| {
+                let item = ListItem::new(Line::raw(format!("{}: {}", i + 1, label)));
+                if i == self.state.selected {
+                    item.fg(SELECTED_COLOR)
+                } else {
+                    item
+                }
+            })
+            .collect();
+
+        let dialog_width = area.width.saturating_sub(4).min(60);
+        let dialog_height = self.labels.len() as u16 + 2 /* border */;
+        let area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
+
+        let title = Title::from("Go to");
+        let list = List::new(list_items).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .padding(Padding::horizontal(1)),
+        );
+        let dialog = Dialog::new(Box::new(list));
+        dialog.render_ref(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::set_cells;
+
+    use super::*;
+
+    #[test]
+    fn test_render_ancestor_list_dialog() {
+        let labels: Vec<String> = ["bucket-1/", "bucket-1/dir1/", "bucket-1/dir1/dir2/"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let state = AncestorListDialogState::new(labels.len());
+        let dialog = AncestorListDialog::new(state, &labels);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 7));
+        dialog.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "                              ",
+            "  ╭Go to───────────────────╮  ",
+            "  │ 1: bucket-1/           │  ",
+            "  │ 2: bucket-1/dir1/      │  ",
+            "  │ 3: bucket-1/dir1/dir2/ │  ",
+            "  ╰────────────────────────╯  ",
+            "                              ",
+        ]);
+        set_cells! { expected =>
+            // selected item
+            (4..26, [4]) => fg: Color::Cyan,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_select_ancestor() {
+        let mut state = AncestorListDialogState::new(3);
+        assert_eq!(state.selected(), 2);
+        state.select_next();
+        assert_eq!(state.selected(), 0);
+        state.select_prev();
+        assert_eq!(state.selected(), 2);
+        state.select_prev();
+        assert_eq!(state.selected(), 1);
+    }
+}
@@ -0,0 +1,197 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Stylize},
+    text::{Line, Span},
+    widgets::{block::Title, Block, BorderType, Padding, Paragraph, Widget, WidgetRef},
+};
+
+use crate::{
+    object::{BucketAccess, Readable},
+    ui::common::calc_centered_dialog_rect,
+    widget::Dialog,
+};
+
+const PUBLIC_COLOR: Color = Color::Red;
+const NOT_PUBLIC_COLOR: Color = Color::Green;
+const UNKNOWN_COLOR: Color = Color::Yellow;
+const DENIED_COLOR: Color = Color::DarkGray;
+
+#[derive(Debug)]
+pub struct BucketAccessDialogState {
+    bucket: String,
+    access: BucketAccess,
+}
+
+impl BucketAccessDialogState {
+    pub fn new(bucket: String, access: BucketAccess) -> Self {
+        Self { bucket, access }
+    }
+}
+
+pub struct BucketAccessDialog<'a> {
+    state: &'a BucketAccessDialogState,
+}
+
+impl<'a> BucketAccessDialog<'a> {
+    pub fn new(state: &'a BucketAccessDialogState) -> Self {
+        Self { state }
+    }
+}
+
+impl Widget for BucketAccessDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let access = &self.state.access;
+
+        let public = match access.is_public() {
+            Some(true) => "yes".fg(PUBLIC_COLOR),
+            Some(false) => "no".fg(NOT_PUBLIC_COLOR),
+            None => "unknown".fg(UNKNOWN_COLOR),
+        };
+        let mut lines = vec![
+            Line::from(vec![label("Public: "), public]),
+            Line::raw(""),
+            Line::from(label("Public access block:")),
+        ];
+        match &access.public_access_block {
+            Readable::Value(Some(block)) => {
+                let settings = [
+                    ("BlockPublicAcls", block.block_public_acls),
+                    ("IgnorePublicAcls", block.ignore_public_acls),
+                    ("BlockPublicPolicy", block.block_public_policy),
+                    ("RestrictPublicBuckets", block.restrict_public_buckets),
+                ];
+                lines.extend(settings.iter().map(|(name, on)| {
+                    let value = if *on { "on" } else { "off" };
+                    Line::raw(format!("  {}: {}", name, value))
+                }));
+            }
+            Readable::Value(None) => lines.push(Line::raw("  not configured")),
+            Readable::Denied => lines.push(denied_line("public access block")),
+        }
+
+        lines.push(Line::raw(""));
+        let policy = match &access.policy_public {
+            Readable::Value(Some(true)) => Span::raw("public"),
+            Readable::Value(Some(false)) => Span::raw("not public"),
+            Readable::Value(None) => Span::raw("no policy"),
+            Readable::Denied => denied_span("policy"),
+        };
+        lines.push(Line::from(vec![label("Bucket policy: "), policy]));
+
+        lines.push(Line::raw(""));
+        lines.push(Line::from(label("Grants:")));
+        match &access.grants {
+            Readable::Value(grants) if grants.is_empty() => lines.push(Line::raw("  none")),
+            Readable::Value(grants) => {
+                lines.extend(grants.iter().map(|grant| {
+                    let line = Line::raw(format!("  {}: {}", grant.grantee, grant.permission));
+                    if grant.public {
+                        line.fg(PUBLIC_COLOR)
+                    } else {
+                        line
+                    }
+                }));
+            }
+            Readable::Denied => lines.push(denied_line("ACL")),
+        }
+
+        let dialog_width = area.width.saturating_sub(4).min(60);
+        let dialog_height = lines.len() as u16 + 2 /* border */;
+        let area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
+
+        let title = Title::from(format!("Access: {}", self.state.bucket));
+        let paragraph = Paragraph::new(lines).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .padding(Padding::horizontal(1)),
+        );
+        let dialog = Dialog::new(Box::new(paragraph));
+        dialog.render_ref(area, buf);
+    }
+}
+
+fn label(s: &str) -> Span<'_> {
+    s.add_modifier(Modifier::BOLD)
+}
+
+fn denied_span(name: &str) -> Span<'static> {
+    format!("insufficient permissions to read {}", name).fg(DENIED_COLOR)
+}
+
+fn denied_line(name: &str) -> Line<'static> {
+    Line::from(vec!["  ".into(), denied_span(name)])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        object::{BucketGrant, PublicAccessBlock},
+        set_cells,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_render_bucket_access_dialog() {
+        let access = BucketAccess {
+            policy_public: Readable::Denied,
+            public_access_block: Readable::Value(Some(PublicAccessBlock {
+                block_public_acls: true,
+                ignore_public_acls: true,
+                block_public_policy: false,
+                restrict_public_buckets: false,
+            })),
+            grants: Readable::Value(vec![
+                BucketGrant {
+                    grantee: "owner".into(),
+                    permission: "FULL_CONTROL".into(),
+                    public: false,
+                },
+                BucketGrant {
+                    grantee: "AllUsers (group)".into(),
+                    permission: "READ".into(),
+                    public: true,
+                },
+            ]),
+        };
+        let state = BucketAccessDialogState::new("bucket-1".into(), access);
+        let dialog = BucketAccessDialog::new(&state);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 64, 17));
+        dialog.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "                                                                ",
+            "  ╭Access: bucket-1──────────────────────────────────────────╮  ",
+            "  │ Public: unknown                                          │  ",
+            "  │                                                          │  ",
+            "  │ Public access block:                                     │  ",
+            "  │   BlockPublicAcls: on                                    │  ",
+            "  │   IgnorePublicAcls: on                                   │  ",
+            "  │   BlockPublicPolicy: off                                 │  ",
+            "  │   RestrictPublicBuckets: off                             │  ",
+            "  │                                                          │  ",
+            "  │ Bucket policy: insufficient permissions to read policy   │  ",
+            "  │                                                          │  ",
+            "  │ Grants:                                                  │  ",
+            "  │   owner: FULL_CONTROL                                    │  ",
+            "  │   AllUsers (group): READ                                 │  ",
+            "  ╰──────────────────────────────────────────────────────────╯  ",
+            "                                                                ",
+        ]);
+        set_cells! { expected =>
+            (4..12, [2]) => modifier: Modifier::BOLD,
+            (12..19, [2]) => fg: Color::Yellow,
+            (4..24, [4]) => modifier: Modifier::BOLD,
+            (4..19, [10]) => modifier: Modifier::BOLD,
+            (19..58, [10]) => fg: Color::DarkGray,
+            (4..11, [12]) => modifier: Modifier::BOLD,
+            (4..28, [14]) => fg: Color::Red,
+        }
+
+        assert_eq!(buf, expected);
+    }
+}
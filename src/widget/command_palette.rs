@@ -0,0 +1,266 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{block::Title, Block, BorderType, Padding, Paragraph, StatefulWidget, WidgetRef},
+};
+use tui_input::{backend::crossterm::EventHandler, Input};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    ui::common::calc_centered_dialog_rect,
+    util::fuzzy_match_indices,
+    widget::{highlighted_line, Dialog},
+};
+
+const SELECTED_COLOR: Color = Color::Cyan;
+const KEY_COLOR: Color = Color::DarkGray;
+const MAX_ITEMS: usize = 10;
+
+#[derive(Debug, Clone)]
+pub struct PaletteCommand {
+    pub keys: String,
+    pub desc: String,
+    pub key: KeyEvent,
+}
+
+#[derive(Debug, Default)]
+pub struct CommandPaletteState {
+    input: Input,
+    commands: Vec<PaletteCommand>,
+    view_indices: Vec<usize>,
+    selected: usize,
+    cursor: (u16, u16),
+}
+
+impl CommandPaletteState {
+    pub fn new(commands: Vec<PaletteCommand>) -> Self {
+        let view_indices = (0..commands.len()).collect();
+        Self {
+            commands,
+            view_indices,
+            ..Default::default()
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.view_indices.is_empty() {
+            return;
+        }
+        self.selected = (self.selected + 1) % self.view_indices.len();
+    }
+
+    pub fn select_prev(&mut self) {
+        if self.view_indices.is_empty() {
+            return;
+        }
+        self.selected = (self.selected + self.view_indices.len() - 1) % self.view_indices.len();
+    }
+
+    pub fn selected_command(&self) -> Option<&PaletteCommand> {
+        self.view_indices
+            .get(self.selected)
+            .map(|&i| &self.commands[i])
+    }
+
+    pub fn cursor(&self) -> (u16, u16) {
+        self.cursor
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) {
+        let event = &ratatui::crossterm::event::Event::Key(key);
+        if self.input.handle_event(event).is_some_and(|c| c.value) {
+            self.filter_view_indices();
+        }
+    }
+
+    fn filter_view_indices(&mut self) {
+        let pattern = self.input.value();
+        self.view_indices = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter(|(_, command)| fuzzy_match_indices(pattern, &command.desc).is_some())
+            .map(|(i, _)| i)
+            .collect();
+        self.selected = 0;
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CommandPalette {}
+
+impl StatefulWidget for CommandPalette {
+    type State = CommandPaletteState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let dialog_width = area.width.saturating_sub(4).min(60);
+        let list_height = state.view_indices.len().clamp(1, MAX_ITEMS);
+        let dialog_height = 1 /* input */ + list_height as u16 + 2 /* border */;
+        let dialog_area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
+
+        let content_width = dialog_width.saturating_sub(4) as usize;
+        let offset = state.selected.saturating_sub(list_height - 1);
+
+        let mut lines = vec![Line::from(format!("> {}", state.input.value()))];
+        lines.extend(
+            state
+                .view_indices
+                .iter()
+                .enumerate()
+                .skip(offset)
+                .take(list_height)
+                .map(|(i, &index)| {
+                    let command = &state.commands[index];
+                    let pad = content_width.saturating_sub(
+                        UnicodeWidthStr::width(command.desc.as_str())
+                            + UnicodeWidthStr::width(command.keys.as_str()),
+                    );
+                    let indices =
+                        fuzzy_match_indices(state.input.value(), &command.desc).unwrap_or_default();
+                    let mut line = highlighted_line(&command.desc, &indices);
+                    line.spans.extend([
+                        Span::raw(" ".repeat(pad)),
+                        Span::raw(command.keys.clone()).fg(KEY_COLOR),
+                    ]);
+                    if i == state.selected {
+                        line.fg(SELECTED_COLOR)
+                    } else {
+                        line
+                    }
+                }),
+        );
+
+        let title = Title::from("Commands");
+        let dialog_content = Paragraph::new(lines).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .padding(Padding::horizontal(1)),
+        );
+        let dialog = Dialog::new(Box::new(dialog_content));
+        dialog.render_ref(dialog_area, buf);
+
+        // update cursor position
+        let cursor_x = dialog_area.x + 2 /* border and padding */ + 2 /* prompt */;
+        let cursor_x = cursor_x + state.input.visual_cursor() as u16;
+        let cursor_y = dialog_area.y + 1;
+        state.cursor = (cursor_x, cursor_y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyCode;
+
+    use crate::set_cells;
+
+    use super::*;
+
+    #[test]
+    fn test_render_command_palette() {
+        let mut state = CommandPaletteState::new(commands());
+        state.select_next();
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 34, 7));
+        CommandPalette::default().render(buf.area, &mut buf, &mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "  ╭Commands────────────────────╮  ",
+            "  │ >                          │  ",
+            "  │ Open object              o │  ",
+            "  │ Download object          s │  ",
+            "  │ Download object as       S │  ",
+            "  ╰────────────────────────────╯  ",
+            "                                  ",
+        ]);
+        set_cells! { expected =>
+            (4..29, [3]) => fg: Color::Cyan,
+            ([29], [2, 3, 4]) => fg: Color::DarkGray,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_render_command_palette_highlight() {
+        let mut state = CommandPaletteState::new(commands());
+        for c in ['a', 's'] {
+            state.handle_key_event(KeyEvent::from(KeyCode::Char(c)));
+        }
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 34, 7));
+        CommandPalette::default().render(buf.area, &mut buf, &mut state);
+
+        // the height fits the filtered commands
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "                                  ",
+            "  ╭Commands────────────────────╮  ",
+            "  │ > as                       │  ",
+            "  │ Download object as       S │  ",
+            "  ╰────────────────────────────╯  ",
+            "                                  ",
+            "                                  ",
+        ]);
+        set_cells! { expected =>
+            (4..29, [3]) => fg: Color::Cyan,
+            ([10, 21], [3]) => fg: Color::Red,
+            ([29], [3]) => fg: Color::DarkGray,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_render_command_palette_wide_chars() {
+        let mut state = CommandPaletteState::new(vec![PaletteCommand {
+            keys: "o".into(),
+            desc: "ファイルを開く".into(),
+            key: KeyEvent::from(KeyCode::Char('o')),
+        }]);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 34, 4));
+        CommandPalette::default().render(buf.area, &mut buf, &mut state);
+
+        // the keys are aligned to the right edge by the display width
+        assert_eq!(buf.get(4, 2).symbol(), "フ");
+        assert_eq!(buf.get(29, 2).symbol(), "o");
+    }
+
+    #[test]
+    fn test_filter_command_palette() {
+        let mut state = CommandPaletteState::new(commands());
+
+        for c in ['d', 'o', 'a', 's'] {
+            state.handle_key_event(KeyEvent::from(KeyCode::Char(c)));
+        }
+        assert_eq!(state.selected_command().unwrap().desc, "Download object as");
+
+        state.handle_key_event(KeyEvent::from(KeyCode::Char('x')));
+        assert!(state.selected_command().is_none());
+
+        state.handle_key_event(KeyEvent::from(KeyCode::Backspace));
+        state.handle_key_event(KeyEvent::from(KeyCode::Backspace));
+        state.select_next();
+        assert_eq!(state.selected_command().unwrap().desc, "Download object as");
+    }
+
+    fn commands() -> Vec<PaletteCommand> {
+        [
+            ("o", "Open object"),
+            ("s", "Download object"),
+            ("S", "Download object as"),
+        ]
+        .iter()
+        .map(|(keys, desc)| PaletteCommand {
+            keys: keys.to_string(),
+            desc: desc.to_string(),
+            key: KeyEvent::from(KeyCode::Char(keys.chars().next().unwrap())),
+        })
+        .collect()
+    }
+}
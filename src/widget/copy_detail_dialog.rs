@@ -1,3 +1,4 @@
+use chrono::SecondsFormat;
 use itsuki::zero_indexed_enum;
 use ratatui::{
     buffer::Buffer,
@@ -7,7 +8,11 @@ use ratatui::{
     widgets::{block::Title, Block, BorderType, List, ListItem, Padding, Widget, WidgetRef},
 };
 
-use crate::{object::FileDetail, ui::common::calc_centered_dialog_rect, widget::Dialog};
+use crate::{
+    object::FileDetail,
+    ui::common::{calc_centered_dialog_rect, format_datetime},
+    widget::Dialog,
+};
 
 const SELECTED_COLOR: Color = Color::Cyan;
 
@@ -20,18 +25,51 @@ enum ItemType {
     Arn,
     ObjectUrl,
     Etag,
+    LastModified,
+    LastModifiedRfc3339,
+    LastModifiedEpochSecs,
+    LastModifiedEpochMillis,
+    Json,
+    Yaml,
 }
 
 impl ItemType {
     pub fn name_and_value(&self, file_detail: &FileDetail) -> (String, String) {
         let (name, value) = match self {
-            Self::Key => ("Key", &file_detail.key),
-            Self::S3Uri => ("S3 URI", &file_detail.s3_uri),
-            Self::Arn => ("ARN", &file_detail.arn),
-            Self::ObjectUrl => ("Object URL", &file_detail.object_url),
-            Self::Etag => ("ETag", &file_detail.e_tag),
+            Self::Key => ("Key", file_detail.key.clone()),
+            Self::S3Uri => ("S3 URI", file_detail.s3_uri.clone()),
+            Self::Arn => ("ARN", file_detail.arn.clone()),
+            Self::ObjectUrl => ("Object URL", file_detail.object_url.clone()),
+            Self::Etag => ("ETag", file_detail.e_tag.clone()),
+            Self::LastModified => ("Last modified", format_datetime(&file_detail.last_modified)),
+            Self::LastModifiedRfc3339 => (
+                "Last modified (RFC 3339, UTC)",
+                file_detail
+                    .last_modified
+                    .to_utc()
+                    .to_rfc3339_opts(SecondsFormat::Secs, true),
+            ),
+            Self::LastModifiedEpochSecs => (
+                "Last modified (epoch seconds)",
+                file_detail.last_modified.timestamp().to_string(),
+            ),
+            Self::LastModifiedEpochMillis => (
+                "Last modified (epoch milliseconds)",
+                file_detail.last_modified.timestamp_millis().to_string(),
+            ),
+            Self::Json => ("Detail (JSON)", file_detail.to_json()),
+            Self::Yaml => ("Detail (YAML)", file_detail.to_yaml()),
         };
-        (name.into(), value.into())
+        (name.into(), value)
+    }
+
+    // the serialized detail has multiple lines, so only the description is shown
+    fn name_and_view_value(&self, file_detail: &FileDetail) -> (String, String) {
+        match self {
+            Self::Json => ("Detail (JSON)".into(), "All fields as JSON".into()),
+            Self::Yaml => ("Detail (YAML)".into(), "All fields as YAML".into()),
+            _ => self.name_and_value(file_detail),
+        }
     }
 }
 
@@ -74,8 +112,8 @@ impl Widget for CopyDetailDialog<'_> {
             .map(|(i, item_type)| build_list_item(i, selected, *item_type, self.file_detail))
             .collect();
 
-        let dialog_width = (area.width - 4).min(80);
-        let dialog_height = 2 * 5 /* list */ + 2 /* border */;
+        let dialog_width = area.width.saturating_sub(4).min(80);
+        let dialog_height = 2 * list_items.len() as u16 /* list */ + 2 /* border */;
         let area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
 
         let title = Title::from("Copy");
@@ -95,8 +133,8 @@ fn build_list_item(
     selected: usize,
     item_type: ItemType,
     file_detail: &FileDetail,
-) -> ListItem {
-    let (name, value) = item_type.name_and_value(file_detail);
+) -> ListItem<'_> {
+    let (name, value) = item_type.name_and_view_value(file_detail);
     let item = ListItem::new(vec![
         Line::from(format!("{}:", name).add_modifier(Modifier::BOLD)),
         Line::from(format!("  {}", value)),
@@ -111,6 +149,7 @@ fn build_list_item(
 #[cfg(test)]
 mod tests {
     use chrono::{DateTime, Local, NaiveDateTime};
+    use rstest::rstest;
 
     use crate::set_cells;
 
@@ -122,50 +161,95 @@ mod tests {
         let file_detail = file_detail();
         let copy_detail_dialog = CopyDetailDialog::new(state, &file_detail);
 
-        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 20));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 26));
         copy_detail_dialog.render(buf.area, &mut buf);
 
+        // the epoch and UTC values depend on the local time zone of the fixture
+        let value_line = |value: String| format!("  │   {:<30} │  ", value);
+        let last_modified = file_detail.last_modified;
         #[rustfmt::skip]
         let mut expected = Buffer::with_lines([
-            "                                        ",
-            "                                        ",
-            "                                        ",
-            "                                        ",
-            "  ╭Copy──────────────────────────────╮  ",
-            "  │ Key:                             │  ",
-            "  │   file.txt                       │  ",
-            "  │ S3 URI:                          │  ",
-            "  │   s3://bucket-1/file.txt         │  ",
-            "  │ ARN:                             │  ",
-            "  │   arn:aws:s3:::bucket-1/file.txt │  ",
-            "  │ Object URL:                      │  ",
-            "  │   https://bucket-1.s3.ap-northea │  ",
-            "  │ ETag:                            │  ",
-            "  │   bef684de-a260-48a4-8178-8a535e │  ",
-            "  ╰──────────────────────────────────╯  ",
-            "                                        ",
-            "                                        ",
-            "                                        ",
-            "                                        ",
+            "                                        ".to_string(),
+            "  ╭Copy──────────────────────────────╮  ".to_string(),
+            "  │ Key:                             │  ".to_string(),
+            "  │   file.txt                       │  ".to_string(),
+            "  │ S3 URI:                          │  ".to_string(),
+            "  │   s3://bucket-1/file.txt         │  ".to_string(),
+            "  │ ARN:                             │  ".to_string(),
+            "  │   arn:aws:s3:::bucket-1/file.txt │  ".to_string(),
+            "  │ Object URL:                      │  ".to_string(),
+            "  │   https://bucket-1.s3.ap-northea │  ".to_string(),
+            "  │ ETag:                            │  ".to_string(),
+            "  │   bef684de-a260-48a4-8178-8a535e │  ".to_string(),
+            "  │ Last modified:                   │  ".to_string(),
+            "  │   2024-01-02 13:01:02            │  ".to_string(),
+            "  │ Last modified (RFC 3339, UTC):   │  ".to_string(),
+            value_line(last_modified.to_utc().to_rfc3339_opts(SecondsFormat::Secs, true)),
+            "  │ Last modified (epoch seconds):   │  ".to_string(),
+            value_line(last_modified.timestamp().to_string()),
+            "  │ Last modified (epoch millisecond │  ".to_string(),
+            value_line(last_modified.timestamp_millis().to_string()),
+            "  │ Detail (JSON):                   │  ".to_string(),
+            "  │   All fields as JSON             │  ".to_string(),
+            "  │ Detail (YAML):                   │  ".to_string(),
+            "  │   All fields as YAML             │  ".to_string(),
+            "  ╰──────────────────────────────────╯  ".to_string(),
+            "                                        ".to_string(),
         ]);
         set_cells! { expected =>
             // "Key" is bold
-            (4..8, [5]) => modifier: Modifier::BOLD,
+            (4..8, [2]) => modifier: Modifier::BOLD,
             // "S3 URI" is bold
-            (4..11, [7]) => modifier: Modifier::BOLD,
+            (4..11, [4]) => modifier: Modifier::BOLD,
             // "ARN" is bold
-            (4..8, [9]) => modifier: Modifier::BOLD,
+            (4..8, [6]) => modifier: Modifier::BOLD,
             // "Object URL" is bold
-            (4..15, [11]) => modifier: Modifier::BOLD,
+            (4..15, [8]) => modifier: Modifier::BOLD,
             // "ETag" is bold
-            (4..9, [13]) => modifier: Modifier::BOLD,
+            (4..9, [10]) => modifier: Modifier::BOLD,
+            // "Last modified" is bold
+            (4..18, [12]) => modifier: Modifier::BOLD,
+            // "Last modified (RFC 3339, UTC)" is bold
+            (4..34, [14]) => modifier: Modifier::BOLD,
+            // "Last modified (epoch seconds)" is bold
+            (4..34, [16]) => modifier: Modifier::BOLD,
+            // "Last modified (epoch milliseconds)" is bold
+            (4..36, [18]) => modifier: Modifier::BOLD,
+            // "Detail (JSON)" is bold
+            (4..18, [20]) => modifier: Modifier::BOLD,
+            // "Detail (YAML)" is bold
+            (4..18, [22]) => modifier: Modifier::BOLD,
             // selected item
-            (4..36, [5, 6]) => fg: Color::Cyan,
+            (4..36, [2, 3]) => fg: Color::Cyan,
         }
 
         assert_eq!(buf, expected);
     }
 
+    #[rstest]
+    #[case(ItemType::LastModifiedRfc3339, "2024-01-02T13:01:02Z")]
+    #[case(ItemType::LastModifiedEpochSecs, "1704200462")]
+    #[case(ItemType::LastModifiedEpochMillis, "1704200462123")]
+    fn test_last_modified_value(#[case] item_type: ItemType, #[case] expected: &str) {
+        let file_detail = FileDetail {
+            last_modified: DateTime::from_timestamp_millis(1704200462123)
+                .unwrap()
+                .with_timezone(&Local),
+            ..file_detail()
+        };
+        let (_, value) = item_type.name_and_value(&file_detail);
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_last_modified_localized_value() {
+        // parsed as the local time, so the string does not depend on the time zone
+        let file_detail = file_detail();
+        let (name, value) = ItemType::LastModified.name_and_value(&file_detail);
+        assert_eq!(name, "Last modified");
+        assert_eq!(value, "2024-01-02 13:01:02");
+    }
+
     fn file_detail() -> FileDetail {
         FileDetail {
             name: "file.txt".to_string(),
@@ -174,10 +258,15 @@ mod tests {
             e_tag: "bef684de-a260-48a4-8178-8a535ecccadb".to_string(),
             content_type: "text/plain".to_string(),
             storage_class: "STANDARD".to_string(),
+            encryption: None,
+            kms_key_id: None,
+            bucket_key_enabled: None,
+            content_encoding: None,
             key: "file.txt".to_string(),
             s3_uri: "s3://bucket-1/file.txt".to_string(),
             arn: "arn:aws:s3:::bucket-1/file.txt".to_string(),
             object_url: "https://bucket-1.s3.ap-northeast-1.amazonaws.com/file.txt".to_string(),
+            restore_status: None,
         }
     }
 
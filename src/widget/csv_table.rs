@@ -0,0 +1,252 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, StatefulWidget, Widget},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{csv::Csv, util::truncate_with_ellipsis};
+
+const MAX_COLUMN_WIDTH: usize = 32;
+const COLUMN_SEPARATOR: &str = " │ ";
+const MALFORMED_MARK: &str = "! ";
+const NORMAL_MARK: &str = "  ";
+
+const SEPARATOR_COLOR: Color = Color::DarkGray;
+const MALFORMED_COLOR: Color = Color::Yellow;
+
+#[derive(Debug)]
+pub struct CsvTableState {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+    widths: Vec<usize>,
+    v_offset: usize,
+    col_offset: usize,
+    height: usize,
+}
+
+impl CsvTableState {
+    pub fn new(csv: Csv) -> Self {
+        let columns = csv
+            .rows
+            .iter()
+            .map(Vec::len)
+            .chain([csv.header.len()])
+            .max()
+            .unwrap_or_default();
+        let widths = (0..columns)
+            .map(|i| {
+                csv.rows
+                    .iter()
+                    .chain([&csv.header])
+                    .filter_map(|fields| fields.get(i))
+                    .map(|field| cell_string(field).width())
+                    .max()
+                    .unwrap_or_default()
+                    .clamp(1, MAX_COLUMN_WIDTH)
+            })
+            .collect();
+        Self {
+            header: csv.header,
+            rows: csv.rows,
+            widths,
+            v_offset: 0,
+            col_offset: 0,
+            height: 0,
+        }
+    }
+
+    pub fn scroll_forward(&mut self) {
+        if self.v_offset < self.rows.len().saturating_sub(1) {
+            self.v_offset += 1;
+        }
+    }
+
+    pub fn scroll_backward(&mut self) {
+        self.v_offset = self.v_offset.saturating_sub(1);
+    }
+
+    pub fn scroll_page_forward(&mut self) {
+        let max = self.rows.len().saturating_sub(1);
+        self.v_offset = (self.v_offset + self.height).min(max);
+    }
+
+    pub fn scroll_page_backward(&mut self) {
+        self.v_offset = self.v_offset.saturating_sub(self.height);
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.v_offset = 0;
+    }
+
+    pub fn scroll_to_end(&mut self) {
+        self.v_offset = self.rows.len().saturating_sub(self.height);
+    }
+
+    pub fn scroll_right(&mut self) {
+        if self.col_offset < self.widths.len().saturating_sub(1) {
+            self.col_offset += 1;
+        }
+    }
+
+    pub fn scroll_left(&mut self) {
+        self.col_offset = self.col_offset.saturating_sub(1);
+    }
+
+    pub fn scroll_to_first_column(&mut self) {
+        self.col_offset = 0;
+    }
+
+    pub fn scroll_to_last_column(&mut self) {
+        self.col_offset = self.widths.len().saturating_sub(1);
+    }
+
+    fn build_line(&self, fields: &[String], malformed: bool) -> Line<'static> {
+        let mark = if malformed {
+            MALFORMED_MARK.fg(MALFORMED_COLOR)
+        } else {
+            Span::raw(NORMAL_MARK)
+        };
+        let mut spans = vec![mark];
+        for (i, width) in self.widths.iter().enumerate().skip(self.col_offset) {
+            if i > self.col_offset {
+                spans.push(COLUMN_SEPARATOR.fg(SEPARATOR_COLOR));
+            }
+            let field = fields.get(i).map(|f| cell_string(f)).unwrap_or_default();
+            let field = truncate_with_ellipsis(&field, *width);
+            let pad = width.saturating_sub(field.width());
+            spans.push(Span::raw(format!("{}{}", field, " ".repeat(pad))));
+        }
+        Line::from(spans)
+    }
+}
+
+// the fields may contain line breaks and tabs, which break the alignment
+fn cell_string(field: &str) -> String {
+    field.replace(['\r', '\n', '\t'], " ")
+}
+
+/// Renders the CSV as a table aligned by columns, keeping the header at the top.
+///
+/// The rows whose number of fields differs from the header are marked.
+#[derive(Debug, Default)]
+pub struct CsvTable {
+    block: Option<Block<'static>>,
+}
+
+impl CsvTable {
+    pub fn block(mut self, block: Block<'static>) -> Self {
+        self.block = Some(block);
+        self
+    }
+}
+
+impl StatefulWidget for CsvTable {
+    type State = CsvTableState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let content_area = match self.block {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            }
+            None => area,
+        };
+        // header and separator
+        state.height = content_area.height.saturating_sub(2) as usize;
+
+        let header = state.build_line(&state.header, false).bold();
+        let separator = Line::from("─".repeat(content_area.width as usize)).fg(SEPARATOR_COLOR);
+        let columns = state.header.len();
+        let rows = state
+            .rows
+            .iter()
+            .skip(state.v_offset)
+            .take(state.height)
+            .map(|fields| state.build_line(fields, fields.len() != columns));
+
+        let lines: Vec<Line> = [header, separator].into_iter().chain(rows).collect();
+        Paragraph::new(lines).render(content_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Modifier;
+
+    use crate::set_cells;
+
+    use super::*;
+
+    fn csv() -> Csv {
+        Csv::parse(
+            "id,name,note\n1,foo,bar\n2,baz\n3,long name of qux,x\n",
+            100,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render_csv_table() {
+        let mut state = CsvTableState::new(csv());
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 32, 7));
+        let table = CsvTable::default().block(Block::bordered().title("Preview"));
+        table.render(buf.area, &mut buf, &mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌Preview───────────────────────┐",
+            "│  id │ name             │ note│",
+            "│──────────────────────────────│",
+            "│  1  │ foo              │ bar │",
+            "│! 2  │ baz              │     │",
+            "│  3  │ long name of qux │ x   │",
+            "└──────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            (1..31, [1]) => modifier: Modifier::BOLD,
+            (1..31, [2]) => fg: Color::DarkGray,
+            ([5, 6, 7, 24, 25, 26], [1, 3, 4, 5]) => fg: Color::DarkGray,
+            ([1, 2], [4]) => fg: Color::Yellow,
+        }
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_render_csv_table_scrolled() {
+        let mut state = CsvTableState::new(csv());
+        state.scroll_right();
+        state.scroll_forward();
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 32, 6));
+        let table = CsvTable::default().block(Block::bordered().title("Preview"));
+        table.render(buf.area, &mut buf, &mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌Preview───────────────────────┐",
+            "│  name             │ note     │",
+            "│──────────────────────────────│",
+            "│! baz              │          │",
+            "│  long name of qux │ x        │",
+            "└──────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            (1..26, [1]) => modifier: Modifier::BOLD,
+            (1..31, [2]) => fg: Color::DarkGray,
+            ([19, 20, 21], [1, 3, 4]) => fg: Color::DarkGray,
+            ([1, 2], [3]) => fg: Color::Yellow,
+        }
+        assert_eq!(buf, expected);
+
+        state.scroll_to_end();
+        assert_eq!((state.v_offset, state.col_offset), (1, 1));
+        state.scroll_to_last_column();
+        state.scroll_right();
+        assert_eq!((state.v_offset, state.col_offset), (1, 2));
+    }
+}
@@ -1,18 +1,46 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Margin, Rect},
-    widgets::{Block, Padding, Paragraph, Widget},
+    layout::{Alignment, Margin, Rect},
+    widgets::{block::Title, Block, Padding, Paragraph, Widget},
 };
 
-use crate::{constant::APP_NAME, util::prune_strings_to_fit_width};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    constant::APP_NAME,
+    util::{prune_strings_to_fit_width, truncate_with_ellipsis},
+};
 
 pub struct Header {
     breadcrumb: Vec<String>,
+    anonymous: bool,
+    requester_pays: bool,
+    region: Option<String>,
 }
 
 impl Header {
     pub fn new(breadcrumb: Vec<String>) -> Header {
-        Header { breadcrumb }
+        Header {
+            breadcrumb,
+            anonymous: false,
+            requester_pays: false,
+            region: None,
+        }
+    }
+
+    pub fn anonymous(mut self, anonymous: bool) -> Header {
+        self.anonymous = anonymous;
+        self
+    }
+
+    pub fn requester_pays(mut self, requester_pays: bool) -> Header {
+        self.requester_pays = requester_pays;
+        self
+    }
+
+    pub fn region(mut self, region: Option<String>) -> Header {
+        self.region = region;
+        self
     }
 }
 
@@ -25,16 +53,33 @@ impl Widget for Header {
 impl Header {
     const DELIMITER: &'static str = " / ";
     const ELLIPSIS: &'static str = "...";
+    const ANONYMOUS_NOTE: &'static str = "anonymous: read-only";
+    const REQUESTER_PAYS_NOTE: &'static str = "requester pays: charges apply";
 
     fn render_header(self, area: Rect, buf: &mut Buffer) {
         let inner_area = area.inner(Margin::new(1, 1));
         let pad = Padding::horizontal(1);
-        let max_width = (inner_area.width - pad.left - pad.right) as usize;
+        let max_width = inner_area.width.saturating_sub(pad.left + pad.right) as usize;
 
+        let notes: Vec<&str> = [
+            (self.anonymous, Self::ANONYMOUS_NOTE),
+            (self.requester_pays, Self::REQUESTER_PAYS_NOTE),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, note)| enabled.then_some(note))
+        .collect();
+        let title = if notes.is_empty() {
+            APP_NAME.to_string()
+        } else {
+            format!("{} ({})", APP_NAME, notes.join(", "))
+        };
+        let mut block = Block::bordered().title(title).padding(pad);
+        if let Some(region) = self.region.clone() {
+            block = block.title(Title::from(region).alignment(Alignment::Right));
+        }
         let current_key_str = self.build_current_key_str(max_width);
 
-        let paragraph =
-            Paragraph::new(current_key_str).block(Block::bordered().title(APP_NAME).padding(pad));
+        let paragraph = Paragraph::new(current_key_str).block(block);
 
         paragraph.render(area, buf);
     }
@@ -45,7 +90,7 @@ impl Header {
         }
 
         let current_key = self.breadcrumb.join(Self::DELIMITER);
-        if current_key.len() <= max_width {
+        if current_key.width() <= max_width {
             return current_key;
         }
 
@@ -60,7 +105,9 @@ impl Header {
             .collect();
         bs.insert(1, (Self::ELLIPSIS.to_string(), 0));
         bs.first_mut().unwrap().1 = 1;
-        bs.last_mut().unwrap().1 = 0;
+        let last = bs.last_mut().unwrap();
+        last.0 = truncate_with_ellipsis(&last.0, max_width);
+        last.1 = 0;
 
         let keys = prune_strings_to_fit_width(&bs, max_width, Self::DELIMITER);
         keys.join(Self::DELIMITER)
@@ -109,6 +156,101 @@ mod tests {
         assert_eq!(buf, expected);
     }
 
+    #[test]
+    fn test_render_header_with_long_name() {
+        let breadcrumb = ["bucket", "key01", "very-long-long-long-key-name"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        let header = Header::new(breadcrumb);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30 + 4, 3));
+        header.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "┌STU─────────────────────────────┐",
+            "│ very-long-long-long-key-name   │",
+            "└────────────────────────────────┘",
+        ]);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_render_header_with_long_full_width_name() {
+        let breadcrumb = ["バケット", "キー", "とても長い長い長い長いキーの名前"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        let header = Header::new(breadcrumb);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30 + 4, 3));
+        header.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "┌STU─────────────────────────────┐",
+            "│ とても長い長い長い長いキーの…  │",
+            "└────────────────────────────────┘",
+        ]);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_render_header_anonymous() {
+        let breadcrumb = ["bucket", "key01"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        let header = Header::new(breadcrumb).anonymous(true);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30 + 4, 3));
+        header.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "┌STU (anonymous: read-only)──────┐",
+            "│ bucket / key01                 │",
+            "└────────────────────────────────┘",
+        ]);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_render_header_requester_pays() {
+        let breadcrumb = ["bucket", "key01"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        let header = Header::new(breadcrumb).requester_pays(true);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 36 + 4, 3));
+        header.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "┌STU (requester pays: charges apply)───┐",
+            "│ bucket / key01                       │",
+            "└──────────────────────────────────────┘",
+        ]);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_render_header_region() {
+        let breadcrumb = ["bucket", "key01"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        let header = Header::new(breadcrumb).region(Some("us-west-2".into()));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30 + 4, 3));
+        header.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "┌STU────────────────────us-west-2┐",
+            "│ bucket / key01                 │",
+            "└────────────────────────────────┘",
+        ]);
+        assert_eq!(buf, expected);
+    }
+
     #[test]
     fn test_render_header_empty() {
         let header = Header::new(vec![]);
@@ -0,0 +1,104 @@
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+/// Color of the matched characters of filters and searches.
+pub const HIGHLIGHT_COLOR: Color = Color::Red;
+
+/// Builds a line of `s` with the characters at the char `indices` highlighted.
+pub fn highlighted_line(s: &str, indices: &[usize]) -> Line<'static> {
+    Line::from(highlighted_spans(s, indices, Style::default()))
+}
+
+/// Splits `s` into spans with the characters at the char `indices` (ascending) highlighted on top of `style`.
+///
+/// Adjacent matched characters are merged into one span, and indices out of `s` are ignored.
+/// Characters are never split, so full-width characters keep their width.
+pub fn highlighted_spans(s: &str, indices: &[usize], style: Style) -> Vec<Span<'static>> {
+    let highlight_style = style.fg(HIGHLIGHT_COLOR);
+    let mut indices = indices.iter().peekable();
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_matched = false;
+    for (i, c) in s.chars().enumerate() {
+        while indices.next_if(|&&j| j < i).is_some() {}
+        let matched = indices.next_if_eq(&&i).is_some();
+        if matched != current_matched && !current.is_empty() {
+            let s = std::mem::take(&mut current);
+            let style = if current_matched {
+                highlight_style
+            } else {
+                style
+            };
+            spans.push(Span::styled(s, style));
+        }
+        current_matched = matched;
+        current.push(c);
+    }
+    if !current.is_empty() {
+        let style = if current_matched {
+            highlight_style
+        } else {
+            style
+        };
+        spans.push(Span::styled(current, style));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{buffer::Buffer, layout::Rect, style::Stylize, widgets::Widget};
+    use rstest::rstest;
+
+    use crate::set_cells;
+
+    use super::*;
+
+    #[rstest]
+    #[case("abcdef", &[], &[("abcdef", false)])]
+    #[case("abcdef", &[0], &[("a", true), ("bcdef", false)])]
+    #[case("abcdef", &[5], &[("abcde", false), ("f", true)])]
+    #[case("abcdef", &[1, 2, 3], &[("a", false), ("bcd", true), ("ef", false)])]
+    #[case("abcdef", &[0, 2, 3, 5], &[("a", true), ("b", false), ("cd", true), ("e", false), ("f", true)])]
+    #[case("abcdef", &[0, 1, 2, 3, 4, 5], &[("abcdef", true)])]
+    #[case("abc", &[1, 9], &[("a", false), ("b", true), ("c", false)])]
+    #[case("あいうえお", &[1, 2], &[("あ", false), ("いう", true), ("えお", false)])]
+    #[case("", &[0], &[])]
+    fn test_highlighted_spans(
+        #[case] s: &str,
+        #[case] indices: &[usize],
+        #[case] expected: &[(&str, bool)],
+    ) {
+        let style = Style::default().bold();
+        let actual = highlighted_spans(s, indices, style);
+        let expected: Vec<Span> = expected
+            .iter()
+            .map(|(s, matched)| {
+                let style = if *matched {
+                    style.fg(Color::Red)
+                } else {
+                    style
+                };
+                Span::styled(s.to_string(), style)
+            })
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_render_highlighted_line_full_width() {
+        let line = highlighted_line("aあいb", &[1, 3]);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 1));
+        line.render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(["aあいb  "]);
+        set_cells! { expected =>
+            ([1], [0]) => fg: Color::Red,
+            ([5], [0]) => fg: Color::Red,
+        }
+
+        assert_eq!(buf, expected);
+    }
+}
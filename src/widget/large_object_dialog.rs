@@ -0,0 +1,112 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{block::Title, Block, BorderType, Padding, Paragraph, Widget, WidgetRef},
+};
+
+use crate::{
+    ui::common::{calc_centered_dialog_rect, format_size_byte},
+    widget::Dialog,
+};
+
+const KEY_COLOR: Color = Color::Cyan;
+
+#[derive(Debug, Clone, Copy)]
+pub struct LargeObjectDialogState {
+    size_byte: usize,
+    max_size_byte: usize,
+}
+
+impl LargeObjectDialogState {
+    pub fn new(size_byte: usize, max_size_byte: usize) -> Self {
+        Self {
+            size_byte,
+            max_size_byte,
+        }
+    }
+}
+
+pub struct LargeObjectDialog {
+    state: LargeObjectDialogState,
+}
+
+impl LargeObjectDialog {
+    pub fn new(state: LargeObjectDialogState) -> Self {
+        Self { state }
+    }
+}
+
+impl Widget for LargeObjectDialog {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = area.width.saturating_sub(4).min(50);
+        let text_width = dialog_width.saturating_sub(4 /* border + padding */) as usize;
+
+        let msg = format!(
+            "Object is {} — too large to preview.",
+            format_size_byte(self.state.size_byte)
+        );
+        let mut lines: Vec<Line> = textwrap::wrap(&msg, text_width.max(1))
+            .into_iter()
+            .map(|s| Line::raw(s.into_owned()))
+            .collect();
+        lines.push(Line::raw(""));
+        lines.push(Line::from(vec!["Download? ".into(), "(d)".fg(KEY_COLOR)]));
+        lines.push(Line::from(vec![
+            format!(
+                "Preview the first {}? ",
+                format_size_byte(self.state.max_size_byte)
+            )
+            .into(),
+            "(p)".fg(KEY_COLOR),
+        ]));
+
+        let dialog_height = lines.len() as u16 + 2 /* border */;
+        let area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
+
+        let title = Title::from("Large object");
+        let paragraph = Paragraph::new(lines).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .padding(Padding::horizontal(1)),
+        );
+        let dialog = Dialog::new(Box::new(paragraph));
+        dialog.render_ref(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::set_cells;
+
+    use super::*;
+
+    #[test]
+    fn test_render_large_object_dialog() {
+        let state = LargeObjectDialogState::new(240 * 1024 * 1024, 5 * 1024 * 1024);
+        let dialog = LargeObjectDialog::new(state);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 36, 8));
+        dialog.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "  ╭Large object──────────────────╮  ",
+            "  │ Object is 240 MiB — too      │  ",
+            "  │ large to preview.            │  ",
+            "  │                              │  ",
+            "  │ Download? (d)                │  ",
+            "  │ Preview the first 5 MiB? (p) │  ",
+            "  ╰──────────────────────────────╯  ",
+            "                                    ",
+        ]);
+        set_cells! { expected =>
+            (14..17, [4]) => fg: Color::Cyan,
+            (29..32, [5]) => fg: Color::Cyan,
+        }
+
+        assert_eq!(buf, expected);
+    }
+}
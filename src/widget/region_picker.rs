@@ -0,0 +1,223 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{block::Title, Block, BorderType, Padding, Paragraph, StatefulWidget, WidgetRef},
+};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use crate::{
+    ui::common::calc_centered_dialog_rect,
+    util::substring_match_indices,
+    widget::{highlighted_line, Dialog},
+};
+
+const SELECTED_COLOR: Color = Color::Cyan;
+const CURRENT_COLOR: Color = Color::DarkGray;
+const MAX_ITEMS: usize = 10;
+
+const CURRENT_MARK: &str = " (current)";
+
+const COMMON_REGIONS: [&str; 17] = [
+    "us-east-1",
+    "us-east-2",
+    "us-west-1",
+    "us-west-2",
+    "ca-central-1",
+    "eu-west-1",
+    "eu-west-2",
+    "eu-west-3",
+    "eu-central-1",
+    "eu-north-1",
+    "ap-northeast-1",
+    "ap-northeast-2",
+    "ap-northeast-3",
+    "ap-southeast-1",
+    "ap-southeast-2",
+    "ap-south-1",
+    "sa-east-1",
+];
+
+#[derive(Debug, Default)]
+pub struct RegionPickerState {
+    input: Input,
+    current: String,
+    view_regions: Vec<&'static str>,
+    selected: usize,
+    cursor: (u16, u16),
+}
+
+impl RegionPickerState {
+    pub fn new(current: String) -> Self {
+        Self {
+            current,
+            view_regions: COMMON_REGIONS.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.view_regions.is_empty() {
+            return;
+        }
+        self.selected = (self.selected + 1) % self.view_regions.len();
+    }
+
+    pub fn select_prev(&mut self) {
+        if self.view_regions.is_empty() {
+            return;
+        }
+        self.selected = (self.selected + self.view_regions.len() - 1) % self.view_regions.len();
+    }
+
+    /// Returns the selected region, or the input as is if it matches none of the listed regions.
+    pub fn selected_region(&self) -> Option<String> {
+        match self.view_regions.get(self.selected) {
+            Some(region) => Some(region.to_string()),
+            None => {
+                let input = self.input.value().trim();
+                (!input.is_empty()).then(|| input.to_string())
+            }
+        }
+    }
+
+    pub fn cursor(&self) -> (u16, u16) {
+        self.cursor
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) {
+        let event = &ratatui::crossterm::event::Event::Key(key);
+        if self.input.handle_event(event).is_some_and(|c| c.value) {
+            self.filter_view_regions();
+        }
+    }
+
+    fn filter_view_regions(&mut self) {
+        let pattern = self.input.value();
+        self.view_regions = COMMON_REGIONS
+            .into_iter()
+            .filter(|region| region.contains(pattern))
+            .collect();
+        self.selected = 0;
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RegionPicker {}
+
+impl StatefulWidget for RegionPicker {
+    type State = RegionPickerState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let dialog_width = area.width.saturating_sub(4).min(40);
+        let list_height = COMMON_REGIONS.len().clamp(1, MAX_ITEMS);
+        let dialog_height = 1 /* input */ + list_height as u16 + 2 /* border */;
+        let dialog_area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
+
+        let offset = state.selected.saturating_sub(list_height - 1);
+        let pattern = state.input.value();
+
+        let mut lines = vec![Line::from(format!("> {}", pattern))];
+        if state.view_regions.is_empty() {
+            lines.push(Line::from("Press Enter to use the input").fg(CURRENT_COLOR));
+        }
+        lines.extend(
+            state
+                .view_regions
+                .iter()
+                .enumerate()
+                .skip(offset)
+                .take(list_height)
+                .map(|(i, region)| {
+                    let indices = substring_match_indices(pattern, region).unwrap_or_default();
+                    let mut line = highlighted_line(region, &indices);
+                    if *region == state.current {
+                        line.spans.push(CURRENT_MARK.fg(CURRENT_COLOR));
+                    }
+                    if i == state.selected {
+                        line.fg(SELECTED_COLOR)
+                    } else {
+                        line
+                    }
+                }),
+        );
+
+        let title = Title::from("Region");
+        let dialog_content = Paragraph::new(lines).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .padding(Padding::horizontal(1)),
+        );
+        let dialog = Dialog::new(Box::new(dialog_content));
+        dialog.render_ref(dialog_area, buf);
+
+        // update cursor position
+        let cursor_x = dialog_area.x + 2 /* border and padding */ + 2 /* prompt */;
+        let cursor_x = cursor_x + state.input.visual_cursor() as u16;
+        let cursor_y = dialog_area.y + 1;
+        state.cursor = (cursor_x, cursor_y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyCode;
+
+    use crate::set_cells;
+
+    use super::*;
+
+    #[test]
+    fn test_render_region_picker() {
+        let mut state = RegionPickerState::new("eu-west-2".into());
+        for c in "eu-w".chars() {
+            state.handle_key_event(KeyEvent::from(KeyCode::Char(c)));
+        }
+        state.select_next();
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 34, 13));
+        RegionPicker::default().render(buf.area, &mut buf, &mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "  ╭Region──────────────────────╮  ",
+            "  │ > eu-w                     │  ",
+            "  │ eu-west-1                  │  ",
+            "  │ eu-west-2 (current)        │  ",
+            "  │ eu-west-3                  │  ",
+            "  │                            │  ",
+            "  │                            │  ",
+            "  │                            │  ",
+            "  │                            │  ",
+            "  │                            │  ",
+            "  │                            │  ",
+            "  │                            │  ",
+            "  ╰────────────────────────────╯  ",
+        ]);
+        set_cells! { expected =>
+            (4..8, [2, 3, 4]) => fg: Color::Red,
+            (8..13, [3]) => fg: Color::Cyan,
+            (13..23, [3]) => fg: Color::DarkGray,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_selected_region() {
+        let mut state = RegionPickerState::new("ap-northeast-1".into());
+        assert_eq!(state.selected_region().as_deref(), Some("us-east-1"));
+
+        state.select_prev();
+        assert_eq!(state.selected_region().as_deref(), Some("sa-east-1"));
+
+        for c in "me-south-1".chars() {
+            state.handle_key_event(KeyEvent::from(KeyCode::Char(c)));
+        }
+        // not listed, so the input is used as is
+        assert_eq!(state.selected_region().as_deref(), Some("me-south-1"));
+    }
+}
@@ -0,0 +1,150 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Stylize},
+    text::Line,
+    widgets::{block::Title, Block, BorderType, Padding, Paragraph, Widget, WidgetRef},
+};
+
+use crate::{object::RestoreTier, ui::common::calc_centered_dialog_rect, widget::Dialog};
+
+const SELECTED_COLOR: Color = Color::Cyan;
+
+const DEFAULT_DAYS: i32 = 1;
+const MAX_DAYS: i32 = 30;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RestoreDialogState {
+    tier: RestoreTier,
+    days: i32,
+}
+
+impl Default for RestoreDialogState {
+    fn default() -> Self {
+        Self {
+            tier: RestoreTier::default(),
+            days: DEFAULT_DAYS,
+        }
+    }
+}
+
+impl RestoreDialogState {
+    pub fn select_next(&mut self) {
+        self.tier = self.tier.next();
+    }
+
+    pub fn select_prev(&mut self) {
+        self.tier = self.tier.prev();
+    }
+
+    pub fn increase_days(&mut self) {
+        self.days = (self.days + 1).min(MAX_DAYS);
+    }
+
+    pub fn decrease_days(&mut self) {
+        self.days = (self.days - 1).max(1);
+    }
+
+    pub fn tier(&self) -> RestoreTier {
+        self.tier
+    }
+
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+}
+
+pub struct RestoreDialog {
+    state: RestoreDialogState,
+}
+
+impl RestoreDialog {
+    pub fn new(state: RestoreDialogState) -> Self {
+        Self { state }
+    }
+}
+
+impl Widget for RestoreDialog {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut lines: Vec<Line> = RestoreTier::vars_vec()
+            .iter()
+            .map(|tier| {
+                let line = Line::raw(tier.name());
+                if *tier == self.state.tier {
+                    line.fg(SELECTED_COLOR)
+                } else {
+                    line
+                }
+            })
+            .collect();
+        lines.push(Line::raw(""));
+        lines.push(Line::from(vec![
+            "Days: ".add_modifier(Modifier::BOLD),
+            format!("< {} >", self.state.days).into(),
+        ]));
+
+        let dialog_width = area.width.saturating_sub(4).min(40);
+        let dialog_height = lines.len() as u16 + 2 /* border */;
+        let area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
+
+        let title = Title::from("Restore object");
+        let paragraph = Paragraph::new(lines).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .padding(Padding::horizontal(1)),
+        );
+        let dialog = Dialog::new(Box::new(paragraph));
+        dialog.render_ref(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::set_cells;
+
+    use super::*;
+
+    #[test]
+    fn test_render_restore_dialog() {
+        let mut state = RestoreDialogState::default();
+        state.select_next();
+        state.increase_days();
+        let dialog = RestoreDialog::new(state);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 9));
+        dialog.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "                              ",
+            "  ╭Restore object──────────╮  ",
+            "  │ Expedited              │  ",
+            "  │ Standard               │  ",
+            "  │ Bulk                   │  ",
+            "  │                        │  ",
+            "  │ Days: < 2 >            │  ",
+            "  ╰────────────────────────╯  ",
+            "                              ",
+        ]);
+        set_cells! { expected =>
+            // selected tier
+            (4..8, [4]) => fg: Color::Cyan,
+            // label
+            (4..10, [6]) => modifier: Modifier::BOLD,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_restore_dialog_days_range() {
+        let mut state = RestoreDialogState::default();
+        state.decrease_days();
+        assert_eq!(state.days(), 1);
+        for _ in 0..40 {
+            state.increase_days();
+        }
+        assert_eq!(state.days(), 30);
+    }
+}
@@ -0,0 +1,155 @@
+use itsuki::zero_indexed_enum;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{block::Title, Block, BorderType, List, ListItem, Padding, Widget, WidgetRef},
+};
+
+use crate::{ui::common::calc_centered_dialog_rect, widget::Dialog};
+
+const SELECTED_COLOR: Color = Color::Cyan;
+
+#[derive(Default)]
+#[zero_indexed_enum]
+pub enum SaveConfirmAction {
+    Overwrite,
+    // not to overwrite the file by pressing Enter again
+    #[default]
+    Rename,
+    Cancel,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SaveConfirmDialogState {
+    selected: SaveConfirmAction,
+    input: String,
+    renamed_input: String,
+}
+
+impl SaveConfirmDialogState {
+    pub fn new(input: String, renamed_input: String) -> Self {
+        Self {
+            selected: SaveConfirmAction::default(),
+            input,
+            renamed_input,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = self.selected.next();
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.prev();
+    }
+
+    pub fn selected(&self) -> SaveConfirmAction {
+        self.selected
+    }
+
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn renamed_input(&self) -> &str {
+        &self.renamed_input
+    }
+}
+
+pub struct SaveConfirmDialog<'a> {
+    state: &'a SaveConfirmDialogState,
+}
+
+impl<'a> SaveConfirmDialog<'a> {
+    pub fn new(state: &'a SaveConfirmDialogState) -> Self {
+        Self { state }
+    }
+}
+
+impl Widget for SaveConfirmDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let selected = self.state.selected.val();
+        let list_items: Vec<ListItem> = SaveConfirmAction::vars_vec()
+            .iter()
+            .enumerate()
+            .map(|(i, action)| {
+                let label = match action {
+                    SaveConfirmAction::Overwrite => "Overwrite".to_string(),
+                    SaveConfirmAction::Rename => {
+                        format!("Save as '{}'", self.state.renamed_input)
+                    }
+                    SaveConfirmAction::Cancel => "Cancel".to_string(),
+                };
+                let item = ListItem::new(Line::raw(label));
+                if i == selected {
+                    item.fg(SELECTED_COLOR)
+                } else {
+                    item
+                }
+            })
+            .collect();
+
+        let dialog_width = area.width.saturating_sub(4).min(60);
+        let dialog_height = 3 /* list */ + 2 /* border */;
+        let area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
+
+        let title = Title::from("File already exists");
+        let list = List::new(list_items).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .padding(Padding::horizontal(1)),
+        );
+        let dialog = Dialog::new(Box::new(list));
+        dialog.render_ref(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::set_cells;
+
+    use super::*;
+
+    #[test]
+    fn test_render_save_confirm_dialog() {
+        let state = SaveConfirmDialogState::new("file.txt".into(), "file (1).txt".into());
+        let dialog = SaveConfirmDialog::new(&state);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 34, 7));
+        dialog.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "                                  ",
+            "  ╭File already exists─────────╮  ",
+            "  │ Overwrite                  │  ",
+            "  │ Save as 'file (1).txt'     │  ",
+            "  │ Cancel                     │  ",
+            "  ╰────────────────────────────╯  ",
+            "                                  ",
+        ]);
+        set_cells! { expected =>
+            // renaming is selected by default
+            (4..30, [3]) => fg: Color::Cyan,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_select_save_confirm_action() {
+        let mut state = SaveConfirmDialogState::new("file.txt".into(), "file (1).txt".into());
+        assert_eq!(state.selected(), SaveConfirmAction::Rename);
+        state.select_prev();
+        assert_eq!(state.selected(), SaveConfirmAction::Overwrite);
+        state.select_prev();
+        assert_eq!(state.selected(), SaveConfirmAction::Cancel);
+        state.select_next();
+        assert_eq!(state.selected(), SaveConfirmAction::Overwrite);
+        state.select_next();
+        assert_eq!(state.selected(), SaveConfirmAction::Rename);
+    }
+}
@@ -6,6 +6,8 @@ use ratatui::{
     widgets::{block::BlockExt, Block, Borders, Padding, Paragraph, StatefulWidget, Widget, Wrap},
 };
 
+use unicode_width::UnicodeWidthChar;
+
 use crate::util::digits;
 
 const PREVIEW_LINE_NUMBER_COLOR: Color = Color::DarkGray;
@@ -22,17 +24,40 @@ enum ScrollEvent {
     End,
     Right,
     Left,
+    LineStart,
+    LineEnd,
+    WordRight,
+    WordLeft,
 }
 
 #[derive(Debug, Clone)]
 pub struct ScrollLinesOptions {
     pub number: bool,
     pub wrap: bool,
+    /// If set, lines are wrapped at this width instead of the width of the area.
+    pub wrap_width: Option<usize>,
+    /// If set, line numbers are hidden while the content would be narrower than this width with them.
+    pub min_content_width: Option<usize>,
 }
 
 impl ScrollLinesOptions {
     pub fn new(number: bool, wrap: bool) -> Self {
-        Self { number, wrap }
+        Self {
+            number,
+            wrap,
+            wrap_width: None,
+            min_content_width: None,
+        }
+    }
+
+    pub fn wrap_width(mut self, wrap_width: Option<usize>) -> Self {
+        self.wrap_width = wrap_width.filter(|w| *w > 0);
+        self
+    }
+
+    pub fn min_content_width(mut self, min_content_width: Option<usize>) -> Self {
+        self.min_content_width = min_content_width.filter(|w| *w > 0);
+        self
     }
 }
 
@@ -67,6 +92,33 @@ impl ScrollLinesState {
         }
     }
 
+    pub fn with_offset(mut self, v_offset: usize, h_offset: usize) -> Self {
+        self.v_offset = v_offset.min(self.lines.len().saturating_sub(1));
+        self.h_offset = h_offset.min(self.max_line_width.saturating_sub(1));
+        self
+    }
+
+    pub fn offset(&self) -> (usize, usize) {
+        (self.v_offset, self.h_offset)
+    }
+
+    pub fn options(&self) -> &ScrollLinesOptions {
+        &self.options
+    }
+
+    pub fn lines(&self) -> &[Line<'static>] {
+        &self.lines
+    }
+
+    /// Replaces the lines while keeping the options and the current position.
+    pub fn replace_lines(&mut self, lines: Vec<Line<'static>>) {
+        self.max_digits = digits(lines.len());
+        self.max_line_width = lines.iter().map(Line::width).max().unwrap_or_default();
+        self.lines = lines;
+        self.v_offset = self.v_offset.min(self.lines.len().saturating_sub(1));
+        self.h_offset = self.h_offset.min(self.max_line_width.saturating_sub(1));
+    }
+
     pub fn scroll_forward(&mut self) {
         self.scroll_event = ScrollEvent::Forward;
     }
@@ -99,6 +151,27 @@ impl ScrollLinesState {
         self.scroll_event = ScrollEvent::Left;
     }
 
+    pub fn scroll_to_line_start(&mut self) {
+        self.scroll_event = ScrollEvent::LineStart;
+    }
+
+    pub fn scroll_to_line_end(&mut self) {
+        self.scroll_event = ScrollEvent::LineEnd;
+    }
+
+    pub fn scroll_word_right(&mut self) {
+        self.scroll_event = ScrollEvent::WordRight;
+    }
+
+    pub fn scroll_word_left(&mut self) {
+        self.scroll_event = ScrollEvent::WordLeft;
+    }
+
+    /// Scrolls so that the line is shown at the top.
+    pub fn scroll_to_line(&mut self, line: usize) {
+        self.v_offset = line.min(self.lines.len().saturating_sub(1));
+    }
+
     pub fn toggle_wrap(&mut self) {
         self.options.wrap = !self.options.wrap;
         self.h_offset = 0;
@@ -133,24 +206,64 @@ impl StatefulWidget for ScrollLines {
         } else {
             0
         };
+        // the numbers are hidden only while the area is narrow, the option itself is kept
+        let content_width = content_area
+            .width
+            .saturating_sub(line_numbers_width + 2 /* padding */);
+        let line_numbers_width = match state.options.min_content_width {
+            Some(min) if (content_width as usize) < min => 0,
+            _ => line_numbers_width,
+        };
 
         let chunks =
             Layout::horizontal([Constraint::Length(line_numbers_width), Constraint::Min(0)])
                 .split(content_area);
 
         let show_lines_count = content_area.height as usize;
-        let text_area_width = chunks[1].width as usize - 2 /* padding */;
+        let text_area_width = (chunks[1].width as usize).saturating_sub(2 /* padding */);
+        let wrap_width = match state.options.wrap_width {
+            Some(w) if state.options.wrap => w,
+            _ => text_area_width,
+        };
 
         // handle scroll events and update the state
-        handle_scroll_events(state, text_area_width, show_lines_count);
+        handle_scroll_events(state, text_area_width, wrap_width, show_lines_count);
 
         let line_numbers_paragraph =
-            build_line_numbers_paragraph(state, text_area_width, show_lines_count);
+            build_line_numbers_paragraph(state, wrap_width, show_lines_count);
         let lines_paragraph = build_lines_paragraph(state, show_lines_count);
 
         self.block.render(area, buf);
         line_numbers_paragraph.render(chunks[0], buf);
-        lines_paragraph.render(chunks[1], buf);
+        if wrap_width == text_area_width {
+            lines_paragraph.render(chunks[1], buf);
+        } else {
+            render_fixed_width_lines(lines_paragraph, chunks[1], buf, wrap_width, state.h_offset);
+        }
+    }
+}
+
+// render the wrapped lines in a separate buffer and copy the visible columns, to scroll horizontally
+fn render_fixed_width_lines(
+    paragraph: Paragraph,
+    area: Rect,
+    buf: &mut Buffer,
+    wrap_width: usize,
+    h_offset: usize,
+) {
+    let lines_area = Rect::new(0, 0, (wrap_width + 2/* padding */) as u16, area.height);
+    let mut lines_buf = Buffer::empty(lines_area);
+    paragraph.render(lines_area, &mut lines_buf);
+
+    let text_area_width = area.width.saturating_sub(2 /* padding */);
+    for y in 0..area.height {
+        for x in 0..text_area_width {
+            let src_x = 1 /* padding */ + h_offset as u16 + x;
+            if src_x >= lines_area.width.saturating_sub(1) {
+                break;
+            }
+            *buf.get_mut(area.x + 1 + x, area.y + y) = lines_buf.get(src_x, y).clone();
+        }
     }
 }
 
@@ -158,7 +271,7 @@ fn build_line_numbers_paragraph(
     state: &ScrollLinesState,
     text_area_width: usize,
     show_lines_count: usize,
-) -> Paragraph {
+) -> Paragraph<'_> {
     // may not be correct because the wrap of the text is calculated separately...
     let line_heights = wrapped_line_width_iter(
         &state.lines,
@@ -176,7 +289,7 @@ fn build_line_numbers_paragraph(
             } else {
                 let line_number = format!("{:>width$}", line, width = state.max_digits);
                 let number_line: Line = line_number.fg(PREVIEW_LINE_NUMBER_COLOR).into();
-                let empty_lines = (0..(line_height - 1)).map(|_| Line::raw(""));
+                let empty_lines = (0..line_height.saturating_sub(1)).map(|_| Line::raw(""));
                 std::iter::once(number_line).chain(empty_lines).collect()
             }
         })
@@ -190,7 +303,7 @@ fn build_line_numbers_paragraph(
     )
 }
 
-fn build_lines_paragraph(state: &ScrollLinesState, show_lines_count: usize) -> Paragraph {
+fn build_lines_paragraph(state: &ScrollLinesState, show_lines_count: usize) -> Paragraph<'_> {
     let lines_content: Vec<Line> = state
         .lines
         .iter()
@@ -212,7 +325,13 @@ fn build_lines_paragraph(state: &ScrollLinesState, show_lines_count: usize) -> P
     }
 }
 
-fn handle_scroll_events(state: &mut ScrollLinesState, width: usize, height: usize) {
+fn handle_scroll_events(
+    state: &mut ScrollLinesState,
+    text_area_width: usize,
+    wrap_width: usize,
+    height: usize,
+) {
+    let width = wrap_width;
     match state.scroll_event {
         ScrollEvent::None => {}
         ScrollEvent::Forward => {
@@ -286,7 +405,13 @@ fn handle_scroll_events(state: &mut ScrollLinesState, width: usize, height: usiz
             state.v_offset = state.lines.len().saturating_sub(1);
         }
         ScrollEvent::Right => {
-            if state.h_offset < state.max_line_width.saturating_sub(1) {
+            let max_h_offset = if state.options.wrap {
+                // only the overflow of the fixed wrap width can be scrolled
+                wrap_width.saturating_sub(text_area_width)
+            } else {
+                state.max_line_width.saturating_sub(1)
+            };
+            if state.h_offset < max_h_offset {
                 state.h_offset = state.h_offset.saturating_add(1);
             }
         }
@@ -295,6 +420,33 @@ fn handle_scroll_events(state: &mut ScrollLinesState, width: usize, height: usiz
                 state.h_offset = state.h_offset.saturating_sub(1);
             }
         }
+        ScrollEvent::LineStart => {
+            state.h_offset = 0;
+        }
+        ScrollEvent::LineEnd => {
+            if !state.options.wrap {
+                state.h_offset = max_visible_h_offset(state, width, height);
+            }
+        }
+        ScrollEvent::WordRight => {
+            if !state.options.wrap {
+                let max_h_offset = max_visible_h_offset(state, width, height);
+                let next = widest_visible_line(state, height)
+                    .map(word_start_columns)
+                    .and_then(|cols| cols.into_iter().find(|&c| c > state.h_offset))
+                    .unwrap_or(max_h_offset);
+                state.h_offset = next.min(max_h_offset).max(state.h_offset);
+            }
+        }
+        ScrollEvent::WordLeft => {
+            if !state.options.wrap {
+                let prev = widest_visible_line(state, height)
+                    .map(word_start_columns)
+                    .and_then(|cols| cols.into_iter().rev().find(|&c| c < state.h_offset))
+                    .unwrap_or(0);
+                state.h_offset = prev;
+            }
+        }
     }
     // reset the scroll event
     state.scroll_event = ScrollEvent::None;
@@ -341,6 +493,38 @@ fn wrapped_reversed_line_width_iter<'a>(
         })
 }
 
+fn widest_visible_line<'a>(state: &'a ScrollLinesState, height: usize) -> Option<&'a Line<'a>> {
+    state
+        .lines
+        .iter()
+        .skip(state.v_offset)
+        .take(height)
+        .max_by_key(|line| line.width())
+}
+
+// the offset at which the end of the widest visible line is shown at the right edge
+fn max_visible_h_offset(state: &ScrollLinesState, width: usize, height: usize) -> usize {
+    widest_visible_line(state, height)
+        .map(|line| line.width().saturating_sub(width))
+        .unwrap_or_default()
+}
+
+// columns (not char indices) where each word starts
+fn word_start_columns(line: &Line) -> Vec<usize> {
+    let mut cols = Vec::new();
+    let mut col = 0;
+    let mut prev_whitespace = true;
+    for c in line_to_string(line).chars() {
+        let whitespace = c.is_whitespace();
+        if prev_whitespace && !whitespace {
+            cols.push(col);
+        }
+        prev_whitespace = whitespace;
+        col += c.width().unwrap_or_default();
+    }
+    cols
+}
+
 fn line_to_string(line: &Line) -> String {
     line.styled_graphemes(Style::default())
         .map(|g| g.symbol)
@@ -678,6 +862,274 @@ mod tests {
         assert_eq!(buf, expected);
     }
 
+    #[test]
+    fn test_scroll_lines_with_offset() {
+        let mut state = state(true, true).with_offset(8, 3);
+
+        let buf = render_scroll_lines(&mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌TITLE─────────────┐",
+            "│  9 a             │",
+            "│ 10 b             │",
+            "│ 11 c             │",
+            "│ 12 d             │",
+            "│ 13 e             │",
+            "└──────────────────┘",
+        ]);
+        set_cells! { expected =>
+            ([2, 3], [1, 2, 3, 4, 5]) => fg: Color::DarkGray,
+        }
+
+        assert_eq!(buf, expected);
+        assert_eq!(state.offset(), (8, 3));
+    }
+
+    #[test]
+    fn test_scroll_lines_replace_lines() {
+        let mut state = state(true, true).with_offset(3, 0);
+
+        let lines = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l"]
+            .iter()
+            .cloned()
+            .map(Line::raw)
+            .collect();
+        state.replace_lines(lines);
+        assert_eq!(state.offset(), (3, 0));
+        state.scroll_to_end();
+
+        let buf = render_scroll_lines(&mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌TITLE─────────────┐",
+            "│ 12 l             │",
+            "│                  │",
+            "│                  │",
+            "│                  │",
+            "│                  │",
+            "└──────────────────┘",
+        ]);
+        set_cells! { expected =>
+            ([2, 3], [1]) => fg: Color::DarkGray,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_scroll_lines_with_offset_out_of_range() {
+        let state = state(true, true).with_offset(100, 100);
+        assert_eq!(state.offset(), (15, 26));
+    }
+
+    #[test]
+    fn test_scroll_lines_line_start_end() {
+        let mut state = state(true, false);
+
+        // text area width is 13, and the widest visible line is "aaa bbb ccc ddd eee" (19)
+        state.scroll_to_line_end();
+        render_scroll_lines(&mut state);
+        assert_eq!(state.offset(), (0, 6));
+
+        state.scroll_to_line_end();
+        render_scroll_lines(&mut state);
+        assert_eq!(state.offset(), (0, 6));
+
+        state.scroll_to_line_start();
+        render_scroll_lines(&mut state);
+        assert_eq!(state.offset(), (0, 0));
+
+        // the widest visible line is "aaa bbb ccc ddd eee fff ggg" (27)
+        let mut state = state.with_offset(12, 0);
+        state.scroll_to_line_end();
+        let buf = render_scroll_lines(&mut state);
+        assert_eq!(state.offset(), (12, 14));
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌TITLE─────────────┐",
+            "│ 13               │",
+            "│ 14 d eee fff ggg │",
+            "│ 15               │",
+            "│ 16               │",
+            "│                  │",
+            "└──────────────────┘",
+        ]);
+        set_cells! { expected =>
+            ([2, 3], [1, 2, 3, 4]) => fg: Color::DarkGray,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_scroll_lines_word_right_left() {
+        let mut state = state(true, false);
+
+        // word starts of "aaa bbb ccc ddd eee" are 0, 4, 8, 12, 16, and the max offset is 6
+        for expected in [4, 6, 6] {
+            state.scroll_word_right();
+            render_scroll_lines(&mut state);
+            assert_eq!(state.offset(), (0, expected));
+        }
+        for expected in [4, 0, 0] {
+            state.scroll_word_left();
+            render_scroll_lines(&mut state);
+            assert_eq!(state.offset(), (0, expected));
+        }
+    }
+
+    #[test]
+    fn test_scroll_lines_line_end_wrap() {
+        let mut state = state(true, true);
+
+        state.scroll_to_line_end();
+        state.scroll_word_right();
+        render_scroll_lines(&mut state);
+        assert_eq!(state.offset(), (0, 0));
+    }
+
+    #[test]
+    fn test_scroll_lines_fixed_wrap_width_narrower() {
+        let mut state = state_with_wrap_width(7);
+
+        let buf = render_scroll_lines(&mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌TITLE─────────────┐",
+            "│  1 aaa bbb       │",
+            "│    ccc ddd       │",
+            "│  2 aaa bbb       │",
+            "│    ccc           │",
+            "│  3 aaa           │",
+            "└──────────────────┘",
+        ]);
+        set_cells! { expected =>
+            ([2, 3], [1, 3, 5]) => fg: Color::DarkGray,
+        }
+
+        assert_eq!(buf, expected);
+
+        state.scroll_page_forward();
+        let buf = render_scroll_lines(&mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌TITLE─────────────┐",
+            "│  4 aaa bbb       │",
+            "│  5 aaa bbb       │",
+            "│    ccc ddd       │",
+            "│    eee           │",
+            "│  6 aaaaaaa       │",
+            "└──────────────────┘",
+        ]);
+        set_cells! { expected =>
+            ([2, 3], [1, 2, 5]) => fg: Color::DarkGray,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_scroll_lines_fixed_wrap_width_wider() {
+        let mut state = state_with_wrap_width(20);
+
+        let buf = render_scroll_lines(&mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌TITLE─────────────┐",
+            "│  1 aaa bbb ccc d │",
+            "│  2 aaa bbb ccc   │",
+            "│  3 aaa           │",
+            "│  4 aaa bbb       │",
+            "│  5 aaa bbb ccc d │",
+            "└──────────────────┘",
+        ]);
+        set_cells! { expected =>
+            ([2, 3], [1, 2, 3, 4, 5]) => fg: Color::DarkGray,
+        }
+
+        assert_eq!(buf, expected);
+
+        // the overflow can be reached by scrolling horizontally, up to the wrap width
+        for _ in 0..10 {
+            state.scroll_right();
+            render_scroll_lines(&mut state);
+        }
+        assert_eq!(state.offset(), (0, 7));
+
+        let buf = render_scroll_lines(&mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌TITLE─────────────┐",
+            "│  1  ccc ddd      │",
+            "│  2  ccc          │",
+            "│  3               │",
+            "│  4               │",
+            "│  5  ccc ddd eee  │",
+            "└──────────────────┘",
+        ]);
+        set_cells! { expected =>
+            ([2, 3], [1, 2, 3, 4, 5]) => fg: Color::DarkGray,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_auto_hide_line_numbers_on_narrow_area() {
+        let state = state(true, true);
+        let options = ScrollLinesOptions::new(true, true).min_content_width(Some(16));
+        let mut state = ScrollLinesState::new(state.lines, options);
+
+        let buf = render_scroll_lines(&mut state);
+
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "┌TITLE─────────────┐",
+            "│ aaa bbb ccc ddd  │",
+            "│ aaa bbb ccc      │",
+            "│ aaa              │",
+            "│ aaa bbb          │",
+            "│ aaa bbb ccc ddd  │",
+            "└──────────────────┘",
+        ]);
+
+        assert_eq!(buf, expected);
+
+        // shown again when the area is wide enough
+        let scroll_lines = ScrollLines::default().block(Block::bordered().title("TITLE"));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 5 + 2));
+        scroll_lines.render(buf.area, &mut buf, &mut state);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "┌TITLE───────────────────────┐",
+            "│  1 aaa bbb ccc ddd         │",
+            "│  2 aaa bbb ccc             │",
+            "│  3 aaa                     │",
+            "│  4 aaa bbb                 │",
+            "│  5 aaa bbb ccc ddd eee     │",
+            "└────────────────────────────┘",
+        ]);
+        set_cells! { expected =>
+            ([2, 3], [1, 2, 3, 4, 5]) => fg: Color::DarkGray,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    fn state_with_wrap_width(wrap_width: usize) -> ScrollLinesState {
+        let state = state(true, true);
+        let options = ScrollLinesOptions::new(true, true).wrap_width(Some(wrap_width));
+        ScrollLinesState::new(state.lines, options)
+    }
+
     fn state(number: bool, wrap: bool) -> ScrollLinesState {
         let lines: Vec<Line> = [
             "aaa bbb ccc ddd",
@@ -701,7 +1153,7 @@ mod tests {
         .cloned()
         .map(Line::raw)
         .collect();
-        let options = ScrollLinesOptions { number, wrap };
+        let options = ScrollLinesOptions::new(number, wrap);
         ScrollLinesState::new(lines, options)
     }
 
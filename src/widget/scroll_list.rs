@@ -14,22 +14,65 @@ pub struct ScrollListState {
     pub offset: usize,
     total: usize,
     height: usize,
+    wrap: bool,
 }
 
 impl ScrollListState {
     pub fn new(total: usize) -> ScrollListState {
         ScrollListState {
             total,
+            wrap: true,
             ..Default::default()
         }
     }
 
+    /// Sets whether moving past the last (or before the first) item moves the selection to the other end.
+    pub fn with_wrap(mut self, wrap: bool) -> ScrollListState {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index >= self.total {
+            return;
+        }
+        self.selected = index;
+        if self.height == 0 || index < self.offset {
+            // not rendered yet, so show the selected item at the top
+            self.offset = index;
+        } else if index >= self.offset + self.height {
+            self.offset = index + 1 - self.height;
+        }
+    }
+
+    /// Updates the number of items and selects `index`, keeping the selection at the same row.
+    pub fn update_total(&mut self, total: usize, index: usize) {
+        let row = self.selected.saturating_sub(self.offset);
+        self.total = total;
+        self.selected = index.min(total.saturating_sub(1));
+        self.offset = self.selected.saturating_sub(row);
+    }
+
     pub fn select_next(&mut self) {
         if self.total == 0 {
             return;
         }
         if self.selected >= self.total - 1 {
-            self.select_first();
+            if self.wrap {
+                self.select_first();
+            }
         } else {
             if self.selected - self.offset == self.height - 1 {
                 self.offset += 1;
@@ -43,7 +86,9 @@ impl ScrollListState {
             return;
         }
         if self.selected == 0 {
-            self.select_last();
+            if self.wrap {
+                self.select_last();
+            }
         } else {
             if self.selected - self.offset == 0 {
                 self.offset -= 1;
@@ -126,7 +171,7 @@ impl StatefulWidget for ScrollList<'_> {
     type State = ScrollListState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        state.height = area.height as usize - 2 /* border */;
+        state.height = (area.height as usize).saturating_sub(2 /* border */);
 
         let title = format_list_count(state.total, state.selected);
         let list = List::new(self.items).block(
@@ -159,6 +204,7 @@ fn format_list_count(total_count: usize, selected: usize) -> String {
 #[cfg(test)]
 mod tests {
     use ratatui::text::Line;
+    use rstest::rstest;
 
     use super::*;
 
@@ -263,6 +309,59 @@ mod tests {
         assert_eq!(buf, expected);
     }
 
+    #[test]
+    fn test_scroll_list_select() {
+        let mut state = ScrollListState::new(20);
+
+        // before rendering, the selected item is shown at the top
+        state.select(15);
+        assert_eq!((state.selected, state.offset), (15, 15));
+
+        let mut state = ScrollListState::new(20);
+        render_scroll_list(&mut state);
+
+        state.select(5);
+        assert_eq!((state.selected, state.offset), (5, 0));
+        state.select(15);
+        assert_eq!((state.selected, state.offset), (15, 6));
+        state.select(3);
+        assert_eq!((state.selected, state.offset), (3, 3));
+        state.select(20);
+        assert_eq!((state.selected, state.offset), (3, 3));
+    }
+
+    #[rstest]
+    #[case(true, (0, 0), (19, 10))]
+    #[case(false, (19, 10), (0, 0))]
+    fn test_scroll_list_select_at_boundaries(
+        #[case] wrap: bool,
+        #[case] next_from_last: (usize, usize),
+        #[case] prev_from_first: (usize, usize),
+    ) {
+        let mut state = ScrollListState::new(20).with_wrap(wrap);
+        render_scroll_list(&mut state);
+
+        state.select_last();
+        state.select_next();
+        assert_eq!((state.selected, state.offset), next_from_last);
+
+        state.select_first();
+        state.select_prev();
+        assert_eq!((state.selected, state.offset), prev_from_first);
+    }
+
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn test_scroll_list_select_empty(#[case] wrap: bool) {
+        let mut state = ScrollListState::new(0).with_wrap(wrap);
+
+        state.select_next();
+        assert_eq!((state.selected, state.offset), (0, 0));
+        state.select_prev();
+        assert_eq!((state.selected, state.offset), (0, 0));
+    }
+
     fn render_scroll_list(state: &mut ScrollListState) -> Buffer {
         let show_item_count = 10_u16;
         let items: Vec<ListItem> = (1..=20)
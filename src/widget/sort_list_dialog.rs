@@ -174,7 +174,7 @@ impl Widget for ListSortDialog {
             })
             .collect();
 
-        let dialog_width = (area.width - 4).min(30);
+        let dialog_width = area.width.saturating_sub(4).min(30);
         let dialog_height = self.labels.len() as u16 + 2 /* border */;
         let area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
 
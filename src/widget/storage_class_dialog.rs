@@ -0,0 +1,196 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Stylize},
+    text::Line,
+    widgets::{block::Title, Block, BorderType, Padding, Paragraph, Widget, WidgetRef},
+};
+
+use crate::{object::StorageClass, ui::common::calc_centered_dialog_rect, widget::Dialog};
+
+const SELECTED_COLOR: Color = Color::Cyan;
+const CURRENT_COLOR: Color = Color::DarkGray;
+const WARNING_COLOR: Color = Color::Yellow;
+
+#[derive(Debug, Clone)]
+pub struct StorageClassDialogState {
+    current: String,
+    selected: StorageClass,
+}
+
+impl StorageClassDialogState {
+    /// `current` is the name of the current storage class, which may not be one of the selectable ones.
+    pub fn new(current: impl Into<String>) -> Self {
+        let current = current.into();
+        let selected = StorageClass::vars_vec()
+            .into_iter()
+            .find(|class| class.name() == current)
+            .unwrap_or(StorageClass::Standard);
+        Self { current, selected }
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = self.selected.next();
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.prev();
+    }
+
+    pub fn selected(&self) -> StorageClass {
+        self.selected
+    }
+
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    pub fn is_current_selected(&self) -> bool {
+        self.selected.name() == self.current
+    }
+}
+
+pub struct StorageClassDialog<'a> {
+    state: &'a StorageClassDialogState,
+}
+
+impl<'a> StorageClassDialog<'a> {
+    pub fn new(state: &'a StorageClassDialogState) -> Self {
+        Self { state }
+    }
+}
+
+impl Widget for StorageClassDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut lines = vec![
+            Line::from(vec![
+                "Current: ".add_modifier(Modifier::BOLD),
+                self.state.current.as_str().into(),
+            ]),
+            Line::raw(""),
+        ];
+        lines.extend(StorageClass::vars_vec().iter().map(|class| {
+            let line = Line::raw(class.name());
+            if *class == self.state.selected {
+                line.fg(SELECTED_COLOR)
+            } else if class.name() == self.state.current {
+                line.fg(CURRENT_COLOR)
+            } else {
+                line
+            }
+        }));
+        lines.push(Line::raw(""));
+        // the notes always take two lines so that the dialog is not resized by the selection
+        let notes = build_notes(self.state);
+        lines.extend(
+            notes
+                .into_iter()
+                .chain([Line::raw(""), Line::raw("")])
+                .take(2),
+        );
+
+        let dialog_width = area.width.saturating_sub(4).min(40);
+        let dialog_height = lines.len() as u16 + 2 /* border */;
+        let area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
+
+        let title = Title::from("Change storage class");
+        let paragraph = Paragraph::new(lines).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .padding(Padding::horizontal(1)),
+        );
+        let dialog = Dialog::new(Box::new(paragraph));
+        dialog.render_ref(area, buf);
+    }
+}
+
+fn build_notes(state: &StorageClassDialogState) -> Vec<Line<'static>> {
+    if state.is_current_selected() {
+        return vec![Line::raw("Already in this storage class").fg(CURRENT_COLOR)];
+    }
+    let mut notes = Vec::new();
+    if let Some(days) = state.selected.min_storage_days() {
+        let note = format!("Charged for at least {} days", days);
+        notes.push(Line::raw(note).fg(WARNING_COLOR));
+    }
+    if state.selected.is_archive() {
+        notes.push(Line::raw("Must be restored before reading").fg(WARNING_COLOR));
+    }
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::set_cells;
+
+    use super::*;
+
+    #[test]
+    fn test_render_storage_class_dialog() {
+        let mut state = StorageClassDialogState::new("STANDARD");
+        for _ in 0..5 {
+            state.select_next();
+        }
+        let dialog = StorageClassDialog::new(&state);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 16));
+        dialog.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "                                        ",
+            "  ╭Change storage class──────────────╮  ",
+            "  │ Current: STANDARD                │  ",
+            "  │                                  │  ",
+            "  │ STANDARD                         │  ",
+            "  │ INTELLIGENT_TIERING              │  ",
+            "  │ STANDARD_IA                      │  ",
+            "  │ ONEZONE_IA                       │  ",
+            "  │ GLACIER_IR                       │  ",
+            "  │ GLACIER                          │  ",
+            "  │ DEEP_ARCHIVE                     │  ",
+            "  │                                  │  ",
+            "  │ Charged for at least 90 days     │  ",
+            "  │ Must be restored before reading  │  ",
+            "  ╰──────────────────────────────────╯  ",
+            "                                        ",
+        ]);
+        set_cells! { expected =>
+            // label
+            (4..13, [2]) => modifier: Modifier::BOLD,
+            // current class
+            (4..12, [4]) => fg: Color::DarkGray,
+            // selected class
+            (4..11, [9]) => fg: Color::Cyan,
+            // notes
+            (4..32, [12]) => fg: Color::Yellow,
+            (4..35, [13]) => fg: Color::Yellow,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_storage_class_dialog_state() {
+        // the current class is selected first
+        let mut state = StorageClassDialogState::new("STANDARD_IA");
+        assert_eq!(state.selected(), StorageClass::StandardIa);
+        assert!(state.is_current_selected());
+
+        state.select_next();
+        assert_eq!(state.selected(), StorageClass::OnezoneIa);
+        assert!(!state.is_current_selected());
+
+        state.select_prev();
+        state.select_prev();
+        state.select_prev();
+        state.select_prev();
+        assert_eq!(state.selected(), StorageClass::DeepArchive);
+
+        // not selectable, e.g. REDUCED_REDUNDANCY
+        let state = StorageClassDialogState::new("REDUCED_REDUNDANCY");
+        assert_eq!(state.selected(), StorageClass::Standard);
+        assert!(!state.is_current_selected());
+    }
+}
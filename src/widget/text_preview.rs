@@ -1,10 +1,11 @@
 use ansi_to_tui::IntoText;
 use once_cell::sync::Lazy;
 use ratatui::{
-    buffer::Buffer,
+    buffer::{Buffer, Cell},
     layout::Rect,
-    text::Line,
-    widgets::{Block, StatefulWidget},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Padding, Paragraph, StatefulWidget, Widget},
 };
 use syntect::{
     easy::HighlightLines,
@@ -14,17 +15,55 @@ use syntect::{
 };
 
 use crate::{
+    encoding::{ContentEncoding, Decoded, MAX_DECODED_SIZE_BYTES},
+    image::{ImageFormat, ImageInfo},
     object::{FileDetail, RawObject},
-    util::extension_from_file_name,
+    ui::common::format_size_byte,
+    util::{
+        extension_from_file_name, find_matching_bracket_line, find_urls, hex_dump_lines,
+        BracketMatch,
+    },
     widget::{ScrollLines, ScrollLinesOptions, ScrollLinesState},
 };
 
 static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
 static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
 
+const EMPTY_CONTENT_MSG: &str = "(empty file \u{2014} 0 bytes)";
+const EMPTY_CONTENT_COLOR: Color = Color::DarkGray;
+const LINK_COLOR: Color = Color::Blue;
+
+/// A part of a URL rendered in the preview, to be written again as an OSC 8 hyperlink.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hyperlink {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub url: String,
+}
+
+pub fn link_style() -> Style {
+    Style::new()
+        .fg(LINK_COLOR)
+        .add_modifier(Modifier::UNDERLINED)
+}
+
+pub fn is_link_cell(cell: &Cell) -> bool {
+    cell.fg == LINK_COLOR && cell.modifier.contains(Modifier::UNDERLINED)
+}
+
 #[derive(Debug)]
 pub struct TextPreviewState {
     pub scroll_lines_state: ScrollLinesState,
+    empty: bool,
+    hyperlink: bool,
+    hyperlinks: Vec<Hyperlink>,
+    raw_encoded: bool,
+    encoding_note: Option<String>,
+    // false if the content is shown as hex or as the image metadata
+    text: bool,
+    // whether only the first part of the decoded content is shown
+    truncated: bool,
 }
 
 impl TextPreviewState {
@@ -32,28 +71,270 @@ impl TextPreviewState {
         file_detail: &FileDetail,
         object: &RawObject,
         highlight: bool,
+        hyperlink: bool,
+        options: ScrollLinesOptions,
+        offset: (usize, usize),
     ) -> (Self, Option<String>) {
-        let mut warn_msg = None;
-
-        let s = to_preview_string(&object.bytes);
-
-        let lines: Vec<Line<'static>> =
-            match build_highlighted_lines(&s, &file_detail.name, highlight) {
-                Ok(lines) => lines,
-                Err(msg) => {
-                    // If there is an error, display the original text
-                    if let Some(msg) = msg {
-                        warn_msg = Some(msg);
-                    }
-                    s.split('\n').map(|s| Line::raw(s.to_string())).collect()
-                }
-            };
+        let (lines, warn_msg, encoding_note, text, truncated) =
+            build_lines(file_detail, object, highlight, hyperlink, false);
 
-        let scroll_lines_state = ScrollLinesState::new(lines, ScrollLinesOptions::default());
+        let (v_offset, h_offset) = offset;
+        let scroll_lines_state =
+            ScrollLinesState::new(lines, options).with_offset(v_offset, h_offset);
 
-        let state = Self { scroll_lines_state };
+        let empty = object.bytes.is_empty();
+        let state = Self {
+            scroll_lines_state,
+            empty,
+            hyperlink,
+            hyperlinks: Vec::new(),
+            raw_encoded: false,
+            encoding_note,
+            text,
+            truncated,
+        };
         (state, warn_msg)
     }
+
+    pub fn update(
+        &mut self,
+        file_detail: &FileDetail,
+        object: &RawObject,
+        highlight: bool,
+        hyperlink: bool,
+    ) -> Option<String> {
+        let (lines, warn_msg, encoding_note, text, truncated) =
+            build_lines(file_detail, object, highlight, hyperlink, self.raw_encoded);
+        self.scroll_lines_state.replace_lines(lines);
+        self.empty = object.bytes.is_empty();
+        self.hyperlink = hyperlink;
+        self.encoding_note = encoding_note;
+        self.text = text;
+        self.truncated = truncated;
+        warn_msg
+    }
+
+    pub fn raw_encoded(&self) -> bool {
+        self.raw_encoded
+    }
+
+    /// Sets whether the encoded content is shown as is (in hex) instead of decoded, applied by the next `update`.
+    pub fn set_raw_encoded(&mut self, raw_encoded: bool) {
+        self.raw_encoded = raw_encoded;
+    }
+
+    /// Returns the shown text of the content (decoded if encoded), or `None` if it is shown as hex or as the image metadata.
+    pub fn text(&self) -> Option<String> {
+        if !self.text {
+            return None;
+        }
+        let lines = self.scroll_lines_state.lines().iter().map(Line::to_string);
+        Some(lines.collect::<Vec<_>>().join("\n"))
+    }
+
+    /// Returns whether only the first part of the decoded content is shown.
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Jumps to the bracket matching the one on the top line, or returns the message to warn.
+    pub fn jump_to_matching_bracket(&mut self) -> Result<(), String> {
+        let (v_offset, _) = self.scroll_lines_state.offset();
+        let lines: Vec<String> = self
+            .scroll_lines_state
+            .lines()
+            .iter()
+            .map(Line::to_string)
+            .collect();
+        match find_matching_bracket_line(&lines, v_offset) {
+            BracketMatch::Line(line) => {
+                self.scroll_lines_state.scroll_to_line(line);
+                Ok(())
+            }
+            BracketMatch::NoBracket => Err("No bracket to match on the top line".into()),
+            BracketMatch::Unmatched(c) => Err(format!("No matching bracket for `{}`", c)),
+        }
+    }
+
+    /// The hyperlinks in the last rendered area.
+    pub fn hyperlinks(&self) -> &[Hyperlink] {
+        &self.hyperlinks
+    }
+}
+
+// Returns the lines, the message to warn, the note of the content encoding to show in the title
+// and whether the lines are the text of the content.
+fn build_lines(
+    file_detail: &FileDetail,
+    object: &RawObject,
+    highlight: bool,
+    hyperlink: bool,
+    raw_encoded: bool,
+) -> (
+    Vec<Line<'static>>,
+    Option<String>,
+    Option<String>,
+    bool,
+    bool,
+) {
+    let encoding = file_detail
+        .content_encoding
+        .as_deref()
+        .and_then(ContentEncoding::parse);
+    let mut warn_msg = None;
+    let mut truncated = false;
+    let (bytes, encoding_note) = match encoding {
+        None => (None, None),
+        Some(_) if object.bytes.is_empty() => (None, None),
+        Some(Ok(encoding)) if raw_encoded => {
+            let note = format!("{}, raw", encoding.name());
+            return (hex_lines(&object.bytes), None, Some(note), false, false);
+        }
+        Some(Ok(encoding)) => match encoding.decode(&object.bytes, MAX_DECODED_SIZE_BYTES) {
+            Ok(Decoded {
+                bytes,
+                truncated: decoded_truncated,
+            }) => {
+                truncated = decoded_truncated;
+                let mut note = format!("{} decoded", encoding.name());
+                if truncated {
+                    note.push_str(", truncated");
+                    warn_msg = Some(format!(
+                        "Only the first {} of the decoded content are shown",
+                        format_size_byte(MAX_DECODED_SIZE_BYTES)
+                    ));
+                }
+                (Some(bytes), Some(note))
+            }
+            Err(_) => {
+                let note = format!("{}, raw", encoding.name());
+                let msg = format!(
+                    "Failed to decode {} content (truncated or corrupt), showing as hex",
+                    encoding.name()
+                );
+                return (
+                    hex_lines(&object.bytes),
+                    Some(msg),
+                    Some(note),
+                    false,
+                    false,
+                );
+            }
+        },
+        Some(Err(value)) => {
+            let note = format!("{}, raw", value);
+            let msg = format!(
+                "Content encoding `{}` is not supported, showing as hex",
+                value
+            );
+            return (
+                hex_lines(&object.bytes),
+                Some(msg),
+                Some(note),
+                false,
+                false,
+            );
+        }
+    };
+    let bytes = bytes.as_deref().unwrap_or(&object.bytes);
+
+    let image_format = ImageFormat::detect(&file_detail.content_type, &file_detail.name);
+    if let (Some(format), false) = (image_format, bytes.is_empty()) {
+        let (lines, image_warn_msg) = build_image_lines(format, file_detail, bytes);
+        return (
+            lines,
+            warn_msg.or(image_warn_msg),
+            encoding_note,
+            false,
+            truncated,
+        );
+    }
+
+    let s = to_preview_string(bytes);
+
+    let lines: Vec<Line<'static>> = match build_highlighted_lines(&s, &file_detail.name, highlight)
+    {
+        Ok(lines) => lines,
+        Err(msg) => {
+            // If there is an error, display the original text
+            if let Some(msg) = msg {
+                warn_msg.get_or_insert(msg);
+            }
+            s.split('\n').map(|s| Line::raw(s.to_string())).collect()
+        }
+    };
+
+    let lines = if hyperlink {
+        lines.into_iter().map(style_urls).collect()
+    } else {
+        lines
+    };
+
+    (lines, warn_msg, encoding_note, true, truncated)
+}
+
+fn hex_lines(bytes: &[u8]) -> Vec<Line<'static>> {
+    hex_dump_lines(bytes).into_iter().map(Line::raw).collect()
+}
+
+// images cannot be shown as text, so only the metadata in the header is shown
+fn build_image_lines(
+    format: ImageFormat,
+    file_detail: &FileDetail,
+    bytes: &[u8],
+) -> (Vec<Line<'static>>, Option<String>) {
+    match ImageInfo::decode(bytes) {
+        Some(info) => {
+            let lines = [
+                ("Format", info.format.name().to_string()),
+                ("Dimensions", format!("{} x {}", info.width, info.height)),
+                ("Color", info.color),
+                ("Size", format_size_byte(file_detail.size_byte)),
+            ]
+            .into_iter()
+            .map(|(label, value)| Line::from(vec![format!("{}: ", label).bold(), value.into()]))
+            .collect();
+            (lines, None)
+        }
+        None => {
+            let lines = hex_lines(bytes);
+            let msg = format!("Failed to decode {} header, showing as hex", format.name());
+            (lines, Some(msg))
+        }
+    }
+}
+
+// only the style is changed, so the width of the line is kept as is
+fn style_urls(line: Line<'static>) -> Line<'static> {
+    let content: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+    let urls = find_urls(&content);
+    if urls.is_empty() {
+        return line;
+    }
+
+    let mut spans = Vec::new();
+    let mut start = 0;
+    for span in line.spans {
+        let end = start + span.content.len();
+        let mut pos = start;
+        for url in urls.iter().filter(|u| u.start < end && u.end > start) {
+            let (url_start, url_end) = (url.start.max(start), url.end.min(end));
+            if pos < url_start {
+                spans.push(Span::styled(
+                    content[pos..url_start].to_string(),
+                    span.style,
+                ));
+            }
+            let style = span.style.patch(link_style());
+            spans.push(Span::styled(content[url_start..url_end].to_string(), style));
+            pos = url_end;
+        }
+        if pos < end {
+            spans.push(Span::styled(content[pos..end].to_string(), span.style));
+        }
+        start = end;
+    }
+    Line { spans, ..line }
 }
 
 fn to_preview_string(bytes: &[u8]) -> String {
@@ -113,13 +394,97 @@ impl StatefulWidget for TextPreview<'_> {
     type State = TextPreviewState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let title = if let Some(version_id) = self.file_version_id {
+        let mut title = if let Some(version_id) = self.file_version_id {
             format!("Preview [{} (Version ID: {})]", self.file_name, version_id)
         } else {
             format!("Preview [{}]", self.file_name)
         };
+        if let Some(note) = &state.encoding_note {
+            title = format!("{} ({})", title, note);
+        }
+        let block = Block::bordered().title(title);
+        state.hyperlinks.clear();
+        if state.empty {
+            // distinguish from the content that is still loading
+            Paragraph::new(Line::from(EMPTY_CONTENT_MSG).fg(EMPTY_CONTENT_COLOR))
+                .block(block.padding(Padding::horizontal(1)))
+                .render(area, buf);
+            return;
+        }
+        let content_area = block.inner(area);
         ScrollLines::default()
-            .block(Block::bordered().title(title))
+            .block(block)
             .render(area, buf, &mut state.scroll_lines_state);
+
+        if state.hyperlink {
+            let (v_offset, _) = state.scroll_lines_state.offset();
+            let urls: Vec<String> = state
+                .scroll_lines_state
+                .lines()
+                .iter()
+                .skip(v_offset)
+                .take(content_area.height as usize)
+                .flat_map(|line| {
+                    let content = line.to_string();
+                    find_urls(&content)
+                        .into_iter()
+                        .map(move |r| content[r].to_string())
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            state.hyperlinks = collect_hyperlinks(content_area, buf, &urls);
+        }
+    }
+}
+
+// Find the link cells from the rendered buffer, since the positions of the lines are determined by wrapping.
+// A link at the end of a row is joined with a link at the start of the next row, if it is a wrapped line.
+fn collect_hyperlinks(area: Rect, buf: &Buffer, urls: &[String]) -> Vec<Hyperlink> {
+    let is_blank = |x: u16, y: u16| buf.get(x, y).symbol().trim().is_empty();
+
+    let mut hyperlinks = Vec::new();
+    let mut segments: Vec<(u16, u16, u16)> = Vec::new();
+    let mut text = String::new();
+    let mut flush = |segments: &mut Vec<(u16, u16, u16)>, text: &mut String| {
+        // the text may be a part of the URL if it is scrolled horizontally
+        if let Some(url) = urls.iter().find(|url| url.contains(text.as_str())) {
+            hyperlinks.extend(segments.iter().map(|(x, y, width)| Hyperlink {
+                x: *x,
+                y: *y,
+                width: *width,
+                url: url.clone(),
+            }));
+        }
+        segments.clear();
+        text.clear();
+    };
+
+    for y in area.top()..area.bottom() {
+        if !segments.is_empty() {
+            let first = (area.left()..area.right()).find(|x| !is_blank(*x, y));
+            if !first.is_some_and(|x| is_link_cell(buf.get(x, y))) {
+                flush(&mut segments, &mut text);
+            }
+        }
+        let mut x = area.left();
+        while x < area.right() {
+            if !is_link_cell(buf.get(x, y)) {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < area.right() && is_link_cell(buf.get(x, y)) {
+                text.push_str(buf.get(x, y).symbol());
+                x += 1;
+            }
+            segments.push((start, y, x - start));
+            if !(x..area.right()).all(|x| is_blank(x, y)) {
+                flush(&mut segments, &mut text);
+            }
+        }
+    }
+    if !segments.is_empty() {
+        flush(&mut segments, &mut text);
     }
+    hyperlinks
 }
@@ -0,0 +1,212 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Stylize},
+    text::{Line, Span},
+    widgets::{block::Title, Block, BorderType, Padding, Paragraph, Widget, WidgetRef},
+};
+
+use crate::{
+    object::FileDetail,
+    ui::common::{calc_centered_dialog_rect, format_datetime, format_size_byte, format_version},
+    util::{pad_to_width, truncate_with_ellipsis},
+    widget::Dialog,
+};
+
+const CHANGED_COLOR: Color = Color::Yellow;
+const UNCHANGED_COLOR: Color = Color::DarkGray;
+const LABEL_WIDTH: usize = 15;
+
+#[derive(Debug)]
+pub struct VersionDiffDialogState {
+    old_version_id: String,
+    new_version_id: String,
+    old_detail: FileDetail,
+    new_detail: FileDetail,
+}
+
+impl VersionDiffDialogState {
+    pub fn new(
+        old_version_id: String,
+        new_version_id: String,
+        old_detail: FileDetail,
+        new_detail: FileDetail,
+    ) -> Self {
+        Self {
+            old_version_id,
+            new_version_id,
+            old_detail,
+            new_detail,
+        }
+    }
+
+    fn diff_rows(&self) -> Vec<(&'static str, String, String)> {
+        let (o, n) = (&self.old_detail, &self.new_detail);
+        vec![
+            (
+                "Size",
+                format_size_byte(o.size_byte),
+                format_size_byte(n.size_byte),
+            ),
+            (
+                "Content-Type",
+                o.content_type.clone(),
+                n.content_type.clone(),
+            ),
+            (
+                "Last Modified",
+                format_datetime(&o.last_modified),
+                format_datetime(&n.last_modified),
+            ),
+            ("ETag", o.e_tag.clone(), n.e_tag.clone()),
+            (
+                "Storage Class",
+                o.storage_class.clone(),
+                n.storage_class.clone(),
+            ),
+        ]
+    }
+}
+
+pub struct VersionDiffDialog<'a> {
+    state: &'a VersionDiffDialogState,
+}
+
+impl<'a> VersionDiffDialog<'a> {
+    pub fn new(state: &'a VersionDiffDialogState) -> Self {
+        Self { state }
+    }
+}
+
+impl Widget for VersionDiffDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = self.state.diff_rows();
+
+        let dialog_width = area.width.saturating_sub(4).min(90);
+        let dialog_height = 1 /* header */ + rows.len() as u16 + 2 /* border */;
+        let area = calc_centered_dialog_rect(area, dialog_width, dialog_height);
+
+        let content_width = dialog_width.saturating_sub(4) as usize;
+        let value_width = content_width.saturating_sub(LABEL_WIDTH + 1) / 2;
+
+        let header = Line::from(vec![
+            Span::raw(" ".repeat(LABEL_WIDTH)),
+            Span::raw(fit(format_version(&self.state.old_version_id), value_width)),
+            Span::raw(" "),
+            Span::raw(fit(format_version(&self.state.new_version_id), value_width)),
+        ])
+        .add_modifier(Modifier::BOLD);
+
+        let mut lines = vec![header];
+        lines.extend(rows.iter().map(|(name, old, new)| {
+            let label = format!("{:>w$}: ", name, w = LABEL_WIDTH - 2);
+            let line = Line::from(vec![
+                Span::raw(label).add_modifier(Modifier::BOLD),
+                Span::raw(fit(old, value_width)),
+                Span::raw(" "),
+                Span::raw(fit(new, value_width)),
+            ]);
+            if old == new {
+                line.fg(UNCHANGED_COLOR)
+            } else {
+                line.fg(CHANGED_COLOR)
+            }
+        }));
+
+        let title = Title::from("Compare versions");
+        let paragraph = Paragraph::new(lines).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .padding(Padding::horizontal(1)),
+        );
+        let dialog = Dialog::new(Box::new(paragraph));
+        dialog.render_ref(area, buf);
+    }
+}
+
+fn fit(s: &str, width: usize) -> String {
+    pad_to_width(&truncate_with_ellipsis(s, width), width)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Local, NaiveDateTime};
+
+    use crate::set_cells;
+
+    use super::*;
+
+    #[test]
+    fn test_render_version_diff_dialog() {
+        let old_detail = file_detail(1024, "text/plain", "2024-01-02 13:01:02", "etag1");
+        let new_detail = file_detail(2048, "text/plain", "2024-01-03 13:01:02", "etag2");
+        let state = VersionDiffDialogState::new("v1".into(), "v2".into(), old_detail, new_detail);
+        let dialog = VersionDiffDialog::new(&state);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 64, 8));
+        dialog.render(buf.area, &mut buf);
+
+        #[rustfmt::skip]
+        let mut expected = Buffer::with_lines([
+            "  ╭Compare versions──────────────────────────────────────────╮  ",
+            "  │                v1                   v2                   │  ",
+            "  │          Size: 1 KiB                2 KiB                │  ",
+            "  │  Content-Type: text/plain           text/plain           │  ",
+            "  │ Last Modified: 2024-01-02 13:01:02  2024-01-03 13:01:02  │  ",
+            "  │          ETag: etag1                etag2                │  ",
+            "  │ Storage Class: STANDARD             STANDARD             │  ",
+            "  ╰──────────────────────────────────────────────────────────╯  ",
+        ]);
+        set_cells! { expected =>
+            (4..60, [1]) => modifier: Modifier::BOLD,
+            (4..19, [2, 3, 4, 5, 6]) => modifier: Modifier::BOLD,
+            // changed
+            (4..60, [2, 4, 5]) => fg: Color::Yellow,
+            // unchanged
+            (4..60, [3, 6]) => fg: Color::DarkGray,
+        }
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_fit() {
+        assert_eq!(fit("text/plain", 12), "text/plain  ");
+        assert_eq!(fit("text/plain", 8), "text/pl…");
+        assert_eq!(fit("テキスト", 10), "テキスト  ");
+        assert_eq!(fit("テキスト", 6), "テキ… ");
+    }
+
+    fn file_detail(
+        size_byte: usize,
+        content_type: &str,
+        last_modified: &str,
+        e_tag: &str,
+    ) -> FileDetail {
+        FileDetail {
+            name: "file.txt".to_string(),
+            size_byte,
+            last_modified: parse_datetime(last_modified),
+            e_tag: e_tag.to_string(),
+            content_type: content_type.to_string(),
+            storage_class: "STANDARD".to_string(),
+            encryption: None,
+            kms_key_id: None,
+            bucket_key_enabled: None,
+            content_encoding: None,
+            key: "file.txt".to_string(),
+            s3_uri: "s3://bucket-1/file.txt".to_string(),
+            arn: "arn:aws:s3:::bucket-1/file.txt".to_string(),
+            object_url: "https://bucket-1.s3.ap-northeast-1.amazonaws.com/file.txt".to_string(),
+            restore_status: None,
+        }
+    }
+
+    fn parse_datetime(s: &str) -> DateTime<Local> {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+    }
+}